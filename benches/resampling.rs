@@ -0,0 +1,55 @@
+extern crate criterion;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::{hint::black_box, time::Duration};
+
+use ionex::prelude::{Grid, GridResampler, IONEX, Linspace};
+
+/// Re-grids all maps, sharing the bilinear weights
+fn resampler(ionex: &IONEX, target: &Grid) {
+    let resampler = GridResampler::new(&ionex.header.grid, target).unwrap();
+    black_box(ionex.resample_with(&resampler).unwrap());
+}
+
+/// Re-grids all maps, interpolating each node individually, for comparison
+fn naive(ionex: &IONEX, target: &Grid) {
+    for epoch in ionex.epoch_iter() {
+        for lat in target.latitude.iter() {
+            for long in target.longitude.iter() {
+                black_box(ionex.interpolate_tec(epoch, lat, long).unwrap());
+            }
+        }
+    }
+}
+
+fn benchmark(c: &mut Criterion) {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap();
+
+    // twice finer grid
+    let target = Grid {
+        latitude: Linspace::from((87.5, -87.5, -1.25)),
+        longitude: Linspace::from((-180.0, 180.0, 2.5)),
+        altitude: ionex.header.grid.altitude,
+    };
+
+    let mut resampling_grp = c.benchmark_group("resampling");
+
+    resampling_grp.measurement_time(Duration::from_secs(20));
+
+    resampling_grp.bench_function("resampler", |b| {
+        b.iter(|| {
+            resampler(&ionex, &target);
+        })
+    });
+
+    resampling_grp.bench_function("naive", |b| {
+        b.iter(|| {
+            naive(&ionex, &target);
+        })
+    });
+
+    resampling_grp.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+extern crate criterion;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::time::Duration;
+
+use ionex::prelude::IONEX;
+
+/// Browses all maps using the range based synchronous iterator
+fn synchronous_range(ionex: &IONEX) {
+    for epoch in ionex.epoch_iter() {
+        for (k, v) in ionex.record.synchronous_iter(epoch) {
+            black_box((k, v));
+        }
+    }
+}
+
+/// Browses all maps by filtering the entire record, for comparison
+fn synchronous_filter(ionex: &IONEX) {
+    for epoch in ionex.epoch_iter() {
+        for (k, v) in ionex.record.iter().filter(|(k, _)| k.epoch == epoch) {
+            black_box((k, v));
+        }
+    }
+}
+
+fn benchmark(c: &mut Criterion) {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap();
+
+    let mut synchronous_grp = c.benchmark_group("synchronous");
+
+    synchronous_grp.measurement_time(Duration::from_secs(20));
+
+    synchronous_grp.bench_function("range", |b| {
+        b.iter(|| {
+            synchronous_range(&ionex);
+        })
+    });
+
+    synchronous_grp.bench_function("filter", |b| {
+        b.iter(|| {
+            synchronous_filter(&ionex);
+        })
+    });
+
+    synchronous_grp.bench_function("map_cell_iter", |b| {
+        b.iter(|| {
+            for cell in ionex.map_cell_iter() {
+                black_box(cell);
+            }
+        })
+    });
+
+    synchronous_grp.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
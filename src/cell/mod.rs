@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use geo::{Contains, GeodesicArea, Geometry, Point, Rect};
 
-use crate::prelude::{Epoch, Error, TEC};
+use crate::{
+    interpolation::corner_weights,
+    prelude::{Epoch, Error, TEC},
+};
 
 mod three_by_three;
 pub use three_by_three::Cell3x3;
@@ -154,12 +157,16 @@ impl MapCell {
             && rhs.north_west.point == self.south_west.point
     }
 
+    /// Returns true if self is the northwestern neighbor of provided (rhs) [MapCell],
+    /// meaning, they only share one corner.
     pub fn is_northwestern_neighbor(&self, rhs: &Self) -> bool {
-        false // TODO
+        rhs.north_west.point == self.south_east.point
     }
 
+    /// Returns true if self is the northeastern neighbor of provided (rhs) [MapCell],
+    /// meaning, they only share one corner.
     pub fn is_northeastern_neighbor(&self, rhs: &Self) -> bool {
-        false // TODO
+        rhs.north_east.point == self.south_west.point
     }
 
     /// Returns true if self is the southern neighbor of provided (rhs) [MapCell].
@@ -168,12 +175,16 @@ impl MapCell {
             && rhs.south_west.point == self.north_west.point
     }
 
+    /// Returns true if self is the southeastern neighbor of provided (rhs) [MapCell],
+    /// meaning, they only share one corner.
     pub fn is_southeastern_neighbor(&self, rhs: &Self) -> bool {
-        false // TODO
+        rhs.south_east.point == self.north_west.point
     }
 
+    /// Returns true if self is the southwestern neighbor of provided (rhs) [MapCell],
+    /// meaning, they only share one corner.
     pub fn is_southwestern_neighbor(&self, rhs: &Self) -> bool {
-        false // TODO
+        rhs.south_west.point == self.north_east.point
     }
 
     /// Returns true if self is the easthern neighbor of provided (rhs) [MapCell].
@@ -188,7 +199,8 @@ impl MapCell {
             && rhs.south_east.point == self.south_west.point
     }
 
-    /// Returns true if both cells are neighbors, meaning, they share two corners.
+    /// Returns true if both cells are neighbors, meaning, they share two corners
+    /// (one edge) or a single corner (diagonal neighbors).
     pub fn is_neighbor(&self, rhs: &Self) -> bool {
         self.is_northern_neighbor(rhs)
             || self.is_northwestern_neighbor(rhs)
@@ -356,10 +368,13 @@ impl MapCell {
         borders.max().x - borders.min().x
     }
 
-    /// Spatial interpolation of the [TEC] value using planery equation
-    /// and 4 boundaries of this [MapCell]. [MapCell::contains] should be true
-    /// for the proposed geometry for this to be correct.
-    /// This method does not verify this assertion, it is up to you to use valid coordinates here.
+    /// Spatial (bilinear) interpolation of the [TEC] value at this point, expressed in absolute
+    /// coordinates (decimal degrees), using the 4 corners of this [MapCell].
+    /// The point must lie within this [MapCell] (boundaries included),
+    /// otherwise [Error::OutsideSpatialBoundaries] is returned.
+    /// The corners are weighted by their actual coordinates, so this applies to any
+    /// [MapCell] whatever the orientation of the grid it was gathered from.
+    /// RMS is interpolated with the same weights, when all corners have one.
     ///
     /// Example: unitary cell
     /// ```
@@ -373,11 +388,11 @@ impl MapCell {
     ///
     /// // central point
     /// let center = Point::new(0.5, 0.5);
-    /// let tec = cell.spatial_tec_interp(center);
+    /// let tec = cell.spatial_tec_interp(center).unwrap();
     /// assert_eq!(tec.tecu(), 1.0);
     /// ```
     ///
-    /// Example: North East gradient
+    /// Example: South West gradient
     /// ```
     /// use ionex::prelude::{MapCell, Epoch, Point, TEC, Unit};
     ///
@@ -394,41 +409,40 @@ impl MapCell {
     /// let cell = MapCell::from_unitary_tec(t0, gradient.0, gradient.1, gradient.2, gradient.3);
     ///
     /// // central point
-    /// let tec = cell.spatial_tec_interp(Point::new(0.5, 0.5));
+    /// let tec = cell.spatial_tec_interp(Point::new(0.5, 0.5)).unwrap();
     /// assert_eq!(tec.tecu(), 0.25);
     ///
     /// // SW boundary
-    /// let tec = cell.spatial_tec_interp(Point::new(0.0, 0.0));
+    /// let tec = cell.spatial_tec_interp(Point::new(0.0, 0.0)).unwrap();
     /// assert_eq!(tec.tecu(), 1.0);
     ///
     /// // SWern point
-    /// let tec = cell.spatial_tec_interp(Point::new(0.1, 0.1));
-    /// assert_eq!(tec.tecu(), 0.81);
+    /// let tec = cell.spatial_tec_interp(Point::new(0.1, 0.1)).unwrap();
+    /// assert!((tec.tecu() - 0.81).abs() < 1.0E-9);
     ///
     /// // SWwern point
-    /// let tec = cell.spatial_tec_interp(Point::new(0.01, 0.01));
-    /// assert_eq!(tec.tecu(), 0.9801);
+    /// let tec = cell.spatial_tec_interp(Point::new(0.01, 0.01)).unwrap();
+    /// assert!((tec.tecu() - 0.9801).abs() < 1.0E-9);
+    ///
+    /// // outside of the cell
+    /// assert!(cell.spatial_tec_interp(Point::new(1.5, 0.5)).is_err());
     /// ```
     pub fn spatial_tec_interp(&self, point: Point<f64>) -> Result<TEC, Error> {
-        if !self.contains(&Geometry::Point(point)) {
-            return Err(Error::OutsideSpatialBoundaries);
-        }
-
-        let (latitude_span, longitude_span) = self.latitude_longitude_span_degrees();
-
-        let (p, q) = (point.y() / latitude_span, point.x() / longitude_span);
-
-        let (e00, e10, e01, e11) = (
-            self.south_west.tec.tecu(),
-            self.south_east.tec.tecu(),
-            self.north_west.tec.tecu(),
-            self.north_east.tec.tecu(),
-        );
-
-        let tecu =
-            (1.0 - p) * (1.0 - q) * e00 + p * (1.0 - q) * e10 + q * (1.0 - p) * e01 + p * q * e11;
-
-        Ok(TEC::from_tecu(tecu))
+        let corners = [
+            self.north_east,
+            self.north_west,
+            self.south_east,
+            self.south_west,
+        ];
+
+        let weights = corner_weights(corners.map(|corner| corner.point), point)?;
+
+        Ok(TEC::interpolated(
+            corners
+                .into_iter()
+                .zip(weights)
+                .map(|(corner, weight)| (corner.tec, weight)),
+        ))
     }
 
     /// Returns a stretched (either upscaled or downscaled, resized in dimension) ROI,
@@ -442,6 +456,11 @@ impl MapCell {
             return Err(Error::InvalidStretchFactor);
         }
 
+        // unitary stretching is an exact no-op
+        if (factor - 1.0).abs() < f64::EPSILON {
+            return Ok(());
+        }
+
         // apply interpolation eq. at 4 coordinates
         let (north_east, north_west, south_east, south_west) = (
             Point::new(
@@ -644,118 +663,100 @@ impl MapCell {
     //     }
     // }
 
-    // /// Merges two neighboring [MapCell]s forming a new (upscaled) [MapCell].
-    // /// Both cells must be synchronous.
-    // pub fn merge_neighbors(&self, rhs: &Self) -> Result<Self, Error> {
-    //     if !self.temporal_match(rhs) {
-    //         return Err(Error::TemporalMismatch);
-    //     }
+    /// Merges two neighboring [MapCell]s forming a new (upscaled) [MapCell],
+    /// the bounding rectangle of both cells. Both cells must be synchronous
+    /// (otherwise [Error::TemporalMismatch] is returned) and share one edge
+    /// (otherwise [Error::SpatialMismatch] is returned): diagonal neighbors can't be merged.
+    /// The corners of the merged cell are the outer corners of both cells,
+    /// so their TEC values are preserved.
+    pub fn merge_neighbors(&self, rhs: &Self) -> Result<Self, Error> {
+        if !self.temporal_match(rhs) {
+            return Err(Error::TemporalMismatch);
+        }
 
-    //     // 1: determine the matching corners. The center
-    //     // point of the matching line is to become the new center.
-    //     // 2: form a new cell, of the boundary rect, interpolate
-    //     // both TEC at the new center
-
-    //     if self.is_northern_neighbor(rhs) {
-    //         Ok(Self::default()) // TODO
-    //     } else if self.is_southern_neighbor(rhs) {
-    //         Ok(Self::default()) // TODO
-    //     } else if self.is_western_neighbor(rhs) {
-    //         Ok(Self::default()) // TODO
-    //     } else if self.is_eastern_neighbor(rhs) {
-    //         Ok(Self::default()) // TODO
-    //     } else {
-    //         Err(Error::SpatialMismatch)
-    //     }
-    // }
+        let (north_east, north_west, south_east, south_west) = if self.is_northern_neighbor(rhs) {
+            (
+                self.north_east,
+                self.north_west,
+                rhs.south_east,
+                rhs.south_west,
+            )
+        } else if self.is_southern_neighbor(rhs) {
+            (
+                rhs.north_east,
+                rhs.north_west,
+                self.south_east,
+                self.south_west,
+            )
+        } else if self.is_eastern_neighbor(rhs) {
+            (
+                self.north_east,
+                rhs.north_west,
+                self.south_east,
+                rhs.south_west,
+            )
+        } else if self.is_western_neighbor(rhs) {
+            (
+                rhs.north_east,
+                self.north_west,
+                rhs.south_east,
+                self.south_west,
+            )
+        } else {
+            return Err(Error::SpatialMismatch);
+        };
 
-    // /// Interpolates two [MapCell]s that must describe the same area,
-    // /// but a different point in time.
-    // ///
-    // /// ## Input
-    // /// - epoch: [Epoch] of interpolation, must be temporally in between
-    // /// [Self] and rhs.
-    // pub fn temporally_interpolated(&self, epoch: Epoch, rhs: &Self) -> Result<Self, Error> {
-    //     if !self.spatial_match(rhs) {
-    //         return Err(Error::SpatialMismatch);
-    //     }
+        Ok(Self::from_cardinal_points(
+            self.epoch, north_east, north_west, south_east, south_west,
+        ))
+    }
 
-    //     let (min_t, max_t) =
-    //     (
-    //         std::cmp::min(self.epoch, rhs.epoch),
-    //         std::cmp::max(self.epoch, rhs.epoch),
-    //     );
+    /// Interpolates two [MapCell]s that must describe the same area
+    /// (see [Self::spatial_match], otherwise [Error::SpatialMismatch] is returned),
+    /// but a different point in time. Each corner [TEC] is linearly interpolated
+    /// and the corner points of [Self] are preserved.
+    ///
+    /// ## Input
+    /// - epoch: [Epoch] of interpolation, must be temporally in between
+    ///   [Self] and rhs (both included), otherwise [Error::TemporalMismatch] is returned.
+    /// - rhs: [MapCell] to interpolate with.
+    pub fn temporally_interpolated(&self, epoch: Epoch, rhs: &Self) -> Result<Self, Error> {
+        if !self.spatial_match(rhs) {
+            return Err(Error::SpatialMismatch);
+        }
 
-    //     if epoch < min_t && epoch > max_t {
-    //         return Err(Error::InvalidTemporalPoint);
-    //     }
+        let (min_t, max_t) = (
+            std::cmp::min(self.epoch, rhs.epoch),
+            std::cmp::max(self.epoch, rhs.epoch),
+        );
 
-    //     let (num_1, num_2, dt) = if self.epoch > rhs.epoch {
-    //         (
-    //             (self.epoch - epoch).to_seconds(),
-    //             (epoch - rhs.epoch).to_seconds(),
-    //             (self.epoch - rhs.epoch).to_seconds(),
-    //         )
-    //     } else {
-    //         (
-    //             (rhs.epoch - epoch).to_seconds(),
-    //             (epoch - self.epoch).to_seconds(),
-    //             (rhs.epoch - self.epoch).to_seconds(),
-    //         )
-    //     };
-
-    //     let (ne_1, ne_2) = if self.epoch > rhs.epoch {
-    //        (self.north_east.tec.tecu(), rhs.north_east.tec.tecu())
-    //     } else {
-    //        (rhs.north_east.tec.tecu(), self.north_east.tec.tecu())
-    //     };
-    //
-    //     let (nw_1, nw_2) = if self.epoch > rhs.epoch {
-    //        (self.north_west.tec.tecu(), rhs.north_west.tec.tecu())
-    //     } else {
-    //        (rhs.north_west.tec.tecu(), self.north_west.tec.tecu())
-    //     };
+        if epoch < min_t || epoch > max_t {
+            return Err(Error::TemporalMismatch);
+        }
 
-    //     let (se_1, se_2) = if self.epoch > rhs.epoch {
-    //        (self.south_east.tec.tecu(), rhs.south_east.tec.tecu())
-    //     } else {
-    //        (rhs.south_east.tec.tecu(), self.south_east.tec.tecu())
-    //     };
-    //
-    //     let (sw_1, sw_2) = if self.epoch > rhs.epoch {
-    //        (self.south_west.tec.tecu(), rhs.south_west.tec.tecu())
-    //     } else {
-    //        (rhs.south_west.tec.tecu(), self.south_west.tec.tecu())
-    //     };
-
-    //     Ok(Self {
-    //         epoch,
-    //         north_east: TecPoint {
-    //             point: self.north_east.point,
-    //             tec: TEC::from_tecu(
-    //                 num_1 * ne_1 /dt + num_2 * ne_2 /dt
-    //             ),
-    //         },
-    //         north_west: TecPoint {
-    //             point: self.north_west.point,
-    //             tec: TEC::from_tecu(
-    //                 num_1 * nw_1 /dt + num_2 * nw_2 /dt
-    //             ),
-    //         },
-    //         south_east: TecPoint {
-    //             point: self.south_east.point,
-    //             tec: TEC::from_tecu(
-    //                 num_1 * se_1 /dt + num_2 * se_2 /dt
-    //             ),
-    //         },
-    //         south_west: TecPoint {
-    //             point: self.south_west.point,
-    //             tec: TEC::from_tecu(
-    //                 num_1 * sw_1 /dt + num_2 * sw_2 /dt
-    //             ),
-    //         },
-    //     })
-    // }
+        // synchronous cells: nothing to interpolate
+        if min_t == max_t {
+            return Ok(*self);
+        }
+
+        let dt = (rhs.epoch - self.epoch).to_seconds();
+
+        // weight of rhs, self weight being its complement
+        let weight = (epoch - self.epoch).to_seconds() / dt;
+
+        let interpolated = |lhs: &TecPoint, rhs: &TecPoint| TecPoint {
+            point: lhs.point,
+            tec: TEC::interpolated([(lhs.tec, 1.0 - weight), (rhs.tec, weight)]),
+        };
+
+        Ok(Self {
+            epoch,
+            north_east: interpolated(&self.north_east, &rhs.north_east),
+            north_west: interpolated(&self.north_west, &rhs.north_west),
+            south_east: interpolated(&self.south_east, &rhs.south_east),
+            south_west: interpolated(&self.south_west, &rhs.south_west),
+        })
+    }
 
     /// Spatial + Temporal Interpolation of [TEC] value using planery equation
     /// and rhs [MapCell], which should be closely sampled in time.
@@ -803,27 +804,27 @@ impl MapCell {
         rhs: &Self,
     ) -> Result<TEC, Error> {
         // interpolate at exact coordinates
-        let (tecu_0, tecu_1) = (
-            self.spatial_tec_interp(coordinates)?.tecu(),
-            rhs.spatial_tec_interp(coordinates)?.tecu(),
+        let (tec_0, tec_1) = (
+            self.spatial_tec_interp(coordinates)?,
+            rhs.spatial_tec_interp(coordinates)?,
         );
 
         if epoch >= self.epoch && epoch < rhs.epoch {
             // forward
             let dt = (rhs.epoch - self.epoch).to_seconds();
 
-            let tecu = (rhs.epoch - epoch).to_seconds() / dt * tecu_0
-                + (epoch - self.epoch).to_seconds() / dt * tecu_1;
-
-            Ok(TEC::from_tecu(tecu))
+            Ok(TEC::interpolated([
+                (tec_0, (rhs.epoch - epoch).to_seconds() / dt),
+                (tec_1, (epoch - self.epoch).to_seconds() / dt),
+            ]))
         } else if epoch >= rhs.epoch && epoch < self.epoch {
             // backwards
             let dt = (self.epoch - rhs.epoch).to_seconds();
 
-            let tecu = (self.epoch - epoch).to_seconds() / dt * tecu_1
-                + (epoch - rhs.epoch).to_seconds() / dt * tecu_0;
-
-            Ok(TEC::from_tecu(tecu))
+            Ok(TEC::interpolated([
+                (tec_1, (self.epoch - epoch).to_seconds() / dt),
+                (tec_0, (epoch - rhs.epoch).to_seconds() / dt),
+            ]))
         } else {
             Err(Error::TemporalMismatch)
         }
@@ -872,6 +873,27 @@ mod test {
         assert_eq!(interpolated.tecu(), 1.0);
     }
 
+    #[test]
+    fn unitary_stretching() {
+        let cell = MapCell::from_unitary_tec(
+            Epoch::default(),
+            TEC::from_tecu(1.1),
+            TEC::from_tecu(2.2),
+            TEC::from_tecu(3.3),
+            TEC::from_tecu(4.4),
+        );
+
+        let stretched = cell.stretched(1.0).unwrap();
+        assert_eq!(stretched, cell);
+
+        let mut stretched = cell;
+        stretched.stretching_mut(1.0).unwrap();
+        stretched.stretching_mut(1.0).unwrap();
+        assert_eq!(stretched, cell);
+
+        assert!(cell.stretched(0.0).is_err());
+    }
+
     #[test]
     fn spatial_south_west_gradient_interpolation() {
         let epoch = Epoch::default();
@@ -959,4 +981,133 @@ mod test {
 
         assert_eq!(tec.tecu(), 1.0);
     }
+
+    #[test]
+    fn temporally_interpolated_cell() {
+        let t0 = Epoch::default();
+        let t1 = t0 + 1.0 * Unit::Second;
+
+        let t_ok = t0 + 0.25 * Unit::Second;
+        let t_nok = t1 + 2.0 * Unit::Second;
+
+        let cell0 = MapCell::from_unitary_tec(
+            t0,
+            TEC::from_tecu(1.0),
+            TEC::from_tecu(2.0),
+            TEC::from_tecu(3.0),
+            TEC::from_tecu(4.0),
+        );
+
+        let cell1 = MapCell::from_unitary_tec(
+            t1,
+            TEC::from_tecu(5.0),
+            TEC::from_tecu(6.0),
+            TEC::from_tecu(7.0),
+            TEC::from_tecu(8.0),
+        );
+
+        assert!(
+            matches!(
+                cell0.temporally_interpolated(t_nok, &cell1),
+                Err(Error::TemporalMismatch)
+            ),
+            "interpolation is temporally incorrect!"
+        );
+
+        for (lhs, rhs) in [(&cell0, &cell1), (&cell1, &cell0)] {
+            let cell = lhs.temporally_interpolated(t_ok, rhs).unwrap_or_else(|e| {
+                panic!("should have been feasible! {}", e);
+            });
+
+            assert_eq!(cell.epoch, t_ok);
+            assert!(cell.spatial_match(&cell0));
+
+            assert_eq!(cell.north_east.tec.tecu(), 2.0);
+            assert_eq!(cell.north_west.tec.tecu(), 3.0);
+            assert_eq!(cell.south_east.tec.tecu(), 4.0);
+            assert_eq!(cell.south_west.tec.tecu(), 5.0);
+        }
+
+        // boundaries are included
+        let cell = cell0.temporally_interpolated(t1, &cell1).unwrap();
+        assert_eq!(cell.north_east.tec.tecu(), 5.0);
+
+        // cells must describe the same area
+        let mut shifted = cell1;
+        shifted.north_east.point = Point::new(2.0, 2.0);
+
+        assert!(matches!(
+            cell0.temporally_interpolated(t_ok, &shifted),
+            Err(Error::SpatialMismatch)
+        ));
+    }
+
+    #[test]
+    fn merge_neighbors() {
+        let t0 = Epoch::default();
+
+        // unitary cell whose south western corner is (x, y), valued x + 10y
+        let cell = |epoch: Epoch, x: f64, y: f64| {
+            let corner = |x: f64, y: f64| TecPoint {
+                tec: TEC::from_tecu(x + 10.0 * y),
+                point: Point::new(x, y),
+            };
+
+            MapCell::from_cardinal_points(
+                epoch,
+                corner(x + 1.0, y + 1.0),
+                corner(x, y + 1.0),
+                corner(x + 1.0, y),
+                corner(x, y),
+            )
+        };
+
+        let center = cell(t0, 0.0, 0.0);
+
+        // eastern neighbor
+        let east = cell(t0, 1.0, 0.0);
+
+        for merged in [
+            east.merge_neighbors(&center).unwrap(),
+            center.merge_neighbors(&east).unwrap(),
+        ] {
+            assert_eq!(merged.epoch, t0);
+            assert_eq!(merged.north_east, east.north_east);
+            assert_eq!(merged.south_east, east.south_east);
+            assert_eq!(merged.north_west, center.north_west);
+            assert_eq!(merged.south_west, center.south_west);
+            assert_eq!(merged.latitude_longitude_span_degrees(), (1.0, 2.0));
+        }
+
+        // northern neighbor
+        let north = cell(t0, 0.0, 1.0);
+
+        for merged in [
+            north.merge_neighbors(&center).unwrap(),
+            center.merge_neighbors(&north).unwrap(),
+        ] {
+            assert_eq!(merged.north_east, north.north_east);
+            assert_eq!(merged.north_west, north.north_west);
+            assert_eq!(merged.south_east, center.south_east);
+            assert_eq!(merged.south_west, center.south_west);
+            assert_eq!(merged.latitude_longitude_span_degrees(), (2.0, 1.0));
+            assert_eq!(merged.north_east.tec.tecu(), 21.0);
+        }
+
+        // diagonal or remote cells
+        for other in [cell(t0, 1.0, 1.0), cell(t0, 2.0, 0.0)] {
+            assert!(matches!(
+                center.merge_neighbors(&other),
+                Err(Error::SpatialMismatch)
+            ));
+        }
+
+        // not synchronous
+        let east = cell(t0 + 1.0 * Unit::Second, 1.0, 0.0);
+
+        assert!(matches!(
+            center.merge_neighbors(&east),
+            Err(Error::TemporalMismatch)
+        ));
+    }
 }
@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use geo::{Contains, GeodesicArea, Geometry, Point, Rect};
 
-use crate::prelude::{Epoch, Error, MapCell, TEC};
+use crate::{
+    cell::TecPoint,
+    prelude::{Epoch, Error, MapCell, TEC},
+};
 
 // #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd)]
 // #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -275,10 +278,262 @@ impl Cell3x3 {
         None
     }
 
+    /// Returns the 9 [MapCell]s of this [Cell3x3].
+    fn cells(&self) -> [&MapCell; 9] {
+        [
+            &self.center,
+            &self.northeast,
+            &self.north,
+            &self.northwest,
+            &self.west,
+            &self.southwest,
+            &self.south,
+            &self.southeast,
+            &self.east,
+        ]
+    }
+
     /// Returns a stretched (spatially upscaled or downscaled) [MapCell] by
-    /// stretching the central element and taking the relative neighboring values into
-    /// account.
+    /// stretching the central element around its center and taking the relative
+    /// neighboring values into account.
+    ///
+    /// Each corner of the resized cell is valued by bilinear interpolation
+    /// within the cell of the 3x3 block that contains it. A corner that lies on the
+    /// edge shared by two cells (or on a node shared by up to four cells) is the average
+    /// of all these interpolations, so the gradient of the neighboring cells is blended
+    /// with the central one. Unlike [MapCell::stretched], which only interpolates
+    /// within the central cell, this is exact on linear gradient fields for any
+    /// factor up to 3, where the resized cell reaches the outer edges of the block.
+    /// [Error::OutsideSpatialBoundaries] is returned beyond that.
     pub fn stretched(&self, factor: f64) -> Result<MapCell, Error> {
-        Err(Error::OutsideSpatialBoundaries) // TODO
+        if !factor.is_normal() || factor < 0.0 {
+            return Err(Error::InvalidStretchFactor);
+        }
+
+        // unitary stretching is an exact no-op
+        if (factor - 1.0).abs() < f64::EPSILON {
+            return Ok(self.center);
+        }
+
+        let center = self.center.center();
+
+        let stretched = |corner: &TecPoint| -> Result<TecPoint, Error> {
+            let point = Point::new(
+                center.x() + (corner.point.x() - center.x()) * factor,
+                center.y() + (corner.point.y() - center.y()) * factor,
+            );
+
+            let interpolations = self
+                .cells()
+                .into_iter()
+                .filter_map(|cell| cell.spatial_tec_interp(point).ok())
+                .collect::<Vec<_>>();
+
+            if interpolations.is_empty() {
+                return Err(Error::OutsideSpatialBoundaries);
+            }
+
+            let weight = 1.0 / interpolations.len() as f64;
+
+            Ok(TecPoint {
+                point,
+                tec: TEC::interpolated(interpolations.into_iter().map(|tec| (tec, weight))),
+            })
+        };
+
+        Ok(MapCell::from_cardinal_points(
+            self.center.epoch,
+            stretched(&self.center.north_east)?,
+            stretched(&self.center.north_west)?,
+            stretched(&self.center.south_east)?,
+            stretched(&self.center.south_west)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::prelude::Duration;
+
+    /// Builds a unitary [MapCell] whose south western corner is (x, y)
+    fn unitary_cell(epoch: Epoch, x: f64, y: f64) -> MapCell {
+        let corner = |x: f64, y: f64| TecPoint {
+            tec: TEC::from_tecu(x + y),
+            point: Point::new(x, y),
+        };
+
+        MapCell::from_cardinal_points(
+            epoch,
+            corner(x + 1.0, y + 1.0),
+            corner(x, y + 1.0),
+            corner(x + 1.0, y),
+            corner(x, y),
+        )
+    }
+
+    #[test]
+    fn diagonal_neighbors() {
+        let epoch = Epoch::default();
+        let center = unitary_cell(epoch, 0.0, 0.0);
+
+        let northwest = unitary_cell(epoch, -1.0, 1.0);
+        let northeast = unitary_cell(epoch, 1.0, 1.0);
+        let southwest = unitary_cell(epoch, -1.0, -1.0);
+        let southeast = unitary_cell(epoch, 1.0, -1.0);
+
+        assert!(northwest.is_northwestern_neighbor(&center));
+        assert!(northeast.is_northeastern_neighbor(&center));
+        assert!(southwest.is_southwestern_neighbor(&center));
+        assert!(southeast.is_southeastern_neighbor(&center));
+
+        assert!(center.is_southeastern_neighbor(&northwest));
+        assert!(center.is_southwestern_neighbor(&northeast));
+        assert!(center.is_northeastern_neighbor(&southwest));
+        assert!(center.is_northwestern_neighbor(&southeast));
+
+        for diagonal in [northwest, northeast, southwest, southeast] {
+            assert!(diagonal.is_neighbor(&center));
+            assert!(!diagonal.is_northern_neighbor(&center));
+            assert!(!diagonal.is_southern_neighbor(&center));
+            assert!(!diagonal.is_eastern_neighbor(&center));
+            assert!(!diagonal.is_western_neighbor(&center));
+        }
+
+        assert!(!northwest.is_northeastern_neighbor(&center));
+        assert!(!northwest.is_southwestern_neighbor(&center));
+        assert!(!northwest.is_southeastern_neighbor(&center));
+
+        // too far away
+        let remote = unitary_cell(epoch, 2.0, 2.0);
+        assert!(!remote.is_neighbor(&center));
+    }
+
+    #[test]
+    fn from_slice() {
+        let epoch = Epoch::default();
+
+        // unordered 3x3 block, centered on the (0, 0) unitary cell
+        let cells = [
+            unitary_cell(epoch, 1.0, -1.0),
+            unitary_cell(epoch, -1.0, 0.0),
+            unitary_cell(epoch, 0.0, 1.0),
+            unitary_cell(epoch, -1.0, -1.0),
+            unitary_cell(epoch, 0.0, 0.0),
+            unitary_cell(epoch, 1.0, 1.0),
+            unitary_cell(epoch, 0.0, -1.0),
+            unitary_cell(epoch, -1.0, 1.0),
+            unitary_cell(epoch, 1.0, 0.0),
+        ];
+
+        let cell3x3 = Cell3x3::from_slice(cells).unwrap_or_else(|| {
+            panic!("failed to assemble the 3x3 block");
+        });
+
+        assert_eq!(cell3x3.center, unitary_cell(epoch, 0.0, 0.0));
+        assert_eq!(cell3x3.northwest, unitary_cell(epoch, -1.0, 1.0));
+        assert_eq!(cell3x3.north, unitary_cell(epoch, 0.0, 1.0));
+        assert_eq!(cell3x3.northeast, unitary_cell(epoch, 1.0, 1.0));
+        assert_eq!(cell3x3.west, unitary_cell(epoch, -1.0, 0.0));
+        assert_eq!(cell3x3.east, unitary_cell(epoch, 1.0, 0.0));
+        assert_eq!(cell3x3.southwest, unitary_cell(epoch, -1.0, -1.0));
+        assert_eq!(cell3x3.south, unitary_cell(epoch, 0.0, -1.0));
+        assert_eq!(cell3x3.southeast, unitary_cell(epoch, 1.0, -1.0));
+
+        // not a 3x3 block
+        let mut off_grid = cells;
+        off_grid[0] = unitary_cell(epoch, 2.0, -1.0);
+        assert!(Cell3x3::from_slice(off_grid).is_none());
+
+        // not synchronous
+        let mut asynchronous = cells;
+        asynchronous[0].epoch = epoch + Duration::from_seconds(1.0);
+        assert!(Cell3x3::from_slice(asynchronous).is_none());
+    }
+
+    #[test]
+    fn stretched() {
+        let epoch = Epoch::default();
+
+        // linear gradient field
+        let tecu = |x: f64, y: f64| 10.0 + 2.0 * x + 3.0 * y;
+
+        let cell = |x: f64, y: f64| {
+            let corner = |x: f64, y: f64| TecPoint {
+                tec: TEC::from_tecu(tecu(x, y)),
+                point: Point::new(x, y),
+            };
+
+            MapCell::from_cardinal_points(
+                epoch,
+                corner(x + 1.0, y + 1.0),
+                corner(x, y + 1.0),
+                corner(x + 1.0, y),
+                corner(x, y),
+            )
+        };
+
+        let mut cells = [MapCell::default(); 9];
+
+        for (nth, (x, y)) in (-1..=1)
+            .flat_map(|x| (-1..=1).map(move |y| (x as f64, y as f64)))
+            .enumerate()
+        {
+            cells[nth] = cell(x, y);
+        }
+
+        let cell3x3 = Cell3x3::from_slice(cells).unwrap();
+
+        // unitary stretching preserves the central cell
+        assert_eq!(cell3x3.stretched(1.0).unwrap(), cell3x3.center);
+
+        for factor in [0.5, 1.5, 2.0, 3.0] {
+            let stretched = cell3x3.stretched(factor).unwrap_or_else(|e| {
+                panic!("failed to stretch by {}: {}", factor, e);
+            });
+
+            assert_eq!(stretched.center(), cell3x3.center.center());
+
+            let (width, height) = (
+                stretched.north_east.point.x() - stretched.south_west.point.x(),
+                stretched.north_east.point.y() - stretched.south_west.point.y(),
+            );
+
+            assert!((width - factor).abs() < 1.0E-9);
+            assert!((height - factor).abs() < 1.0E-9);
+
+            // exact on a linear field
+            for corner in [
+                stretched.north_east,
+                stretched.north_west,
+                stretched.south_east,
+                stretched.south_west,
+            ] {
+                let expected = tecu(corner.point.x(), corner.point.y());
+
+                assert!(
+                    (corner.tec.tecu() - expected).abs() < 1.0E-6,
+                    "x{} ({}, {}): {} vs {}",
+                    factor,
+                    corner.point.x(),
+                    corner.point.y(),
+                    corner.tec.tecu(),
+                    expected,
+                );
+            }
+        }
+
+        // the naive approach can't upscale: it only interpolates within the central cell
+        assert!(cell3x3.center.stretched(1.5).is_err());
+
+        // beyond the 3x3 block
+        assert!(matches!(
+            cell3x3.stretched(3.5),
+            Err(Error::OutsideSpatialBoundaries)
+        ));
+
+        assert!(cell3x3.stretched(0.0).is_err());
+        assert!(cell3x3.stretched(-1.0).is_err());
     }
 }
@@ -59,6 +59,22 @@ impl QuantizedCoordinates {
         }
     }
 
+    /// Smallest [QuantizedCoordinates], used as lower bound in range queries.
+    pub(crate) fn min() -> Self {
+        let min = Quantized::lowest();
+        Self::from_quantized(min, min, min)
+    }
+
+    /// Returns the canonical representation of these [QuantizedCoordinates],
+    /// see [Quantized::canonical].
+    pub(crate) fn canonical(&self) -> Self {
+        Self::from_quantized(
+            self.lat_ddeg.canonical(),
+            self.long_ddeg.canonical(),
+            self.alt_km.canonical(),
+        )
+    }
+
     /// Returns latitude in degrees
     pub fn latitude_ddeg(&self) -> f64 {
         self.lat_ddeg.real_value()
@@ -6,6 +6,8 @@ use gnss_rs::{
 
 use std::io::Error as IoError;
 
+use crate::file_attributes::Error as FileAttributesError;
+
 /// Errors that may rise during parsing process.
 #[derive(Debug, Error)]
 pub enum ParsingError {
@@ -39,9 +41,18 @@ pub enum ParsingError {
     #[error("failed to parse elevation cutoff")]
     ElevationCutoff,
 
+    #[error("failed to parse base radius")]
+    BaseRadius,
+
+    #[error("failed to parse model parameters")]
+    ModelParameters,
+
     #[error("failed to parse sampling period")]
     SamplingPeriod,
 
+    #[error("invalid sampling period: must be strictly positive")]
+    InvalidSamplingPeriod,
+
     #[error("error when parsing a coordinates")]
     CoordinatesParsing,
 
@@ -89,6 +100,12 @@ pub enum ParsingError {
 
     #[error("scaling parsing issue")]
     ExponentScaling,
+
+    #[error("invalid CSV record: \"{0}\"")]
+    CsvRecord(String),
+
+    #[error("inconsistent longitude spacing: {1} while {0} was previously used")]
+    InconsistentGridSpacing(f64, f64),
 }
 
 #[derive(Error, Debug)]
@@ -105,14 +122,51 @@ pub enum Error {
     #[error("temporal coordinates outside this temporal axis")]
     OutsideTemporalBoundaries,
 
+    #[error("coordinates do not lie on a grid node")]
+    OffGrid,
+
+    #[error("epoch does not lie on the temporal axis")]
+    OffTemporalAxis,
+
     #[error("both regions do not describe the same spatial ROI")]
     SpatialMismatch,
 
+    #[error("grid mismatch: {}", .0.join(", "))]
+    GridMismatch(Vec<String>),
+
     #[error("both regions are not synchronous in time")]
     TemporalMismatch,
 
+    #[error("missing TEC value at one of the cell corners")]
+    IncompleteCell,
+
     #[error("invalid temporal interpolation instant")]
     InvalidTemporalPoint,
+
+    #[error("non uniform grid spacing")]
+    NonUniformGrid,
+
+    #[error("3D map: altitude is not fixed")]
+    NotFixedAltitude,
+
+    #[error("no file could be parsed")]
+    NoFileParsed,
+
+    #[error("weight must be within [0; 1]")]
+    InvalidWeight,
+
+    #[error("parsing error: {0}")]
+    Parsing(#[from] ParsingError),
+
+    #[error("formatting error: {0}")]
+    Formatting(#[from] FormattingError),
+
+    #[error("file attributes error: {0}")]
+    FileAttributes(#[from] FileAttributesError),
+
+    #[cfg(feature = "serde")]
+    #[error("json serialization error: {0}")]
+    Json(#[from] serde_json::Error),
 }
 
 /// Errors that may rise during Formatting process
@@ -123,4 +177,7 @@ pub enum FormattingError {
 
     #[error("missing grid definition")]
     NoGridDefinition,
+
+    #[error("header grid does not match the record")]
+    GridMismatch,
 }
@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// File info parsing errors
 #[derive(Error, Debug)]
 pub enum Error {
@@ -8,6 +11,7 @@ pub enum Error {
 }
 
 #[derive(Debug, Copy, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Region {
     /// Local/Regional IONEX map (specific ROI).
     Regional,
@@ -30,6 +34,7 @@ impl std::fmt::Display for Region {
 /// RINEX data that follows standard naming conventions,
 /// or attached to data parsed from such files.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FileAttributes {
     /// File agency
     pub agency: String,
@@ -1,8 +1,21 @@
-use crate::{error::ParsingError, linspace::Linspace};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use itertools::Itertools;
+
+use crate::{
+    error::{Error, ParsingError},
+    interpolation::surrounding_nodes,
+    linspace::Linspace,
+    quantized::Quantized,
+    record::Record,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// Quantization exponent used by [Grid::signature]
+const SIGNATURE_EXPONENT: i8 = 3;
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Axis {
@@ -61,6 +74,92 @@ impl Grid {
         !self.is_worldwide()
     }
 
+    /// Infers a [Grid] from the distinct coordinates present in this [Record].
+    /// The point spacing of each axis is the smallest gap between two distinct coordinates,
+    /// every other gap must be a multiple of it (missing rows or columns are tolerated).
+    /// The returned [Grid] follows the IONEX conventions: latitudes are described
+    /// from North to South, longitudes from West to East and altitudes upwards.
+    /// Returns [Error::UndefinedBoundaries] if the [Record] is empty and
+    /// [Error::NonUniformGrid] if one axis is not uniformly spaced.
+    pub fn from_record(record: &Record) -> Result<Self, Error> {
+        let latitude = uniform_linspace(record.map.keys().map(|k| k.latitude_ddeg()), true)?;
+        let longitude = uniform_linspace(record.map.keys().map(|k| k.longitude_ddeg()), false)?;
+        let altitude = uniform_linspace(record.map.keys().map(|k| k.altitude_km()), false)?;
+
+        Ok(Self {
+            latitude,
+            longitude,
+            altitude,
+        })
+    }
+
+    /// Snaps these coordinates (latitude and longitude in decimal degrees, altitude in kilometers)
+    /// to the nearest [Grid] node, which is always within half a spacing.
+    /// Returns None if these coordinates are outside this [Grid].
+    pub fn snap(&self, lat_ddeg: f64, long_ddeg: f64, alt_km: f64) -> Option<(f64, f64, f64)> {
+        let nearest = |linspace: &Linspace, value: f64| {
+            let ((lower, upper), position) = surrounding_nodes(linspace, value).ok()?;
+            if position < 0.5 {
+                Some(lower)
+            } else {
+                Some(upper)
+            }
+        };
+
+        Some((
+            nearest(&self.latitude, lat_ddeg)?,
+            nearest(&self.longitude, long_ddeg)?,
+            nearest(&self.altitude, alt_km)?,
+        ))
+    }
+
+    /// Returns a signature of this [Grid], which is a hash of the (quantized) start,
+    /// end and spacing of all three axes. Two [Grid]s that are equal within
+    /// the quantization (1E-3) share the same signature, which makes it a cheap
+    /// comparison key when processing many files.
+    pub fn signature(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        for linspace in [&self.latitude, &self.longitude, &self.altitude] {
+            for value in [linspace.start, linspace.end, linspace.spacing] {
+                Quantized::new(value, SIGNATURE_EXPONENT).hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
+    /// Returns true if both [Grid]s describe the same grid, within quantization.
+    /// See [Self::signature].
+    pub fn same_grid_as(&self, rhs: &Self) -> bool {
+        self.signature() == rhs.signature()
+    }
+
+    /// Returns the human readable list of disagreements between this [Grid] and `rhs`,
+    /// for example `"latitude spacing -2.5 vs -1.0"`. Empty when both [Grid]s are identical.
+    /// This is the content of [Error::GridMismatch].
+    pub fn diff(&self, rhs: &Self) -> Vec<String> {
+        let mut diff = Vec::new();
+
+        for (axis, lhs, rhs) in [
+            ("latitude", &self.latitude, &rhs.latitude),
+            ("longitude", &self.longitude, &rhs.longitude),
+            ("altitude", &self.altitude, &rhs.altitude),
+        ] {
+            for (field, lhs, rhs) in [
+                ("start", lhs.start, rhs.start),
+                ("end", lhs.end, rhs.end),
+                ("spacing", lhs.spacing, rhs.spacing),
+            ] {
+                if lhs != rhs {
+                    diff.push(format!("{} {} {:?} vs {:?}", axis, field, lhs, rhs));
+                }
+            }
+        }
+
+        diff
+    }
+
     /// Defines a new [Grid] with updated latitude space
     pub fn with_latitude_space(mut self, linspace: Linspace) -> Self {
         self.latitude = linspace;
@@ -80,6 +179,51 @@ impl Grid {
     }
 }
 
+/// Builds a uniformly spaced [Linspace] from a set of (possibly repeated) coordinates.
+fn uniform_linspace(
+    values: impl Iterator<Item = f64>,
+    descending: bool,
+) -> Result<Linspace, Error> {
+    let values = values
+        .sorted_by(|a, b| a.total_cmp(b))
+        .dedup()
+        .collect::<Vec<_>>();
+
+    let (min, max) = match (values.first(), values.last()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => return Err(Error::UndefinedBoundaries),
+    };
+
+    let spacing = values
+        .iter()
+        .tuple_windows()
+        .map(|(a, b)| b - a)
+        .min_by(|a, b| a.total_cmp(b))
+        .unwrap_or(0.0);
+
+    for (a, b) in values.iter().tuple_windows() {
+        let ratio = (b - a) / spacing;
+
+        if (ratio - ratio.round()).abs() > 1.0E-6 {
+            return Err(Error::NonUniformGrid);
+        }
+    }
+
+    if descending {
+        Ok(Linspace {
+            start: max,
+            end: min,
+            spacing: -spacing,
+        })
+    } else {
+        Ok(Linspace {
+            start: min,
+            end: max,
+            spacing,
+        })
+    }
+}
+
 /// [GridSpecs] as found in IONEX descriptor
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub(crate) struct GridSpecs {
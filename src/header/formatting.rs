@@ -1,9 +1,11 @@
 use crate::{
     epoch::format_header as format_epoch,
     fmt_comment, fmt_ionex,
-    prelude::{FormattingError, Header},
+    prelude::{FormattingError, Header, ReferenceSystem},
 };
 
+use itertools::Itertools;
+
 use std::io::{BufWriter, Write};
 
 impl Header {
@@ -11,11 +13,13 @@ impl Header {
     pub fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
         let (major, minor) = (self.version.major, self.version.minor);
 
+        let file_type = self.file_type();
+
         writeln!(
             w,
             "{}",
             fmt_ionex(
-                &format!("{major:6}.{minor:01}            IONOSPHERE MAPS     GNSS"),
+                &format!("{major:6}.{minor:01}            IONOSPHERE MAPS     {file_type}"),
                 "IONEX VERSION / TYPE"
             )
         )?;
@@ -46,6 +50,26 @@ impl Header {
             }
         }
 
+        // theoretical model parameters
+        if let ReferenceSystem::Model(_) = self.reference_system {
+            if let Some(parameters) = &self.model_parameters {
+                writeln!(
+                    w,
+                    "{}",
+                    fmt_ionex(
+                        &format!(
+                            "{:15.7E}{:15.7E}{:15.7E}{:15.7E}",
+                            parameters.amplitude_s,
+                            parameters.period_s,
+                            parameters.phase_rad,
+                            parameters.slant
+                        ),
+                        "MODEL PARAMETERS"
+                    )
+                )?;
+            }
+        }
+
         writeln!(
             w,
             "{}",
@@ -160,8 +184,8 @@ impl Header {
             fmt_ionex(&format!("{:6}", self.exponent), "EXPONENT")
         )?;
 
-        // COMMENTS
-        for comment in self.comments.iter() {
+        // COMMENTS (first occurrence only)
+        for comment in self.comments.iter().unique() {
             writeln!(w, "{}", fmt_comment(comment))?;
         }
 
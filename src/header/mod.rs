@@ -1,5 +1,6 @@
 mod formatting;
 mod parsing;
+mod validation;
 
 #[cfg(feature = "qc")]
 mod qc;
@@ -7,13 +8,18 @@ mod qc;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+pub use validation::HeaderWarning;
+
 use crate::{
     linspace::Linspace,
     prelude::{
-        Comments, Duration, Epoch, Grid, MappingFunction, ReferenceSystem, TimeSeries, Version,
+        Comments, Constellation, Duration, Epoch, Grid, IonosphereParameters, MappingFunction,
+        ReferenceSystem, TimeSeries, Version,
     },
 };
 
+use std::str::FromStr;
+
 /// IONEX file [Header]
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -21,6 +27,10 @@ pub struct Header {
     /// File [Version]
     pub version: Version,
 
+    /// File type marker of the `IONEX VERSION / TYPE` line, only stored when it does not
+    /// describe a [ReferenceSystem] (which is then the marker). See [Self::file_type].
+    pub(crate) file_type: Option<String>,
+
     /// Name of production software
     pub program: Option<String>,
 
@@ -55,6 +65,10 @@ pub struct Header {
     /// of the TEC maps.
     pub reference_system: ReferenceSystem,
 
+    /// [IonosphereParameters] of the theoretical model,
+    /// when the [ReferenceSystem] is a model.
+    pub model_parameters: Option<IonosphereParameters>,
+
     /// It is highly recommended to give a brief description
     /// of the technique, model.. description is not a
     /// general purpose comment.
@@ -108,8 +122,10 @@ impl Default for Header {
             epoch_of_last_map: Epoch::default(),
             epoch_of_first_map: Epoch::default(),
             sampling_period: Duration::from_hours(1.0),
-            reference_system: ReferenceSystem::default(),
+            reference_system: ReferenceSystem::Constellation(Constellation::Mixed),
+            model_parameters: None,
             version: Default::default(),
+            file_type: None,
             program: Default::default(),
             run_by: Default::default(),
             date: Default::default(),
@@ -131,6 +147,25 @@ impl Header {
         )
     }
 
+    /// Returns the total [Duration] described, from [Self::epoch_of_first_map]
+    /// to [Self::epoch_of_last_map].
+    pub fn duration(&self) -> Duration {
+        self.epoch_of_last_map - self.epoch_of_first_map
+    }
+
+    /// Returns the absolute radius (in kilometers) of the first shell described
+    /// by the altitude grid, that is [Self::base_radius_km] + altitude.
+    pub fn shell_radius_km(&self) -> f64 {
+        self.base_radius_km as f64 + self.grid.altitude.start
+    }
+
+    /// Returns the exponent (scaling) of the TEC maps, as described in the header.
+    /// For example, -1 means the TEC values are formatted in 0.1 TECu.
+    /// The parser applies it to all maps, unless a map updates it locally.
+    pub fn exponent(&self) -> i8 {
+        self.exponent
+    }
+
     /// Copies [Self], returning with an updated number of Maps (total).
     pub fn with_number_of_maps(&self, number: u32) -> Self {
         let mut s = self.clone();
@@ -153,12 +188,25 @@ impl Header {
     }
 
     /// Copies and builds Self with updated [ReferenceSystem].
+    /// The file type marker is updated accordingly, see [Self::file_type].
     pub fn with_reference_system(&self, reference: ReferenceSystem) -> Self {
         let mut s = self.clone();
         s.reference_system = reference;
+        s.file_type = None;
         s
     }
 
+    /// Returns the file type, as described in the `IONEX VERSION / TYPE` line.
+    /// This is the satellite system or theoretical model the maps
+    /// were evaluated from (for example "GNSS", "GPS", "ENV" or "IRI"), that is the
+    /// [ReferenceSystem] marker, unless a custom marker is used (see [Self::with_file_type]).
+    pub fn file_type(&self) -> String {
+        match &self.file_type {
+            Some(file_type) => file_type.clone(),
+            None => self.reference_system.to_string(),
+        }
+    }
+
     /// Copies and sets exponent / scaling to currently use
     pub fn with_exponent(&self, e: i8) -> Self {
         let mut s = self.clone();
@@ -166,6 +214,55 @@ impl Header {
         s
     }
 
+    /// Copies and returns new [Header] with updated [IonosphereParameters].
+    pub fn with_model_parameters(&self, parameters: IonosphereParameters) -> Self {
+        let mut s = self.clone();
+        s.model_parameters = Some(parameters);
+        s
+    }
+
+    /// Copies and returns new [Header] with updated production software name.
+    pub fn with_program(&self, program: &str) -> Self {
+        let mut s = self.clone();
+        s.program = Some(program.to_string());
+        s
+    }
+
+    /// Copies and returns new [Header] with updated operator (usually agency) name.
+    pub fn with_run_by(&self, run_by: &str) -> Self {
+        let mut s = self.clone();
+        s.run_by = Some(run_by.to_string());
+        s
+    }
+
+    /// Copies and returns new [Header] with updated production date.
+    pub fn with_date(&self, date: &str) -> Self {
+        let mut s = self.clone();
+        s.date = Some(date.to_string());
+        s
+    }
+
+    /// Copies and returns new [Header] with updated file type.
+    /// When this marker describes a [ReferenceSystem], the [ReferenceSystem] is updated instead.
+    /// Other markers are preserved as is, the [ReferenceSystem] is then left untouched.
+    pub fn with_file_type(&self, file_type: &str) -> Self {
+        let mut s = self.clone();
+        s.set_file_type(file_type);
+        s
+    }
+
+    /// Updates the file type, see [Self::with_file_type].
+    pub(crate) fn set_file_type(&mut self, file_type: &str) {
+        let file_type = file_type.trim();
+
+        if let Ok(reference_system) = ReferenceSystem::from_str(file_type) {
+            self.reference_system = reference_system;
+            self.file_type = None;
+        } else if !file_type.is_empty() {
+            self.file_type = Some(file_type.to_string());
+        }
+    }
+
     /// Copies and sets model description
     pub fn with_description(&self, desc: &str) -> Self {
         let mut s = self.clone();
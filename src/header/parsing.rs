@@ -2,7 +2,7 @@ use crate::{
     epoch::parse_utc as parse_utc_epoch,
     error::ParsingError,
     linspace::Linspace,
-    prelude::{Duration, Header, Version},
+    prelude::{Duration, Header, IonosphereParameters, Version},
 };
 
 use std::{
@@ -35,8 +35,10 @@ impl Header {
                 // Comments are stored as is
                 header.comments.push(content.trim().to_string());
             } else if marker.contains("IONEX VERSION / TYPE") {
-                let (vers_str, _) = content.split_at(20);
+                let (vers_str, rem) = content.split_at(20);
                 header.version = Version::from_str(vers_str.trim())?;
+
+                header.set_file_type(rem.split_at(20).1);
             } else if marker.contains("# OF MAPS IN FILE") {
                 let number = content.split_at(20).0.trim();
                 header.number_of_maps = number
@@ -57,6 +59,39 @@ impl Header {
                 header.elevation_cutoff = number
                     .parse::<f32>()
                     .map_err(|_| ParsingError::ElevationCutoff)?;
+            } else if marker.contains("EXPONENT") {
+                let exponent = content.split_at(20).0.trim();
+                header.exponent = exponent
+                    .parse::<i8>()
+                    .map_err(|_| ParsingError::ExponentScaling)?;
+            } else if marker.contains("BASE RADIUS") {
+                let radius = content.split_at(20).0.trim();
+                header.base_radius_km = radius
+                    .parse::<f32>()
+                    .map_err(|_| ParsingError::BaseRadius)?;
+            } else if marker.contains("MODEL PARAMETERS") {
+                let (amplitude, rem) = content.split_at(15);
+                let (period, rem) = rem.split_at(15);
+                let (phase, slant) = rem.split_at(15);
+
+                let mut values = [0.0_f64; 4];
+
+                for (value, content) in values
+                    .iter_mut()
+                    .zip([amplitude, period, phase, slant].iter())
+                {
+                    *value = content
+                        .trim()
+                        .parse::<f64>()
+                        .map_err(|_| ParsingError::ModelParameters)?;
+                }
+
+                header.model_parameters = Some(IonosphereParameters {
+                    amplitude_s: values[0],
+                    period_s: values[1],
+                    phase_rad: values[2],
+                    slant: values[3],
+                });
             } else if marker.contains("DESCRIPTION") {
                 let description_str = content.trim();
                 if let Some(current) = &mut header.description {
@@ -108,6 +143,10 @@ impl Header {
                     .parse::<f64>()
                     .map_err(|_| ParsingError::SamplingPeriod)?;
 
+                if interval <= 0.0 {
+                    return Err(ParsingError::InvalidSamplingPeriod);
+                }
+
                 header.sampling_period = Duration::from_seconds(interval);
             } else if marker.contains("LAT1 / LAT2 / DLAT") {
                 // latitude grid specs
@@ -2,6 +2,9 @@ use crate::prelude::Header;
 
 use gnss_qc_traits::{Merge, MergeError};
 
+#[cfg(feature = "log")]
+use log::error;
+
 impl Merge for Header {
     fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
         let mut s = self.clone();
@@ -10,6 +13,27 @@ impl Merge for Header {
     }
 
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
+        // both must describe the same grid (spacing, origin, extent and altitude),
+        // otherwise the merged record would be corrupt
+        if !self.grid.same_grid_as(&rhs.grid) {
+            #[cfg(feature = "log")]
+            error!("grid mismatch: {}", self.grid.diff(&rhs.grid).join(", "));
+
+            return Err(MergeError::DimensionMismatch);
+        }
+
+        if self.map_dimension != rhs.map_dimension {
+            return Err(MergeError::DimensionMismatch);
+        }
+
+        if self.reference_system != rhs.reference_system {
+            return Err(MergeError::ReferenceFrameMismatch);
+        }
+
+        if self.mapf != rhs.mapf {
+            return Err(MergeError::Other);
+        }
+
         self.version = std::cmp::min(self.version, rhs.version);
 
         if self.program.is_none() {
@@ -59,16 +83,8 @@ impl Merge for Header {
 
         self.epoch_of_last_map = std::cmp::max(self.epoch_of_last_map, rhs.epoch_of_last_map);
 
-        if self.reference_system != rhs.reference_system {
-            // TODO: both must match
-        }
-
-        if self.mapf != rhs.mapf {
-            // TODO: both must match
-        }
-
-        if self.map_dimension != rhs.map_dimension {
-            // TODO: both must match
+        if self.model_parameters.is_none() {
+            self.model_parameters = rhs.model_parameters;
         }
 
         self.sampling_period = std::cmp::min(self.sampling_period, rhs.sampling_period);
@@ -77,8 +93,6 @@ impl Merge for Header {
             self.elevation_cutoff = rhs.elevation_cutoff;
         }
 
-        // TODO: merge grid def
-
         for comment in rhs.comments.iter() {
             if !self.comments.contains(&comment) {
                 self.comments.push(comment.clone());
@@ -0,0 +1,144 @@
+use crate::{
+    grid::Axis,
+    prelude::{Duration, Epoch, Header, Linspace},
+};
+
+#[cfg(doc)]
+use crate::prelude::IONEX;
+
+/// Smallest realistic base radius, in kilometers.
+const MIN_BASE_RADIUS_KM: f32 = 6300.0;
+
+/// Largest realistic base radius, in kilometers.
+const MAX_BASE_RADIUS_KM: f32 = 6400.0;
+
+/// Largest elevation cutoff, in degrees (zenith).
+const MAX_ELEVATION_CUTOFF_DEG: f32 = 90.0;
+
+/// [HeaderWarning]s are non fatal issues that may be detected
+/// in a [Header] section. Parsing is lenient, so these
+/// help decide whether the content can be trusted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderWarning {
+    /// Base radius (in kilometers) that does not describe a realistic Earth radius.
+    UnrealisticBaseRadius(f32),
+
+    /// Elevation cutoff (in degrees) that is not a valid elevation angle.
+    UnrealisticElevationCutoff(f32),
+
+    /// The described time frame (first [Duration]) does not match the sampling period
+    /// and number of maps (expected [Duration]).
+    InconsistentDuration(Duration, Duration),
+
+    /// No map is announced.
+    NoMaps,
+
+    /// The epoch of last map (second [Epoch]) does not come after the epoch
+    /// of first map (first [Epoch]), while several maps are announced.
+    InvalidTimeFrame(Epoch, Epoch),
+
+    /// Null spacing on this [Axis], while it describes several points.
+    NullSpacing(Axis),
+
+    /// Map dimension that does not match the altitude grid:
+    /// 3D maps require several altitude points, 2D maps a single one.
+    InconsistentMapDimension(u8),
+}
+
+impl std::fmt::Display for HeaderWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnrealisticBaseRadius(radius_km) => write!(
+                f,
+                "base radius {} km is outside [{}; {}] km",
+                radius_km, MIN_BASE_RADIUS_KM, MAX_BASE_RADIUS_KM
+            ),
+            Self::UnrealisticElevationCutoff(cutoff_deg) => write!(
+                f,
+                "elevation cutoff {} deg is outside [0; {}] deg",
+                cutoff_deg, MAX_ELEVATION_CUTOFF_DEG
+            ),
+            Self::InconsistentDuration(duration, expected) => write!(
+                f,
+                "time frame {} does not match sampling period and number of maps ({})",
+                duration, expected
+            ),
+            Self::NoMaps => write!(f, "no map announced"),
+            Self::InvalidTimeFrame(first, last) => write!(
+                f,
+                "epoch of last map {} does not come after epoch of first map {}",
+                last, first
+            ),
+            Self::NullSpacing(axis) => write!(f, "null spacing on multi point {:?} axis", axis),
+            Self::InconsistentMapDimension(dimension) => {
+                write!(f, "{}D map does not match the altitude grid", dimension)
+            },
+        }
+    }
+}
+
+impl Header {
+    /// Verifies this [Header] describes a physically consistent setup,
+    /// returning all [HeaderWarning]s that were detected (empty when the [Header] is sane).
+    /// You should typically call this after [IONEX::from_file].
+    pub fn validate(&self) -> Vec<HeaderWarning> {
+        let mut warnings = Vec::new();
+
+        if !(MIN_BASE_RADIUS_KM..=MAX_BASE_RADIUS_KM).contains(&self.base_radius_km) {
+            warnings.push(HeaderWarning::UnrealisticBaseRadius(self.base_radius_km));
+        }
+
+        if !(0.0..=MAX_ELEVATION_CUTOFF_DEG).contains(&self.elevation_cutoff) {
+            warnings.push(HeaderWarning::UnrealisticElevationCutoff(
+                self.elevation_cutoff,
+            ));
+        }
+
+        if self.number_of_maps == 0 {
+            warnings.push(HeaderWarning::NoMaps);
+        }
+
+        if self.number_of_maps > 1 && self.epoch_of_last_map <= self.epoch_of_first_map {
+            warnings.push(HeaderWarning::InvalidTimeFrame(
+                self.epoch_of_first_map,
+                self.epoch_of_last_map,
+            ));
+        }
+
+        for (axis, linspace) in [
+            (Axis::Latitude, &self.grid.latitude),
+            (Axis::Longitude, &self.grid.longitude),
+            (Axis::Altitude, &self.grid.altitude),
+        ] {
+            if null_spacing(linspace) {
+                warnings.push(HeaderWarning::NullSpacing(axis));
+            }
+        }
+
+        let dimension_mismatch = match self.map_dimension {
+            2 => self.grid.is_3d_grid(),
+            3 => self.grid.is_2d_grid(),
+            _ => true,
+        };
+
+        if dimension_mismatch {
+            warnings.push(HeaderWarning::InconsistentMapDimension(self.map_dimension));
+        }
+
+        if self.number_of_maps > 0 {
+            let duration = self.duration();
+            let expected = self.sampling_period * (self.number_of_maps - 1) as f64;
+
+            if duration != expected {
+                warnings.push(HeaderWarning::InconsistentDuration(duration, expected));
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Returns true if this [Linspace] describes several points with a null spacing.
+fn null_spacing(linspace: &Linspace) -> bool {
+    linspace.spacing == 0.0 && linspace.start != linspace.end
+}
@@ -0,0 +1,188 @@
+//! Bilinear interpolation weights, shared by all the interpolation methods:
+//! single point interpolation, batched interpolation, grid resampling and [MapCell] interpolation.
+
+use geo::Point;
+
+use crate::{error::Error, linspace::Linspace};
+
+#[cfg(doc)]
+use crate::cell::MapCell;
+
+/// Returns the two [Linspace] nodes surrounding this value,
+/// and the relative position of this value between them.
+pub(crate) fn surrounding_nodes(
+    linspace: &Linspace,
+    value: f64,
+) -> Result<((f64, f64), f64), Error> {
+    if value < linspace.min() || value > linspace.max() {
+        return Err(Error::OutsideSpatialBoundaries);
+    }
+
+    if linspace.spacing == 0.0 {
+        return Ok(((linspace.start, linspace.start), 0.0));
+    }
+
+    let position = (value - linspace.start) / linspace.spacing;
+    let index = position.floor();
+    let lower = linspace.start + index * linspace.spacing;
+
+    Ok(((lower, lower + linspace.spacing), position - index))
+}
+
+/// Returns true if these longitudes (decimal degrees) span the whole globe, once the
+/// wraparound step between the easternmost and westernmost nodes is accounted for.
+pub(crate) fn wraps_around_globe(longitude: &Linspace) -> bool {
+    // tolerates the rounding errors of the grid nodes
+    const TOLERANCE: f64 = 1.0E-6;
+
+    let (min, max) = longitude.minmax();
+    longitude.spacing != 0.0 && max - min + longitude.spacing.abs() >= 360.0 - TOLERANCE
+}
+
+/// Returns the two longitude nodes surrounding this longitude (decimal degrees),
+/// and the relative position of this longitude between them.
+/// When the longitudes span the whole globe (see [wraps_around_globe]), longitudes are
+/// wrapped around the globe, and the wraparound step between the easternmost and westernmost
+/// nodes is treated as a regular step: the westernmost node is then returned as second node.
+pub(crate) fn surrounding_longitudes(
+    longitude: &Linspace,
+    long_ddeg: f64,
+) -> Result<((f64, f64), f64), Error> {
+    if !wraps_around_globe(longitude) {
+        return surrounding_nodes(longitude, long_ddeg);
+    }
+
+    let (min, max) = longitude.minmax();
+    let long_ddeg = (long_ddeg - min).rem_euclid(360.0) + min;
+
+    if long_ddeg <= max {
+        return surrounding_nodes(longitude, long_ddeg);
+    }
+
+    let weight = (long_ddeg - max) / (min + 360.0 - max);
+    Ok(((max, min), weight))
+}
+
+/// Returns the (latitude, longitude) grid nodes involved in the bilinear interpolation
+/// at this point (decimal degrees), with their weights. Nodes with a null weight
+/// are omitted, so grid aligned coordinates do not require the opposite nodes.
+/// Longitudes wrap around the globe, see [surrounding_longitudes].
+pub(crate) fn bilinear_nodes(
+    latitude: &Linspace,
+    longitude: &Linspace,
+    point: Point<f64>,
+) -> Result<Vec<((f64, f64), f64)>, Error> {
+    let ((lat0, lat1), lat_weight) = surrounding_nodes(latitude, point.y())?;
+    let ((long0, long1), long_weight) = surrounding_longitudes(longitude, point.x())?;
+
+    let mut nodes = Vec::with_capacity(4);
+
+    for (lat_ddeg, lat_w) in [(lat0, 1.0 - lat_weight), (lat1, lat_weight)] {
+        for (long_ddeg, long_w) in [(long0, 1.0 - long_weight), (long1, long_weight)] {
+            let weight = lat_w * long_w;
+
+            if weight != 0.0 {
+                nodes.push(((lat_ddeg, long_ddeg), weight));
+            }
+        }
+    }
+
+    Ok(nodes)
+}
+
+/// Returns the bilinear weights of these four cell corners, at this point that must lie
+/// within the cell (boundaries included). The corners may be given in any order:
+/// each weight only depends on the distance between the point and the corner, relative to the
+/// cell span, so this applies to any [MapCell] regardless of the grid orientation.
+pub(crate) fn corner_weights(
+    corners: [Point<f64>; 4],
+    point: Point<f64>,
+) -> Result<[f64; 4], Error> {
+    // tolerates the rounding errors of derived coordinates
+    const TOLERANCE: f64 = 1.0E-9;
+
+    let (mut min_x, mut max_x) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+
+    for corner in corners.iter() {
+        min_x = min_x.min(corner.x());
+        max_x = max_x.max(corner.x());
+        min_y = min_y.min(corner.y());
+        max_y = max_y.max(corner.y());
+    }
+
+    if point.x() < min_x - TOLERANCE
+        || point.x() > max_x + TOLERANCE
+        || point.y() < min_y - TOLERANCE
+        || point.y() > max_y + TOLERANCE
+    {
+        return Err(Error::OutsideSpatialBoundaries);
+    }
+
+    // weight of a node along one axis: degenerated spans are shared by both nodes
+    let axis_weight = |value: f64, node: f64, span: f64| {
+        if span == 0.0 {
+            0.5
+        } else {
+            (1.0 - (value - node).abs() / span).clamp(0.0, 1.0)
+        }
+    };
+
+    Ok(corners.map(|corner| {
+        axis_weight(point.x(), corner.x(), max_x - min_x)
+            * axis_weight(point.y(), corner.y(), max_y - min_y)
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bilinear_node_weights() {
+        let latitude = Linspace::new(87.5, -87.5, -2.5).unwrap();
+        let regional = Linspace::new(0.0, 350.0, 5.0).unwrap();
+        let worldwide = Linspace::new(0.0, 355.0, 5.0).unwrap();
+
+        assert!(!wraps_around_globe(&regional));
+        assert!(wraps_around_globe(&worldwide));
+        assert!(wraps_around_globe(
+            &Linspace::new(-180.0, 180.0, 5.0).unwrap()
+        ));
+
+        // grid aligned coordinates only require one node
+        let nodes = bilinear_nodes(&latitude, &regional, Point::new(10.0, 50.0)).unwrap();
+        assert_eq!(nodes, vec![((50.0, 10.0), 1.0)]);
+
+        let nodes = bilinear_nodes(&latitude, &regional, Point::new(11.25, 50.0)).unwrap();
+        assert_eq!(nodes, vec![((50.0, 10.0), 0.75), ((50.0, 15.0), 0.25)]);
+
+        // wraparound step
+        assert!(bilinear_nodes(&latitude, &regional, Point::new(357.5, 50.0)).is_err());
+
+        let nodes = bilinear_nodes(&latitude, &worldwide, Point::new(357.5, 50.0)).unwrap();
+        assert_eq!(nodes, vec![((50.0, 355.0), 0.5), ((50.0, 0.0), 0.5)]);
+
+        let nodes = bilinear_nodes(&latitude, &worldwide, Point::new(-1.25, 50.0)).unwrap();
+        assert_eq!(nodes, vec![((50.0, 355.0), 0.25), ((50.0, 0.0), 0.75)]);
+
+        assert!(bilinear_nodes(&latitude, &worldwide, Point::new(0.0, 90.0)).is_err());
+    }
+
+    #[test]
+    fn corner_weights_any_order() {
+        let (sw, se) = (Point::new(0.0, 0.0), Point::new(1.0, 0.0));
+        let (nw, ne) = (Point::new(0.0, 2.0), Point::new(1.0, 2.0));
+
+        let point = Point::new(0.25, 0.5);
+
+        let weights = corner_weights([ne, nw, se, sw], point).unwrap();
+        assert_eq!(weights, [0.0625, 0.1875, 0.1875, 0.5625]);
+
+        // mislabeled corners lead to the same weights
+        let weights = corner_weights([sw, se, nw, ne], point).unwrap();
+        assert_eq!(weights, [0.5625, 0.1875, 0.1875, 0.0625]);
+
+        assert!(corner_weights([ne, nw, se, sw], Point::new(1.5, 0.5)).is_err());
+    }
+}
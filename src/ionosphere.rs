@@ -1,3 +1,10 @@
+use geo::Point;
+use hifitime::Epoch;
+
+/// [IonosphereParameters] of a theoretical model,
+/// described in the [Header](crate::prelude::Header) of model based maps.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IonosphereParameters {
     /// Amplitude of the ionospheric delay (seconds)
     pub amplitude_s: f64,
@@ -11,3 +18,92 @@ pub struct IonosphereParameters {
     /// Slant factor
     pub slant: f64,
 }
+
+/// Speed of light in vacuum (m/s)
+const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+
+/// GPS L1 carrier frequency (Hz)
+const GPS_L1_FREQUENCY_HZ: f64 = 1575.42E6;
+
+/// [KlobucharModel] is the GPS broadcast ionosphere model (IS-GPS-200).
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KlobucharModel {
+    /// Alpha coefficients of the amplitude polynomial
+    /// (s, s/semicircle, s/semicircle², s/semicircle³)
+    pub alpha: [f64; 4],
+
+    /// Beta coefficients of the period polynomial
+    /// (s, s/semicircle, s/semicircle², s/semicircle³)
+    pub beta: [f64; 4],
+}
+
+impl KlobucharModel {
+    /// Builds a new [KlobucharModel] from broadcast coefficients.
+    pub fn new(alpha: [f64; 4], beta: [f64; 4]) -> Self {
+        Self { alpha, beta }
+    }
+
+    /// Evaluates this [KlobucharModel] for a receiver located at these coordinates
+    /// (longitude, latitude in decimal degrees), observing a signal at this elevation and azimuth angles
+    /// (in degrees). Returns the ionospheric pierce point (IPP) coordinates, in decimal degrees,
+    /// and the vertical TEC at the IPP, in TECu (L1 delay converted to electron content).
+    pub fn vertical_tec(
+        &self,
+        epoch: Epoch,
+        receiver: Point<f64>,
+        elevation_deg: f64,
+        azimuth_deg: f64,
+    ) -> (Point<f64>, f64) {
+        // model works in semicircles
+        let (latitude_sc, longitude_sc) = (receiver.y() / 180.0, receiver.x() / 180.0);
+        let elevation_sc = elevation_deg / 180.0;
+        let azimuth_rad = azimuth_deg.to_radians();
+
+        // earth centered angle
+        let psi = 0.0137 / (elevation_sc + 0.11) - 0.022;
+
+        // IPP
+        let ipp_latitude_sc = (latitude_sc + psi * azimuth_rad.cos()).clamp(-0.416, 0.416);
+
+        let ipp_longitude_sc =
+            longitude_sc + psi * azimuth_rad.sin() / (ipp_latitude_sc * std::f64::consts::PI).cos();
+
+        // geomagnetic latitude of the IPP
+        let geomagnetic_latitude_sc =
+            ipp_latitude_sc + 0.064 * ((ipp_longitude_sc - 1.617) * std::f64::consts::PI).cos();
+
+        // local time at the IPP
+        let t = (4.32E4 * ipp_longitude_sc + epoch.to_gpst_seconds()).rem_euclid(86400.0);
+
+        let (mut amplitude, mut period) = (0.0, 0.0);
+
+        for (n, (alpha, beta)) in self.alpha.iter().zip(self.beta.iter()).enumerate() {
+            let phi_n = geomagnetic_latitude_sc.powi(n as i32);
+            amplitude += alpha * phi_n;
+            period += beta * phi_n;
+        }
+
+        let amplitude = amplitude.max(0.0);
+        let period = period.max(72000.0);
+
+        let x = 2.0 * std::f64::consts::PI * (t - 50400.0) / period;
+
+        // vertical delay (s): obliquity factor is not applied
+        let delay_s = if x.abs() < 1.57 {
+            5.0E-9 + amplitude * (1.0 - x.powi(2) / 2.0 + x.powi(4) / 24.0)
+        } else {
+            5.0E-9
+        };
+
+        let delay_m = delay_s * SPEED_OF_LIGHT_M_S;
+        let tecu = delay_m * GPS_L1_FREQUENCY_HZ.powi(2) / 40.3 / 1.0E16;
+
+        let ipp_longitude_ddeg = (ipp_longitude_sc * 180.0 + 180.0).rem_euclid(360.0) - 180.0;
+
+        (
+            Point::new(ipp_longitude_ddeg, ipp_latitude_sc * 180.0),
+            tecu,
+        )
+    }
+}
@@ -0,0 +1,171 @@
+use geo::Point;
+use serde::Serialize;
+
+use crate::{
+    error::Error,
+    prelude::{Epoch, Grid, IONEX, Key},
+};
+
+/// [Header](crate::prelude::Header) summary, as exposed by [IONEX::to_json]
+#[derive(Serialize)]
+struct JsonHeader {
+    version: String,
+    file_type: String,
+    reference_system: String,
+    mapping_function: String,
+    base_radius_km: f32,
+    elevation_cutoff_deg: f32,
+    epoch_of_first_map: String,
+    epoch_of_last_map: String,
+    sampling_period_s: f64,
+    number_of_maps: u32,
+}
+
+/// One map, as exposed by [IONEX::to_json]
+#[derive(Serialize)]
+struct JsonMap {
+    epoch: String,
+    values: Vec<Option<f64>>,
+}
+
+/// [IONEX] as exposed by [IONEX::to_json]
+#[derive(Serialize)]
+struct JsonIonex {
+    header: JsonHeader,
+    grid: Grid,
+    maps: Vec<JsonMap>,
+}
+
+/// GeoJSON point geometry, as exposed by [IONEX::to_geojson]
+#[derive(Serialize)]
+struct GeoJsonPoint {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    coordinates: [f64; 2],
+}
+
+impl From<Point<f64>> for GeoJsonPoint {
+    fn from(point: Point<f64>) -> Self {
+        Self {
+            kind: "Point",
+            coordinates: [point.x(), point.y()],
+        }
+    }
+}
+
+/// GeoJSON feature properties, as exposed by [IONEX::to_geojson]
+#[derive(Serialize)]
+struct GeoJsonProperties {
+    tecu: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rms: Option<f64>,
+}
+
+/// GeoJSON feature, as exposed by [IONEX::to_geojson]
+#[derive(Serialize)]
+struct GeoJsonFeature {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    geometry: GeoJsonPoint,
+    properties: GeoJsonProperties,
+}
+
+/// GeoJSON feature collection, as exposed by [IONEX::to_geojson]
+#[derive(Serialize)]
+struct GeoJsonFeatureCollection {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    features: Vec<GeoJsonFeature>,
+}
+
+impl IONEX {
+    /// Dumps this [IONEX] as a self-describing JSON document, with a stable shape
+    /// that does not depend on the internal quantization:
+    /// - `header`: summary of the [Header](crate::prelude::Header)
+    /// - `grid`: the latitude, longitude and altitude grid, as `{start, end, spacing}`
+    /// - `maps`: one `{epoch, values}` entry per map, in chronological order.
+    ///
+    /// `values` are the TEC (in TECu, as floating point numbers) in canonical grid order:
+    /// altitude major, then latitude, then longitude (minor),
+    /// each following the grid direction. Missing values are `null`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_json(&self) -> Result<String, Error> {
+        let grid = self.header.grid;
+
+        let header = JsonHeader {
+            version: self.header.version.to_string(),
+            file_type: self.header.file_type(),
+            reference_system: self.header.reference_system.to_string(),
+            mapping_function: self.header.mapf.to_string(),
+            base_radius_km: self.header.base_radius_km,
+            elevation_cutoff_deg: self.header.elevation_cutoff,
+            epoch_of_first_map: self.header.epoch_of_first_map.to_string(),
+            epoch_of_last_map: self.header.epoch_of_last_map.to_string(),
+            sampling_period_s: self.header.sampling_period.to_seconds(),
+            number_of_maps: self.header.number_of_maps,
+        };
+
+        let maps = self
+            .epoch_iter()
+            .map(|epoch| {
+                let mut values = Vec::new();
+
+                for altitude_km in grid.altitude.iter() {
+                    for lat_ddeg in grid.latitude.iter() {
+                        for long_ddeg in grid.longitude.iter() {
+                            let key = Key::from_decimal_degrees_km(
+                                epoch,
+                                lat_ddeg,
+                                long_ddeg,
+                                altitude_km,
+                            );
+
+                            values.push(self.record.get(&key).map(|tec| tec.tecu()));
+                        }
+                    }
+                }
+
+                JsonMap {
+                    epoch: epoch.to_string(),
+                    values,
+                }
+            })
+            .collect();
+
+        let content = serde_json::to_string(&JsonIonex { header, grid, maps })?;
+        Ok(content)
+    }
+
+    /// Dumps the map at this [Epoch] as a GeoJSON `FeatureCollection`, for visualization.
+    /// Each grid node is a `Point` feature, with `[longitude, latitude]` coordinates
+    /// (decimal degrees) and the `tecu` (and `rms`, when determined) properties.
+    /// Missing values are not exported.
+    /// [Error::TemporalMismatch] is returned when this [Epoch] does not exist in the [Record](crate::prelude::Record).
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_geojson(&self, epoch: Epoch) -> Result<String, Error> {
+        let mut synchronous = self.record.synchronous_iter_ref(epoch).peekable();
+
+        if synchronous.peek().is_none() {
+            return Err(Error::TemporalMismatch);
+        }
+
+        let features = synchronous
+            .filter(|(_, tec)| tec.tecu().is_finite())
+            .map(|(key, tec)| GeoJsonFeature {
+                kind: "Feature",
+                geometry: Point::new(key.longitude_ddeg(), key.latitude_ddeg()).into(),
+                properties: GeoJsonProperties {
+                    tecu: tec.tecu(),
+                    rms: tec.root_mean_square(),
+                },
+            })
+            .collect();
+
+        let content = serde_json::to_string(&GeoJsonFeatureCollection {
+            kind: "FeatureCollection",
+            features,
+        })?;
+
+        Ok(content)
+    }
+}
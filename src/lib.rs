@@ -35,9 +35,24 @@ pub mod version;
 mod cell;
 mod coordinates;
 mod epoch;
+mod interpolation;
 mod ionosphere;
+
+#[cfg(feature = "serde")]
+mod json;
+
+mod masking;
+
+#[cfg(feature = "nav")]
+mod nav;
+
+mod options;
+mod padding;
 mod quantized;
 mod record;
+mod resampling;
+mod spatial_index;
+mod summary;
 
 #[cfg(test)]
 mod tests;
@@ -45,8 +60,9 @@ mod tests;
 use std::{
     collections::BTreeMap,
     fs::File,
+    hash::{DefaultHasher, Hash, Hasher},
     io::{BufReader, BufWriter, Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
     str::FromStr,
 };
 
@@ -54,10 +70,13 @@ use itertools::Itertools;
 
 use geo::{BoundingRect, Contains, Geometry, LineString, Point, Polygon, Rect, coord};
 
+#[cfg(feature = "flate2")]
+use std::io::BufRead;
+
 #[cfg(feature = "flate2")]
 use flate2::{Compression as GzCompression, read::GzDecoder, write::GzEncoder};
 
-use hifitime::prelude::{Epoch, TimeSeries};
+use hifitime::prelude::{Duration, Epoch, TimeSeries, Unit};
 
 use crate::{
     cell::{Cell3x3, MapCell, TecPoint},
@@ -66,12 +85,21 @@ use crate::{
     file_attributes::{FileAttributes, Region},
     grid::{Axis, Grid},
     header::Header,
+    interpolation::{bilinear_nodes, surrounding_nodes, wraps_around_globe},
+    ionosphere::KlobucharModel,
     key::Key,
+    linspace::Linspace,
+    mapf::MappingFunction,
+    options::ParseOptions,
     quantized::Quantized,
     record::Record,
+    summary::{finite_tecu, variance},
     tec::TEC,
 };
 
+#[cfg(feature = "qc")]
+use crate::record::MergeStrategy;
+
 pub mod prelude {
     // export
     pub use crate::{
@@ -81,12 +109,18 @@ pub mod prelude {
         error::{Error, FormattingError, ParsingError},
         file_attributes::*,
         grid::{Axis, Grid},
-        header::Header,
-        ionosphere::IonosphereParameters,
+        header::{Header, HeaderWarning},
+        ionosphere::{IonosphereParameters, KlobucharModel},
         key::Key,
         linspace::Linspace,
         mapf::MappingFunction,
+        masking::MaskAction,
+        options::{MissingPolicy, ParseOptions},
+        padding::FillPolicy,
         record::Record,
+        resampling::GridResampler,
+        spatial_index::SpatialIndex,
+        summary::EpochSummary,
         system::ReferenceSystem,
         tec::TEC,
         version::Version,
@@ -99,6 +133,9 @@ pub mod prelude {
     };
     pub use gnss::prelude::{Constellation, SV};
     pub use hifitime::{Duration, Epoch, TimeScale, TimeSeries, Unit};
+
+    #[cfg(feature = "qc")]
+    pub use crate::record::MergeStrategy;
 }
 
 /// IONEX comments are readable descriptions.
@@ -134,6 +171,52 @@ pub(crate) fn quadrant_to_rectangle(
     Rect::new(coord!(x: se_long, y: se_lat), coord!(x: ne_long, y: ne_lat))
 }
 
+/// Returns the position of this [Linspace] node, 0 being [Linspace::start].
+pub(crate) fn linspace_node_index(linspace: &Linspace, node: f64) -> usize {
+    if linspace.spacing == 0.0 {
+        0
+    } else {
+        ((node - linspace.start) / linspace.spacing).round() as usize
+    }
+}
+
+/// Returns the (lowest, highest) [Linspace] nodes wrapping this [min, max] interval,
+/// which are distinct: an interval reduced to a single node is wrapped by the grid step
+/// that ends at this node (or starts at it, for the lowest node).
+pub(crate) fn wrapping_nodes(linspace: &Linspace, min: f64, max: f64) -> Option<(f64, f64)> {
+    // tolerates the rounding errors of the grid nodes
+    const TOLERANCE: f64 = 1.0E-6;
+
+    let nodes = linspace
+        .iter()
+        .sorted_by(|a, b| a.total_cmp(b))
+        .collect::<Vec<_>>();
+
+    let lowest = nodes
+        .iter()
+        .rev()
+        .position(|node| *node <= min + TOLERANCE)?;
+    let lowest = nodes.len() - 1 - lowest;
+
+    let highest = nodes.iter().position(|node| *node >= max - TOLERANCE)?;
+
+    if highest > lowest {
+        Some((nodes[lowest], nodes[highest]))
+    } else if lowest > 0 {
+        Some((nodes[lowest - 1], nodes[lowest]))
+    } else {
+        nodes.get(1).map(|next| (nodes[0], *next))
+    }
+}
+
+/// Returns true if this value is one of the [Linspace] nodes.
+pub(crate) fn is_linspace_node(linspace: &Linspace, value: f64) -> bool {
+    match surrounding_nodes(linspace, value) {
+        Ok((_, position)) => !(1.0E-6..=1.0 - 1.0E-6).contains(&position),
+        Err(_) => false,
+    }
+}
+
 /// macro to format one header line or a comment
 pub(crate) fn fmt_ionex(content: &str, marker: &str) -> String {
     if content.len() < 60 {
@@ -156,6 +239,10 @@ pub(crate) fn fmt_ionex(content: &str, marker: &str) -> String {
     }
 }
 
+/// Gzip streams start with these two bytes
+#[cfg(feature = "flate2")]
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
 /// macro to generate comments with standardized formatting
 pub(crate) fn fmt_comment(content: &str) -> String {
     fmt_ionex(content, "COMMENT")
@@ -218,6 +305,7 @@ pub(crate) fn fmt_comment(content: &str) -> String {
 ///     });
 /// ```
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IONEX {
     /// [Header] gives general information and describes following content.
     pub header: Header,
@@ -225,7 +313,8 @@ pub struct IONEX {
     /// IONEX [Record].
     pub record: Record,
 
-    /// [Comments] found in file record
+    /// [Comments] found in the record section (after the header).
+    /// Header comments are stored in [Header::comments].
     pub comments: Comments,
 
     /// [FileAttributes] resolved for file names that follow the IGS conventions.
@@ -273,6 +362,124 @@ impl IONEX {
         self.record = record.clone();
     }
 
+    /// Copy and return this [IONEX] with updated record [Comments].
+    /// [Header] comments are described in [Header::comments].
+    pub fn with_comments(&self, comments: Comments) -> Self {
+        let mut s = self.clone();
+        s.comments = comments;
+        s
+    }
+
+    /// Appends one comment to the record [Comments], with mutable access.
+    pub fn push_comment(&mut self, comment: &str) {
+        self.comments.push(comment.to_string());
+    }
+
+    /// Clears the record [Comments], with mutable access.
+    /// [Header::comments] are preserved.
+    pub fn clear_comments(&mut self) {
+        self.comments.clear();
+    }
+
+    /// Returns an iterator over all [Comments]:
+    /// [Header] comments first, then record comments, in order of appearance.
+    pub fn comment_iter(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(
+            self.header
+                .comments
+                .iter()
+                .chain(self.comments.iter())
+                .map(String::as_str),
+        )
+    }
+
+    /// Inserts this [TEC] into the [Record], like [Record::insert], but verifies
+    /// this [Key] is consistent with the [Header] first, so the result can be formatted:
+    /// - the coordinates must lie on a grid node, otherwise [Error::OffGrid] is returned
+    /// - the [Epoch] must lie within the temporal axis, otherwise [Error::OutsideTemporalBoundaries]
+    /// is returned, and must be a multiple of the sampling period, otherwise
+    /// [Error::OffTemporalAxis] is returned.
+    pub fn insert_checked(&mut self, key: Key, tec: TEC) -> Result<(), Error> {
+        let grid = &self.header.grid;
+
+        if !is_linspace_node(&grid.latitude, key.latitude_ddeg())
+            || !is_linspace_node(&grid.longitude, key.longitude_ddeg())
+            || !is_linspace_node(&grid.altitude, key.altitude_km())
+        {
+            return Err(Error::OffGrid);
+        }
+
+        let (t0, t1) = (
+            self.header.epoch_of_first_map,
+            self.header.epoch_of_last_map,
+        );
+
+        if key.epoch < t0 || key.epoch > t1 {
+            return Err(Error::OutsideTemporalBoundaries);
+        }
+
+        let dt = self.header.sampling_period.total_nanoseconds();
+
+        if dt > 0 && (key.epoch - t0).total_nanoseconds() % dt != 0 {
+            return Err(Error::OffTemporalAxis);
+        }
+
+        self.record.insert(key, tec);
+        Ok(())
+    }
+
+    /// Re-maps all the nodes of this [IONEX] onto this new [Grid], by position:
+    /// the n-th node of each axis becomes the n-th node of the new axis,
+    /// and the [Header] is updated. Use this to relabel the coordinates
+    /// (for example, 0..360° to -180..180° longitudes) without breaking the lookups.
+    /// Both grids must have the same number of nodes on each axis, otherwise
+    /// [Error::GridMismatch] is returned. Nodes that do not lie on the current grid
+    /// are not re-mapped and [Error::OffGrid] is returned: self is not modified on error.
+    pub fn reindex_to_grid(&mut self, new_grid: Grid) -> Result<(), Error> {
+        let grid = self.header.grid;
+
+        let mismatches = [
+            ("latitude", grid.latitude, new_grid.latitude),
+            ("longitude", grid.longitude, new_grid.longitude),
+            ("altitude", grid.altitude, new_grid.altitude),
+        ]
+        .iter()
+        .filter(|(_, current, new)| current.length() != new.length())
+        .map(|(axis, current, new)| {
+            format!("{} nodes {} vs {}", axis, current.length(), new.length())
+        })
+        .collect::<Vec<_>>();
+
+        if !mismatches.is_empty() {
+            return Err(Error::GridMismatch(mismatches));
+        }
+
+        let reindex = |current: &Linspace, new: &Linspace, value: f64| {
+            if is_linspace_node(current, value) {
+                Ok(new.start + linspace_node_index(current, value) as f64 * new.spacing)
+            } else {
+                Err(Error::OffGrid)
+            }
+        };
+
+        let mut record = Record::default();
+
+        for (k, v) in self.record.iter() {
+            let key = Key::from_decimal_degrees_km(
+                k.epoch,
+                reindex(&grid.latitude, &new_grid.latitude, k.latitude_ddeg())?,
+                reindex(&grid.longitude, &new_grid.longitude, k.longitude_ddeg())?,
+                reindex(&grid.altitude, &new_grid.altitude, k.altitude_km())?,
+            );
+
+            record.insert(key, *v);
+        }
+
+        self.record = record;
+        self.header.grid = new_grid;
+        Ok(())
+    }
+
     /// Returns true if this [IONEX] is 2D (planar TEC map, not 3D volume).
     pub fn is_2d(&self) -> bool {
         self.header.map_dimension == 2
@@ -288,6 +495,804 @@ impl IONEX {
         self.header.grid.altitude.width()
     }
 
+    /// Returns total latitude range covered, in decimal degrees,
+    /// regardless of the grid direction.
+    pub fn latitude_span_degrees(&self) -> f64 {
+        self.header.grid.latitude.width().abs()
+    }
+
+    /// Returns total longitude range covered, in decimal degrees,
+    /// regardless of the grid direction.
+    pub fn longitude_span_degrees(&self) -> f64 {
+        self.header.grid.longitude.width().abs()
+    }
+
+    /// Changes the [Header::base_radius_km] to this value (in kilometers), while preserving
+    /// the absolute shell geometry: the altitude grid and all [Record] altitudes are shifted
+    /// accordingly, so [Header::shell_radius_km] is not modified.
+    /// This allows comparing files from providers using different base radii.
+    pub fn normalize_base_radius(&mut self, target_km: f32) {
+        let offset_km = self.header.base_radius_km as f64 - target_km as f64;
+
+        if offset_km == 0.0 {
+            return;
+        }
+
+        self.header.base_radius_km = target_km;
+        self.header.grid.altitude.start += offset_km;
+        self.header.grid.altitude.end += offset_km;
+
+        self.record.map = self
+            .record
+            .map
+            .iter()
+            .map(|(k, v)| {
+                let key = Key::from_decimal_degrees_km(
+                    k.epoch,
+                    k.latitude_ddeg(),
+                    k.longitude_ddeg(),
+                    k.altitude_km() + offset_km,
+                );
+
+                (key, *v)
+            })
+            .collect();
+    }
+
+    /// Returns the fixed altitude (in kilometers) of this 2D [IONEX].
+    /// 2D processing ([Self::map_cell_iter], [Record::from_map_cells]..) assumes a single
+    /// altitude, this returns [Error::NotFixedAltitude] for 3D maps instead of
+    /// silently using the lowest layer.
+    pub fn fixed_altitude_km(&self) -> Result<f64, Error> {
+        if self.is_2d() {
+            Ok(self.header.grid.altitude.start)
+        } else {
+            Err(Error::NotFixedAltitude)
+        }
+    }
+
+    /// Returns the latitude [Linspace] (in decimal degrees) described in [Header].
+    pub fn latitude_grid(&self) -> Linspace {
+        self.header.grid.latitude
+    }
+
+    /// Returns the longitude [Linspace] (in decimal degrees) described in [Header].
+    pub fn longitude_grid(&self) -> Linspace {
+        self.header.grid.longitude
+    }
+
+    /// Returns the altitude [Linspace] (in kilometers) described in [Header].
+    pub fn altitude_grid(&self) -> Linspace {
+        self.header.grid.altitude
+    }
+
+    /// Returns the (latitude, longitude) grid spacing, in decimal degrees.
+    pub fn grid_spacing_degrees(&self) -> (f64, f64) {
+        (
+            self.header.grid.latitude.spacing,
+            self.header.grid.longitude.spacing,
+        )
+    }
+
+    /// Returns the TEC profile along the equator (latitude 0°) at this [Epoch],
+    /// as (longitude in decimal degrees, TECu) tuples, from westernmost to easternmost,
+    /// sampled at every grid longitude. Each value is obtained with [Self::interpolate_tec],
+    /// so the equator does not need to be a grid node, and this [Epoch] does not need
+    /// to be sampled. Longitudes that cannot be interpolated (missing nodes) are omitted.
+    /// Returns an empty list if the map does not cross the equator.
+    pub fn equatorial_profile(&self, epoch: Epoch) -> Vec<(f64, f64)> {
+        self.header
+            .grid
+            .longitude
+            .iter()
+            .sorted_by(|a, b| a.total_cmp(b))
+            .filter_map(|long_ddeg| {
+                let tecu = self.interpolate_tec(epoch, 0.0, long_ddeg).ok()?.tecu();
+
+                if tecu.is_finite() {
+                    Some((long_ddeg, tecu))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the grid nodes, as [Point]s with coordinates in decimal degrees,
+    /// that have no TEC value at this [Epoch]: either absent or explicitly missing
+    /// (see [TEC::missing]). The expected grid is described by the [Header].
+    pub fn missing_coordinates(&self, epoch: Epoch) -> Vec<Point<f64>> {
+        let fixed_altitude_km = self.header.grid.altitude.start;
+
+        self.header
+            .grid
+            .latitude
+            .iter()
+            .cartesian_product(self.header.grid.longitude.iter())
+            .filter_map(|(lat_ddeg, long_ddeg)| {
+                let key =
+                    Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, fixed_altitude_km);
+
+                if self.record.get(&key).is_none_or(|tec| tec.is_missing()) {
+                    Some(Point::new(long_ddeg, lat_ddeg))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the total number of missing TEC values (grid nodes with no value, or an
+    /// explicitly missing value), across all [Epoch]s of the [Record].
+    /// The expected grid is described by the [Header].
+    pub fn missing_count(&self) -> usize {
+        self.epoch_iter()
+            .map(|epoch| self.missing_coordinates(epoch).len())
+            .sum()
+    }
+
+    /// Returns true if at least one TEC (or RMS) value of the [Record] is not finite
+    /// (NaN or infinite), which may happen after degenerate arithmetic or interpolation.
+    /// Explicit missing values (see [TEC::missing]) are not considered.
+    /// Such values would be formatted as garbage, see [Self::replace_non_finite_with_missing].
+    pub fn has_non_finite(&self) -> bool {
+        self.record.iter().any(|(_, tec)| {
+            !tec.is_missing()
+                && (!tec.tecu().is_finite()
+                    || tec.root_mean_square().is_some_and(|rms| !rms.is_finite()))
+        })
+    }
+
+    /// Replaces all non finite values (NaN or infinite) with missing values:
+    /// non finite TEC values are converted to explicit missing values (see [TEC::missing]),
+    /// non finite RMS values are discarded (the TEC value is preserved).
+    /// Explicit missing values are left untouched.
+    /// Returns the number of values that were fixed.
+    /// This is a safety net you should use prior formatting.
+    pub fn replace_non_finite_with_missing(&mut self) -> usize {
+        let mut fixed = 0;
+
+        for (_, tec) in self.record.map.iter_mut() {
+            if tec.is_missing() {
+                continue;
+            }
+
+            if !tec.tecu().is_finite() {
+                *tec = TEC::missing();
+                fixed += 1;
+            } else if tec.root_mean_square().is_some_and(|rms| !rms.is_finite()) {
+                tec.rms = None;
+                fixed += 1;
+            }
+        }
+
+        fixed
+    }
+
+    /// Copies and returns this [IONEX] with latitude grid described from southernmost
+    /// to northernmost. The [Record] is not modified (values are indexed by coordinates),
+    /// only the [Header] convention is, so the maps are formatted in this order.
+    pub fn with_ascending_latitude(&self) -> IONEX {
+        let mut s = self.clone();
+        let (min, max) = s.header.grid.latitude.minmax();
+
+        s.header.grid.latitude = Linspace {
+            start: min,
+            end: max,
+            spacing: s.header.grid.latitude.spacing.abs(),
+        };
+
+        s
+    }
+
+    /// Copies and returns this [IONEX] with latitude grid described from northernmost
+    /// to southernmost (most common convention). The [Record] is not modified
+    /// (values are indexed by coordinates), only the [Header] convention is,
+    /// so the maps are formatted in this order.
+    pub fn with_descending_latitude(&self) -> IONEX {
+        let mut s = self.clone();
+        let (min, max) = s.header.grid.latitude.minmax();
+
+        s.header.grid.latitude = Linspace {
+            start: max,
+            end: min,
+            spacing: -s.header.grid.latitude.spacing.abs(),
+        };
+
+        s
+    }
+
+    /// Iterates over all TEC estimates with their coordinates, as
+    /// (epoch, latitude in decimal degrees, longitude in decimal degrees, altitude in km, [TEC]) tuples,
+    /// in chronological order.
+    ///
+    /// ```
+    /// use ionex::prelude::*;
+    ///
+    /// let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz")
+    ///     .unwrap();
+    ///
+    /// let mut total_tecu = 0.0;
+    ///
+    /// for (_, lat_ddeg, long_ddeg, alt_km, tec) in ionex.points() {
+    ///     assert!(lat_ddeg >= -87.5 && lat_ddeg <= 87.5);
+    ///     assert!(long_ddeg >= -180.0 && long_ddeg <= 180.0);
+    ///     assert_eq!(alt_km, 350.0);
+    ///     total_tecu += tec.tecu();
+    /// }
+    ///
+    /// assert!(total_tecu > 0.0);
+    /// ```
+    pub fn points(&self) -> Box<dyn Iterator<Item = (Epoch, f64, f64, f64, TEC)> + '_> {
+        Box::new(self.record.iter().map(|(k, v)| {
+            (
+                k.epoch,
+                k.latitude_ddeg(),
+                k.longitude_ddeg(),
+                k.altitude_km(),
+                *v,
+            )
+        }))
+    }
+
+    /// Converts this (vertical) TEC [IONEX] to a slant TEC map, assuming a uniform
+    /// elevation angle (in degrees) everywhere. All TEC and RMS values are multiplied by
+    /// the obliquity factor of the [Header] [MappingFunction], evaluated at the shell height
+    /// (fixed altitude) above the base radius. A comment records this transformation.
+    pub fn to_slant_map(&self, elevation_deg: f64) -> IONEX {
+        let mut s = self.clone();
+
+        let obliquity = self.header.mapf.obliquity(
+            elevation_deg,
+            self.header.base_radius_km as f64,
+            self.header.grid.altitude.start,
+        );
+
+        for (_, tec) in s.record.iter_mut() {
+            *tec *= obliquity;
+
+            if let Some(rms) = tec.root_mean_square() {
+                *tec = tec.with_rms(rms * obliquity);
+            }
+        }
+
+        s.header.comments.push(format!(
+            "SLANT TEC MAP AT {:.1} DEG ELEVATION ({})",
+            elevation_deg, self.header.mapf
+        ));
+
+        s
+    }
+
+    /// Compares this [IONEX] to the GPS [KlobucharModel] broadcast ionosphere model.
+    /// The model is evaluated for a receiver located at these coordinates (decimal degrees),
+    /// observing a signal at this elevation and azimuth angles (in degrees).
+    /// Returns the Klobuchar vertical TEC at the ionospheric pierce point (IPP) minus the
+    /// interpolated IONEX vertical TEC at the same point, both in TECu.
+    ///
+    /// ## Input
+    /// - alpha: Klobuchar amplitude coefficients
+    /// - beta: Klobuchar period coefficients
+    /// - epoch: [Epoch] that must fit within the temporal axis
+    /// - receiver: receiver coordinates, as longitude (x) and latitude (y) in decimal degrees
+    /// - elevation_deg: elevation angle, in degrees
+    /// - azimuth_deg: azimuth angle, in degrees
+    pub fn diff_klobuchar(
+        &self,
+        alpha: [f64; 4],
+        beta: [f64; 4],
+        epoch: Epoch,
+        receiver: Point<f64>,
+        elevation_deg: f64,
+        azimuth_deg: f64,
+    ) -> Result<f64, Error> {
+        let model = KlobucharModel::new(alpha, beta);
+        let (ipp, klobuchar_tecu) = model.vertical_tec(epoch, receiver, elevation_deg, azimuth_deg);
+        let tecu = self.interpolated_tecu(epoch, ipp)?;
+        Ok(klobuchar_tecu - tecu)
+    }
+
+    /// Interpolates a complete map at this [Epoch], located between the last map of `a`
+    /// and the first map of `b`. This is typically used at the boundary of two consecutive
+    /// daily files, where neither file alone can interpolate the map.
+    /// Both [IONEX] must share the same [Grid]. Nodes missing in either map
+    /// are also missing in the returned [Record].
+    pub fn lerp_between(a: &IONEX, b: &IONEX, epoch: Epoch) -> Result<Record, Error> {
+        if a.header.grid != b.header.grid {
+            return Err(Error::GridMismatch(a.header.grid.diff(&b.header.grid)));
+        }
+
+        let (t0, t1) = match (a.record.last_epoch(), b.record.first_epoch()) {
+            (Some(t0), Some(t1)) => (t0, t1),
+            _ => return Err(Error::OutsideTemporalBoundaries),
+        };
+
+        if epoch < t0 || epoch > t1 {
+            return Err(Error::OutsideTemporalBoundaries);
+        }
+
+        let dt = if t1 > t0 {
+            (epoch - t0).to_seconds() / (t1 - t0).to_seconds()
+        } else {
+            0.0
+        };
+
+        let mut record = Record::default();
+
+        for (key, tec0) in a.record.synchronous_iter(t0) {
+            let coordinates = key.coordinates;
+
+            let tec1 = match b.record.get(&Key {
+                epoch: t1,
+                coordinates,
+            }) {
+                Some(tec1) => tec1,
+                None => continue,
+            };
+
+            let mut tec = TEC::from_tecu(tec0.tecu() + (tec1.tecu() - tec0.tecu()) * dt);
+
+            if let (Some(rms0), Some(rms1)) = (tec0.root_mean_square(), tec1.root_mean_square()) {
+                tec = tec.with_rms(rms0 + (rms1 - rms0) * dt);
+            }
+
+            record.insert(Key { epoch, coordinates }, tec);
+        }
+
+        Ok(record)
+    }
+
+    /// Interpolates the [TEC] (and its RMS) at these coordinates (decimal degrees) and this [Epoch].
+    /// The four surrounding grid nodes are bilinearly interpolated, and the two surrounding maps
+    /// are linearly interpolated when this [Epoch] is not sampled. Grid aligned coordinates
+    /// and epochs are not interpolated. This returns:
+    /// - [Error::OutsideTemporalBoundaries] when this [Epoch] is not surrounded by two maps
+    /// - [Error::OutsideSpatialBoundaries] when these coordinates lie outside of the grid,
+    ///   or one of the surrounding nodes is missing.
+    ///
+    /// When the grid longitudes span the whole globe (worldwide maps), longitudes wrap around
+    /// the globe, so coordinates lying between the easternmost and westernmost longitudes
+    /// are interpolated too, see [Self::map_cell_iter_wrapped].
+    /// This assumes a 2D map, see [Self::fixed_altitude_km].
+    /// Use [Self::sample_at] for bulk workloads.
+    pub fn interpolate_tec(
+        &self,
+        epoch: Epoch,
+        lat_ddeg: f64,
+        long_ddeg: f64,
+    ) -> Result<TEC, Error> {
+        let point = Point::new(long_ddeg, lat_ddeg);
+
+        let (mut t0, mut t1) = (None, None);
+
+        for t in self.epoch_iter() {
+            if t <= epoch {
+                t0 = Some(t);
+            }
+
+            if t >= epoch {
+                t1 = Some(t);
+                break;
+            }
+        }
+
+        let (t0, t1) = match (t0, t1) {
+            (Some(t0), Some(t1)) => (t0, t1),
+            _ => return Err(Error::OutsideTemporalBoundaries),
+        };
+
+        let tec0 = self.spatially_interpolated_tec(t0, point)?;
+
+        if t0 == t1 {
+            return Ok(tec0);
+        }
+
+        let tec1 = self.spatially_interpolated_tec(t1, point)?;
+        let dt = (epoch - t0).to_seconds() / (t1 - t0).to_seconds();
+
+        Ok(TEC::interpolated([(tec0, 1.0 - dt), (tec1, dt)]))
+    }
+
+    /// Interpolates the TEC (in TECu) at these coordinates (decimal degrees) and this [Epoch],
+    /// see [Self::interpolate_tec].
+    pub(crate) fn interpolated_tecu(&self, epoch: Epoch, point: Point<f64>) -> Result<f64, Error> {
+        let tec = self.interpolate_tec(epoch, point.y(), point.x())?;
+        Ok(tec.tecu())
+    }
+
+    /// Bilinear interpolation of the [TEC] (and its RMS) at these coordinates (decimal degrees),
+    /// for this [Epoch] that must exist in the [Record].
+    fn spatially_interpolated_tec(&self, epoch: Epoch, point: Point<f64>) -> Result<TEC, Error> {
+        let grid = &self.header.grid;
+        let altitude_km = grid.altitude.start;
+
+        let nodes = bilinear_nodes(&grid.latitude, &grid.longitude, point)?;
+
+        let samples = nodes
+            .into_iter()
+            .map(|((lat_ddeg, long_ddeg), weight)| {
+                let key = Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, altitude_km);
+
+                self.record
+                    .get(&key)
+                    .map(|tec| (*tec, weight))
+                    .ok_or(Error::OutsideSpatialBoundaries)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TEC::interpolated(samples))
+    }
+
+    /// Interpolates the [TEC] for a batch of (epoch, coordinates in decimal degrees) queries.
+    /// Like single point interpolation, the four surrounding grid nodes are bilinearly
+    /// interpolated, and the two surrounding maps are linearly interpolated when the [Epoch]
+    /// is not sampled. Queries are processed in chronological order and each map is gathered
+    /// only once, then reused by all queries that need it: this is much faster than
+    /// individual queries, for bulk workloads.
+    ///
+    /// Results are returned in the order of the queries.
+    /// This assumes a 2D map, see [Self::fixed_altitude_km].
+    pub fn sample_at(&self, queries: &[(Epoch, Point<f64>)]) -> Vec<Result<TEC, Error>> {
+        let epochs = self.epoch_iter().collect::<Vec<_>>();
+
+        let mut order = (0..queries.len()).collect::<Vec<_>>();
+        order.sort_by_key(|i| queries[*i].0);
+
+        let mut results = queries
+            .iter()
+            .map(|_| Err(Error::OutsideTemporalBoundaries))
+            .collect::<Vec<_>>();
+
+        // maps currently in use
+        let mut maps = BTreeMap::<Epoch, Vec<Option<TEC>>>::new();
+
+        for i in order {
+            let (epoch, point) = queries[i];
+
+            let position = epochs.partition_point(|t| *t < epoch);
+
+            let (t0, t1) = match epochs.get(position) {
+                Some(t) if *t == epoch => (epoch, epoch),
+                Some(t1) if position > 0 => (epochs[position - 1], *t1),
+                _ => continue,
+            };
+
+            // queries are sorted: older maps are no longer needed
+            maps.retain(|t, _| *t >= t0);
+
+            for t in [t0, t1] {
+                maps.entry(t).or_insert_with(|| self.dense_map(t));
+            }
+
+            let tec0 = match self.dense_interpolated_tec(&maps[&t0], point) {
+                Ok(tec0) => tec0,
+                Err(e) => {
+                    results[i] = Err(e);
+                    continue;
+                },
+            };
+
+            results[i] = if t0 == t1 {
+                Ok(tec0)
+            } else {
+                self.dense_interpolated_tec(&maps[&t1], point).map(|tec1| {
+                    let dt = (epoch - t0).to_seconds() / (t1 - t0).to_seconds();
+                    TEC::interpolated([(tec0, 1.0 - dt), (tec1, dt)])
+                })
+            };
+        }
+
+        results
+    }
+
+    /// Gathers the map at this [Epoch] as a dense grid of [TEC] values,
+    /// latitude major and indexed by node position. Missing values are set to None.
+    fn dense_map(&self, epoch: Epoch) -> Vec<Option<TEC>> {
+        let grid = &self.header.grid;
+        let altitude_km = grid.altitude.start;
+
+        grid.latitude
+            .iter()
+            .flat_map(|lat_ddeg| {
+                grid.longitude
+                    .iter()
+                    .map(move |long_ddeg| (lat_ddeg, long_ddeg))
+            })
+            .map(|(lat_ddeg, long_ddeg)| {
+                let key = Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, altitude_km);
+                self.record.get(&key).copied()
+            })
+            .collect()
+    }
+
+    /// Bilinear interpolation of the [TEC] (and its RMS) at these coordinates
+    /// (decimal degrees), within a map gathered by [Self::dense_map].
+    fn dense_interpolated_tec(&self, map: &[Option<TEC>], point: Point<f64>) -> Result<TEC, Error> {
+        let grid = &self.header.grid;
+        let num_longitudes = grid.longitude.length();
+
+        let nodes = bilinear_nodes(&grid.latitude, &grid.longitude, point)?;
+
+        let samples = nodes
+            .into_iter()
+            .map(|((lat_ddeg, long_ddeg), weight)| {
+                let index = linspace_node_index(&grid.latitude, lat_ddeg) * num_longitudes
+                    + linspace_node_index(&grid.longitude, long_ddeg);
+
+                map.get(index)
+                    .copied()
+                    .flatten()
+                    .map(|tec| (tec, weight))
+                    .ok_or(Error::OutsideSpatialBoundaries)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TEC::interpolated(samples))
+    }
+
+    /// Converts this [IONEX] to another [MappingFunction] convention, so files
+    /// using different mapping functions can be compared. The slant TEC being preserved
+    /// (VTEC_to * M_to = VTEC_from * M_from), all TEC and RMS values are rescaled by the ratio
+    /// of the current obliquity factor to the target one, evaluated at this
+    /// reference elevation angle (in degrees). Explicit missing values (see [TEC::missing])
+    /// are preserved. The [Header] mapping function is updated
+    /// and a comment records this transformation.
+    pub fn convert_mapping_function(&self, to: MappingFunction, elevation_deg: f64) -> IONEX {
+        let mut s = self.clone();
+
+        let (base_radius_km, shell_height_km) = (
+            self.header.base_radius_km as f64,
+            self.header.grid.altitude.start,
+        );
+
+        let ratio = self
+            .header
+            .mapf
+            .obliquity(elevation_deg, base_radius_km, shell_height_km)
+            / to.obliquity(elevation_deg, base_radius_km, shell_height_km);
+
+        for (_, tec) in s.record.iter_mut() {
+            if tec.is_missing() {
+                continue;
+            }
+
+            *tec *= ratio;
+
+            if let Some(rms) = tec.root_mean_square() {
+                *tec = tec.with_rms(rms * ratio);
+            }
+        }
+
+        s.header.mapf = to;
+
+        s.header.comments.push(format!(
+            "MAPPING FUNCTION {} CONVERTED TO {} AT {:.1} DEG",
+            self.header.mapf, to, elevation_deg
+        ));
+
+        s
+    }
+
+    /// Returns a deterministic hash of this [IONEX] content, that may be used for caching.
+    /// It is obtained from the grid definition and all TEC (and RMS) values,
+    /// with their spatial and temporal coordinates.
+    /// Values are hashed as real values, so two [IONEX] that only differ by their
+    /// quantization exponent (see [Self::set_exponent]) have the same hash.
+    /// Comments, file attributes and other [Header] fields are not hashed.
+    /// The hash is only stable for a given build of this library.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        for linspace in [
+            self.header.grid.latitude,
+            self.header.grid.longitude,
+            self.header.grid.altitude,
+        ] {
+            linspace.start.to_bits().hash(&mut hasher);
+            linspace.end.to_bits().hash(&mut hasher);
+            linspace.spacing.to_bits().hash(&mut hasher);
+        }
+
+        for (k, v) in self.record.iter() {
+            k.epoch
+                .to_tai_duration()
+                .total_nanoseconds()
+                .hash(&mut hasher);
+            k.latitude_ddeg().to_bits().hash(&mut hasher);
+            k.longitude_ddeg().to_bits().hash(&mut hasher);
+            k.altitude_km().to_bits().hash(&mut hasher);
+            v.tecu().to_bits().hash(&mut hasher);
+            v.root_mean_square().map(f64::to_bits).hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Converts this [IONEX] to a 0°..360° longitude grid, as expected by some models.
+    /// Longitudes are remapped into 0..360 and snapped to the grid,
+    /// and the 0° meridian is duplicated at 360°, so both are present.
+    /// Regional maps only cover their own span: it is shifted so it starts within 0..360,
+    /// and remains contiguous (it may extend past 360° when crossing the 0° meridian).
+    /// The grid spacing is preserved. This is the inverse of [Self::to_symmetric_longitude].
+    pub fn to_zero_to_360(&self) -> IONEX {
+        let grid = Linspace {
+            start: 0.0,
+            end: 360.0,
+            spacing: self.header.grid.longitude.spacing.abs(),
+        };
+
+        self.longitude_remapped(grid, |long_ddeg| long_ddeg.rem_euclid(360.0), (0.0, 360.0))
+    }
+
+    /// Converts this [IONEX] to a -180°..180° longitude grid (most common convention).
+    /// Longitudes are remapped into -180..180 and snapped to the grid,
+    /// and the 180° meridian is duplicated at -180°, so both are present.
+    /// Regional maps only cover their own span: it is shifted so it starts within -180..180,
+    /// and remains contiguous (it may extend past 180° when crossing the 180° meridian).
+    /// The grid spacing is preserved. This is the inverse of [Self::to_zero_to_360].
+    pub fn to_symmetric_longitude(&self) -> IONEX {
+        let grid = Linspace {
+            start: -180.0,
+            end: 180.0,
+            spacing: self.header.grid.longitude.spacing.abs(),
+        };
+
+        self.longitude_remapped(
+            grid,
+            |long_ddeg| {
+                let long_ddeg = long_ddeg.rem_euclid(360.0);
+                if long_ddeg > 180.0 {
+                    long_ddeg - 360.0
+                } else {
+                    long_ddeg
+                }
+            },
+            (180.0, -180.0),
+        )
+    }
+
+    /// Remaps all longitudes onto this new longitude grid.
+    /// The `duplicated` meridian (source, destination) is copied.
+    /// When several longitudes collide, the easternmost one is preserved.
+    /// Regional maps are not stretched to this grid, see [Self::longitude_shifted].
+    fn longitude_remapped<F: Fn(f64) -> f64>(
+        &self,
+        grid: Linspace,
+        remap: F,
+        duplicated: (f64, f64),
+    ) -> IONEX {
+        if !wraps_around_globe(&self.header.grid.longitude) {
+            return self.longitude_shifted(remap);
+        }
+
+        let mut record = Record::default();
+
+        for (k, v) in self.record.iter() {
+            let mut long_ddeg = remap(k.longitude_ddeg());
+
+            // snap to grid
+            if grid.spacing > 0.0 {
+                long_ddeg =
+                    grid.start + ((long_ddeg - grid.start) / grid.spacing).round() * grid.spacing;
+            }
+
+            let (lat_ddeg, alt_km) = (k.latitude_ddeg(), k.altitude_km());
+
+            let key = Key::from_decimal_degrees_km(k.epoch, lat_ddeg, long_ddeg, alt_km);
+            record.insert(key, *v);
+
+            if long_ddeg == duplicated.0 {
+                let key = Key::from_decimal_degrees_km(k.epoch, lat_ddeg, duplicated.1, alt_km);
+                record.insert(key, *v);
+            }
+        }
+
+        let mut ionex = self.with_record(record);
+        ionex.header.grid.longitude = grid;
+        ionex
+    }
+
+    /// Shifts all longitudes of this regional map by a whole number of turns,
+    /// so the westernmost longitude is remapped, while the span remains contiguous.
+    fn longitude_shifted<F: Fn(f64) -> f64>(&self, remap: F) -> IONEX {
+        let (min, _) = self.header.grid.longitude.minmax();
+        let offset = ((remap(min) - min) / 360.0).round() * 360.0;
+
+        let mut record = Record::default();
+
+        for (k, v) in self.record.iter() {
+            let key = Key::from_decimal_degrees_km(
+                k.epoch,
+                k.latitude_ddeg(),
+                k.longitude_ddeg() + offset,
+                k.altitude_km(),
+            );
+
+            record.insert(key, *v);
+        }
+
+        let mut ionex = self.with_record(record);
+        ionex.header.grid.longitude.start += offset;
+        ionex.header.grid.longitude.end += offset;
+        ionex
+    }
+
+    /// Returns the TEC values (in TECu) of the map at this [Epoch], as a flat array
+    /// that follows the canonical grid order: latitude from northernmost to southernmost,
+    /// then longitude from westernmost to easternmost.
+    /// Missing values are replaced by NaN, so the returned array always has `lat_len * long_len` values.
+    /// This [Epoch] must exist in the [Record].
+    pub fn epoch_values(&self, epoch: Epoch) -> Result<Vec<f64>, Error> {
+        if self.record.synchronous_iter(epoch).next().is_none() {
+            return Err(Error::OutsideTemporalBoundaries);
+        }
+
+        let fixed_altitude_km = self.header.grid.altitude.start;
+
+        let mut latitudes = self.header.grid.latitude.iter().collect::<Vec<_>>();
+        let mut longitudes = self.header.grid.longitude.iter().collect::<Vec<_>>();
+
+        if self.header.grid.latitude.spacing > 0.0 {
+            latitudes.reverse();
+        }
+
+        if self.header.grid.longitude.spacing < 0.0 {
+            longitudes.reverse();
+        }
+
+        let mut values = Vec::with_capacity(latitudes.len() * longitudes.len());
+
+        for lat_ddeg in latitudes.iter() {
+            for long_ddeg in longitudes.iter() {
+                let key =
+                    Key::from_decimal_degrees_km(epoch, *lat_ddeg, *long_ddeg, fixed_altitude_km);
+
+                match self.record.get(&key) {
+                    Some(tec) => values.push(tec.tecu()),
+                    None => values.push(f64::NAN),
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Re-quantizes the entire [Record] (TEC and RMS values) using this exponent (scaling),
+    /// and updates the [Header] accordingly. The physical values are preserved
+    /// (within the new quantization precision), only the stored representation is modified.
+    /// This is typically used to generate files that other tools expect to use a specific scaling.
+    pub fn set_exponent(&mut self, exponent: i8) {
+        for (_, tec) in self.record.iter_mut() {
+            tec.requantize_mut(exponent);
+        }
+
+        self.header.exponent = exponent;
+    }
+
+    /// Returns the maximal error (in TECu) introduced by the quantization of the TEC
+    /// (and RMS) values, which is half the least significant bit at the current
+    /// [Header] exponent. For example, an exponent of -1 (0.1 TECu resolution)
+    /// gives a maximal error of 0.05 TECu. Use [Self::set_exponent] to modify it.
+    pub fn max_quantization_error_tecu(&self) -> f64 {
+        0.5 * 10.0_f64.powi(self.header.exponent as i32)
+    }
+
+    /// Returns the maximal error introduced by the quantization of the coordinates,
+    /// as (latitude in decimal degrees, longitude in decimal degrees, altitude in kilometers).
+    /// On each axis, this is half the least significant bit of the quantization
+    /// used when parsing the maps, which is deduced from the grid spacing.
+    pub fn max_coordinates_quantization_error(&self) -> (f64, f64, f64) {
+        let error = |linspace: &Linspace| {
+            0.5 * 10.0_f64.powi(-(Quantized::find_exponent(linspace.spacing) as i32))
+        };
+
+        (
+            error(&self.header.grid.latitude),
+            error(&self.header.grid.longitude),
+            error(&self.header.grid.altitude),
+        )
+    }
+
     /// Returns a file name that would describe [Self] according to the
     /// standards.
     pub fn generate_standardized_filename(&self) -> String {
@@ -352,16 +1357,54 @@ impl IONEX {
         })
     }
 
+    /// Converts this [Epoch] to a fractional UTC day of year, starting at 1.0 on January 1st
+    /// at midnight (like the DOY of [FileAttributes]). For example, 2022-01-02T12:00:00 UTC is 2.5.
+    /// This is typically used as x-axis when plotting time series.
+    pub fn epoch_to_doy_fraction(&self, epoch: Epoch) -> f64 {
+        let (year, _, _, _, _, _, _) = epoch.to_gregorian_utc();
+        let elapsed = epoch - Epoch::from_gregorian_utc_at_midnight(year, 1, 1);
+        elapsed.to_unit(Unit::Day) + 1.0
+    }
+
     /// Parse [IONEX] content by consuming [BufReader] (efficient buffered reader).
     /// Attributes potentially described by a file name need to be provided either
     /// manually / externally, or guessed when parsing has been completed.
     pub fn parse<R: Read>(reader: &mut BufReader<R>) -> Result<Self, ParsingError> {
+        Self::parse_with(reader, ParseOptions::default())
+    }
+
+    /// Parse [IONEX] from [Read]able I/O, like [Self::parse], using custom [ParseOptions].
+    /// For example, [MissingPolicy::InsertMissing](crate::prelude::MissingPolicy::InsertMissing)
+    /// gives a dense [Record].
+    pub fn parse_with<R: Read>(
+        reader: &mut BufReader<R>,
+        options: ParseOptions,
+    ) -> Result<Self, ParsingError> {
         // Parses Header section (=consumes header until this point)
         let mut header = Header::parse(reader)?;
 
         // Parse record (=consumes rest of this resource)
         // Comments are preserved and store "as is"
-        let (record, comments) = Record::parse(&mut header, reader)?;
+        let (record, comments) = Record::parse_with(&mut header, reader, options)?;
+
+        // Some files omit the first and/or last epoch descriptors:
+        // backfill them from the actual content, otherwise the temporal methods
+        // would operate on an invalid time frame.
+        if let Some(last_epoch) = record.last_epoch() {
+            if header.epoch_of_last_map == Epoch::default()
+                || header.epoch_of_last_map < header.epoch_of_first_map
+            {
+                header.epoch_of_last_map = last_epoch;
+            }
+        }
+
+        if let Some(first_epoch) = record.first_epoch() {
+            if header.epoch_of_first_map == Epoch::default()
+                || header.epoch_of_first_map > header.epoch_of_last_map
+            {
+                header.epoch_of_first_map = first_epoch;
+            }
+        }
 
         Ok(Self {
             header,
@@ -371,27 +1414,75 @@ impl IONEX {
         })
     }
 
+    /// Parses [IONEX] from any [Read]able interface (for example, a byte slice
+    /// or a network stream), which is internally buffered.
+    /// Use [Self::parse] if you already have a [BufReader].
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ParsingError> {
+        let mut reader = BufReader::new(reader);
+        Self::parse(&mut reader)
+    }
+
     /// Format [RINEX] into writable I/O using efficient buffered writer
     /// and following standard specifications. The revision to be followed is defined
     /// in [Header] section. This is the mirror operation of [Self::parse].
     pub fn format<W: Write>(&self, writer: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        self.header.format(writer)?;
+        self.format_with_header(&self.header, writer)
+    }
 
-        // format all comments at beginning of file
-        for comment in self.comments.iter() {
+    /// Format [IONEX] into writable I/O, like [Self::format], but using this [Header]
+    /// instead of the embedded one. This allows re-headering (for example, a new program
+    /// or agency) without copying the [Record], as [Self::with_header] would.
+    /// The [Header] grid must describe all the [Record] coordinates, otherwise
+    /// [FormattingError::GridMismatch] is returned and nothing is written.
+    pub fn format_with_header<W: Write>(
+        &self,
+        header: &Header,
+        writer: &mut BufWriter<W>,
+    ) -> Result<(), FormattingError> {
+        let grid = &header.grid;
+
+        for key in self.record.map.keys() {
+            if !is_linspace_node(&grid.latitude, key.latitude_ddeg())
+                || !is_linspace_node(&grid.longitude, key.longitude_ddeg())
+                || !is_linspace_node(&grid.altitude, key.altitude_km())
+            {
+                return Err(FormattingError::GridMismatch);
+            }
+        }
+
+        header.format(writer)?;
+
+        // record section comments are formatted right after the header,
+        // first occurrence only and not already described in the header
+        for comment in self
+            .comments
+            .iter()
+            .unique()
+            .filter(|comment| !header.comments.contains(comment))
+        {
             writeln!(writer, "{}", fmt_comment(comment))?;
         }
 
-        self.record.format(&self.header, writer)?;
+        self.record.format(header, writer)?;
 
         writer.flush()?;
         Ok(())
     }
 
+    /// Format [IONEX] into any [Write]able interface (for example, a `Vec<u8>`
+    /// or a network stream), which is internally buffered.
+    /// Use [Self::format] if you already have a [BufWriter].
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), FormattingError> {
+        let mut writer = BufWriter::new(writer);
+        self.format(&mut writer)
+    }
+
     /// Parses [IONEX] from local readable file.
     ///
     /// Will panic if provided file does not exist or is not readable.
-    /// See [Self::from_gzip_file] for seamless Gzip support.
+    /// When the `flate2` feature is active, gzip compressed files are detected
+    /// from their content (regardless of the file extension) and decoded transparently,
+    /// see [Self::from_gzip_file].
     ///
     /// If file name follows standard naming conventions, then internal definitions
     /// will truly be complete. Otherwise [FileAttributes] cannot be fully determined.
@@ -419,6 +1510,13 @@ impl IONEX {
         let fd = File::open(path)?;
         let mut reader = BufReader::new(fd);
 
+        #[cfg(feature = "flate2")]
+        if reader.fill_buf()?.starts_with(&GZIP_MAGIC_BYTES) {
+            let mut ionex = Self::from_gzip_reader(reader, None)?;
+            ionex.attributes = file_attributes;
+            return Ok(ionex);
+        }
+
         let mut ionex = Self::parse(&mut reader)?;
         ionex.attributes = file_attributes;
 
@@ -485,72 +1583,390 @@ impl IONEX {
     pub fn from_gzip_file<P: AsRef<Path>>(path: P) -> Result<IONEX, ParsingError> {
         let path = path.as_ref();
 
+        let filename = path
+            .file_name()
+            .map(|filename| filename.to_string_lossy().to_string());
+
+        let fd = File::open(path)?;
+
+        Self::from_gzip_reader(fd, filename.as_deref())
+    }
+
+    /// Parses [IONEX] from any gzip compressed [Read]able interface
+    /// (for example, an object storage stream), which is internally decoded and buffered.
+    ///
+    /// The optional `hint` is the file name, from which we deduce the [FileAttributes]
+    /// when it follows standard naming conventions (see [Self::from_file]).
+    ///
+    /// This is the mirror operation of [Self::write_gzip].
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn from_gzip_reader<R: Read>(reader: R, hint: Option<&str>) -> Result<IONEX, ParsingError> {
         // deduce all we can from file name
-        let file_attributes = match path.file_name() {
-            Some(filename) => {
-                let filename = filename.to_string_lossy().to_string();
-                if let Ok(prod) = FileAttributes::from_str(&filename) {
-                    Some(prod)
+        let file_attributes = hint.and_then(|filename| FileAttributes::from_str(filename).ok());
+
+        let reader = GzDecoder::new(reader);
+        let mut reader = BufReader::new(reader);
+
+        let mut ionex = Self::parse(&mut reader)?;
+        ionex.attributes = file_attributes;
+
+        Ok(ionex)
+    }
+
+    /// Dumps and gzip encodes [IONEX] into writable local file,
+    /// using efficient buffered formatting.
+    ///
+    /// This operation is [Self::from_gzip_file] mirror operation.
+    /// ```
+    /// // Read a IONEX and dump it without any modifications
+    /// use ionex::prelude::*;
+    ///
+    /// let ionex = IONEX::from_file("data/IONEX/V1/CKMG0020.22I.gz")
+    ///   .unwrap();
+    ///
+    /// assert!(ionex.to_gzip_file("test.txt.gz").is_ok());
+    /// ```
+    ///
+    /// Other useful methods are:
+    ///   * [Self::generate_standardized_filename]: to generate a standardize file name
+    ///   * [Self::guess_file_attributes] in close relation: helps generate standardized
+    /// filenames for files that did now follow the convention
+    ///   * [gnss_qc_traits::Merge]: to combine two files to one another
+    ///   * [Self::to_regional_roi]: to reduce a larger (possibly [Region::GlobalWorldwide] map)
+    /// to a given ROI
+    ///   * [Self::to_worldwide_map]: to enlarge a possibly [Region::Regional] map to a [Region::GlobalWorldwide] IONEX.
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn to_gzip_file<P: AsRef<Path>>(&self, path: P) -> Result<(), FormattingError> {
+        let fd = File::create(path)?;
+        self.write_gzip(fd, 5)
+    }
+
+    /// Dumps and gzip encodes [IONEX] into any [Write]able interface
+    /// (for example, an object storage stream), using efficient buffered formatting.
+    /// `level` is the compression level, from 0 (no compression) to 9 (best compression).
+    /// The gzip stream is completed (trailer written) before returning.
+    ///
+    /// This is the mirror operation of [Self::from_gzip_reader].
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn write_gzip<W: Write>(&self, writer: W, level: u32) -> Result<(), FormattingError> {
+        let compression = GzCompression::new(level);
+        let mut writer = BufWriter::new(GzEncoder::new(writer, compression));
+
+        self.format(&mut writer)?;
+
+        let encoder = writer.into_inner().map_err(|e| e.into_error())?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Parses and concatenates a batch of local files (for example, a directory of daily files),
+    /// in chronological order, using [Self::append_in_time].
+    /// Files with a `.gz` extension are gzip decompressed (requires the `flate2` feature).
+    /// A faulty file does not abort the batch: the returned [IONEX] comes with the list
+    /// of files that could not be parsed or concatenated, and the related [Error].
+    /// Returns [Error::NoFileParsed] when no file could be parsed at all.
+    pub fn from_files<P: AsRef<Path>>(
+        paths: &[P],
+    ) -> Result<(IONEX, Vec<(PathBuf, Error)>), Error> {
+        let mut failures = Vec::new();
+        let mut parsed = Vec::with_capacity(paths.len());
+
+        for path in paths.iter() {
+            let path = path.as_ref();
+
+            #[cfg(feature = "flate2")]
+            let ionex = if path.extension().is_some_and(|ext| ext == "gz") {
+                Self::from_gzip_file(path)
+            } else {
+                Self::from_file(path)
+            };
+
+            #[cfg(not(feature = "flate2"))]
+            let ionex = Self::from_file(path);
+
+            match ionex {
+                Ok(ionex) => parsed.push((path.to_path_buf(), ionex)),
+                Err(e) => failures.push((path.to_path_buf(), Error::Parsing(e))),
+            }
+        }
+
+        parsed.sort_by_key(|(_, ionex)| {
+            ionex
+                .record
+                .first_epoch()
+                .unwrap_or(ionex.header.epoch_of_first_map)
+        });
+
+        let mut parsed = parsed.into_iter();
+
+        let (_, mut ionex) = parsed.next().ok_or(Error::NoFileParsed)?;
+
+        for (path, rhs) in parsed {
+            if let Err(e) = ionex.append_in_time(&rhs) {
+                failures.push((path, e));
+            }
+        }
+
+        Ok((ionex, failures))
+    }
+
+    /// Blends this [IONEX] with another estimate of the same maps (for example,
+    /// from another analysis center), using [Record::blend_mut]: coincident TEC values
+    /// are weighted (`weight` being our own weight, within [0; 1]) and coincident RMS
+    /// are combined in quadrature, assuming statistically independent estimates.
+    /// Both [IONEX] must share the same [Grid].
+    pub fn blend(&self, rhs: &IONEX, weight: f64) -> Result<IONEX, Error> {
+        if self.header.grid != rhs.header.grid {
+            return Err(Error::GridMismatch(self.header.grid.diff(&rhs.header.grid)));
+        }
+
+        let mut s = self.clone();
+        s.record.blend_mut(&rhs.record, weight)?;
+        Ok(s)
+    }
+
+    /// Appends the maps of this [IONEX] to ourselves, in chronological order.
+    /// Both [IONEX] must share the same [Grid], otherwise [Error::GridMismatch] is returned.
+    /// Maps we already describe are preserved. The [Header] temporal description
+    /// (first and last epochs, number of maps) is updated accordingly.
+    pub fn append_in_time(&mut self, rhs: &IONEX) -> Result<(), Error> {
+        if self.header.grid != rhs.header.grid {
+            return Err(Error::GridMismatch(self.header.grid.diff(&rhs.header.grid)));
+        }
+
+        for (key, tec) in rhs.record.iter() {
+            if self.record.get(key).is_none() {
+                self.record.insert(*key, *tec);
+            }
+        }
+
+        if let Some(first) = self.record.first_epoch() {
+            self.header.epoch_of_first_map = first;
+        }
+
+        if let Some(last) = self.record.last_epoch() {
+            self.header.epoch_of_last_map = last;
+        }
+
+        self.header.number_of_maps = self.record.epochs_iter().count() as u32;
+
+        for comment in rhs.comments.iter() {
+            if !self.comments.contains(comment) {
+                self.comments.push(comment.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Determines whether this [IONEX] is the result of a previous merge operation.
+    /// That is, the combination of two files merged together.  
+    /// This is determined by the presence of custom yet somewhat standardized [Comments].
+    pub fn is_merged(&self) -> bool {
+        for comment in self.header.comments.iter() {
+            if comment.contains("FILE MERGE") {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Removes duplicated [Comments], preserving the first occurrence of each comment.
+    /// Record [Comments] already described in the [Header] are discarded.
+    /// This is typically useful after several merge operations.
+    pub fn dedup_comments(&mut self) {
+        self.header.comments = self.header.comments.iter().unique().cloned().collect();
+
+        let header_comments = &self.header.comments;
+
+        self.comments = self
+            .comments
+            .iter()
+            .unique()
+            .filter(|comment| !header_comments.contains(comment))
+            .cloned()
+            .collect();
+    }
+
+    /// Extracts the maps within this [start, end] time window (both included) into a new [IONEX],
+    /// with [Header::epoch_of_first_map], [Header::epoch_of_last_map] and [Header::number_of_maps]
+    /// updated accordingly. The grid definition and [Comments] are preserved.
+    /// [Error::OutsideTemporalBoundaries] is returned when no map lies within this window.
+    pub fn extract_time_window(&self, start: Epoch, end: Epoch) -> Result<IONEX, Error> {
+        let epochs = self
+            .epoch_iter()
+            .filter(|epoch| *epoch >= start && *epoch <= end)
+            .collect::<Vec<_>>();
+
+        let (first_epoch, last_epoch) = match (epochs.first(), epochs.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return Err(Error::OutsideTemporalBoundaries),
+        };
+
+        let mut record = Record::default();
+
+        for (k, v) in self.record.iter() {
+            if k.epoch >= start && k.epoch <= end {
+                record.insert(*k, *v);
+            }
+        }
+
+        let mut ionex = self.with_record(record);
+
+        ionex.header.epoch_of_first_map = first_epoch;
+        ionex.header.epoch_of_last_map = last_epoch;
+        ionex.header.number_of_maps = epochs.len() as u32;
+
+        Ok(ionex)
+    }
+
+    /// Splits this [IONEX] into one [IONEX] per UTC calendar day, in chronological order.
+    /// This is typically used to archive multi-day files under standardized names.
+    /// Each [IONEX] has its [Header] (first and last [Epoch], number of maps)
+    /// and [FileAttributes] (year, day of year) updated accordingly.
+    /// The grid definition and [Comments] are preserved.
+    pub fn split_by_utc_day(&self) -> Vec<IONEX> {
+        let utc_day = |epoch: Epoch| {
+            let (year, month, day, _, _, _, _) = epoch.to_gregorian_utc();
+            (year, month, day)
+        };
+
+        let mut ret = Vec::<IONEX>::new();
+
+        for (day, epochs) in self
+            .epoch_iter()
+            .chunk_by(|epoch| utc_day(*epoch))
+            .into_iter()
+        {
+            let epochs = epochs.collect::<Vec<_>>();
+
+            let (first_epoch, last_epoch) = (epochs[0], epochs[epochs.len() - 1]);
+
+            let mut record = Record::default();
+
+            for (k, v) in self.record.iter() {
+                if utc_day(k.epoch) == day {
+                    record.insert(*k, *v);
+                }
+            }
+
+            let mut ionex = self.with_record(record);
+
+            ionex.header.epoch_of_first_map = first_epoch;
+            ionex.header.epoch_of_last_map = last_epoch;
+            ionex.header.number_of_maps = epochs.len() as u32;
+
+            let (year, month, day) = day;
+
+            let elapsed = Epoch::from_gregorian_utc_at_midnight(year, month, day)
+                - Epoch::from_gregorian_utc_at_midnight(year, 1, 1);
+
+            let mut attributes = self.attributes.clone().unwrap_or_else(|| {
+                let mut attributes = FileAttributes::default();
+
+                if self.header.grid.is_worldwide() {
+                    attributes.region = Region::Worldwide;
                 } else {
-                    None
+                    attributes.region = Region::Regional;
                 }
-            },
-            _ => None,
-        };
 
-        let fd = File::open(path)?;
+                attributes
+            });
+
+            attributes.year = year as u32;
+            attributes.doy = elapsed.to_unit(Unit::Day).round() as u32 + 1;
+
+            ionex.attributes = Some(attributes);
 
-        let reader = GzDecoder::new(fd);
-        let mut reader = BufReader::new(reader);
+            ret.push(ionex);
+        }
 
-        let mut ionex = Self::parse(&mut reader)?;
-        ionex.attributes = file_attributes;
+        ret
+    }
 
-        Ok(ionex)
+    /// Shrinks the [Header] grid to the actual extent of the [Record] (for example,
+    /// a worldwide header describing regional data). The grid spacing and direction are preserved,
+    /// and the [FileAttributes] region becomes [Region::Regional] when this is no longer
+    /// a worldwide map. This is the opposite of [Self::pad_to_grid].
+    pub fn shrink_to_data(&mut self) {
+        self.shrink_grid_mut();
+
+        let regional = self.header.grid.is_regional();
+
+        if let Some(attributes) = self.attributes.as_mut().filter(|_| regional) {
+            attributes.region = Region::Regional;
+        }
     }
 
-    /// Dumps and gzip encodes [IONEX] into writable local file,
-    /// using efficient buffered formatting.
-    ///
-    /// This operation is [Self::from_gzip_file] mirror operation.
-    /// ```
-    /// // Read a IONEX and dump it without any modifications
-    /// use ionex::prelude::*;
-    ///
-    /// let ionex = IONEX::from_file("data/IONEX/V1/CKMG0020.22I.gz")
-    ///   .unwrap();
-    ///
-    /// assert!(ionex.to_gzip_file("test.txt.gz").is_ok());
-    /// ```
-    ///
-    /// Other useful methods are:
-    ///   * [Self::generate_standardized_filename]: to generate a standardize file name
-    ///   * [Self::guess_file_attributes] in close relation: helps generate standardized
-    /// filenames for files that did now follow the convention
-    ///   * [gnss_qc_traits::Merge]: to combine two files to one another
-    ///   * [Self::to_regional_roi]: to reduce a larger (possibly [Region::GlobalWorldwide] map)
-    /// to a given ROI
-    ///   * [Self::to_worldwide_map]: to enlarge a possibly [Region::Regional] map to a [Region::GlobalWorldwide] IONEX.
-    #[cfg(feature = "flate2")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
-    pub fn to_gzip_file<P: AsRef<Path>>(&self, path: P) -> Result<(), FormattingError> {
-        let fd = File::create(path)?;
-        let compression = GzCompression::new(5);
-        let mut writer = BufWriter::new(GzEncoder::new(fd, compression));
-        self.format(&mut writer)?;
+    /// Retains only the entries whose coordinates lie within this [Rect]angle
+    /// (decimal degrees, boundaries included), then shrinks the [Header] grid to match,
+    /// see [Self::shrink_to_data]. When no entry lies within this [Rect]angle,
+    /// [Error::OutsideSpatialBoundaries] is returned and self is not modified.
+    pub fn retain_within_rect(&mut self, rect: Rect) -> Result<(), Error> {
+        let (min, max) = (rect.min(), rect.max());
+
+        let within = |k: &Key| {
+            let (lat_ddeg, long_ddeg) = (k.latitude_ddeg(), k.longitude_ddeg());
+            lat_ddeg >= min.y && lat_ddeg <= max.y && long_ddeg >= min.x && long_ddeg <= max.x
+        };
+
+        if !self.record.map.keys().any(within) {
+            return Err(Error::OutsideSpatialBoundaries);
+        }
+
+        self.record.retain(|k, _| within(k));
+        self.shrink_to_data();
         Ok(())
     }
 
-    /// Determines whether this [IONEX] is the result of a previous merge operation.
-    /// That is, the combination of two files merged together.  
-    /// This is determined by the presence of custom yet somewhat standardized [Comments].
-    pub fn is_merged(&self) -> bool {
-        for comment in self.header.comments.iter() {
-            if comment.contains("FILE MERGE") {
-                return true;
+    /// Reduces the [Header] grid to the actual extent of the [Record],
+    /// preserving the grid spacing and direction.
+    pub(crate) fn shrink_grid_mut(&mut self) {
+        let latitudes = self
+            .record
+            .map
+            .keys()
+            .map(|k| k.latitude_ddeg())
+            .minmax_by(|a, b| a.total_cmp(b))
+            .into_option();
+
+        let longitudes = self
+            .record
+            .map
+            .keys()
+            .map(|k| k.longitude_ddeg())
+            .minmax_by(|a, b| a.total_cmp(b))
+            .into_option();
+
+        for (linspace, minmax) in [
+            (&mut self.header.grid.latitude, latitudes),
+            (&mut self.header.grid.longitude, longitudes),
+        ] {
+            if let Some((min, max)) = minmax {
+                // snap to the grid nodes
+                let (min, max) = if linspace.spacing != 0.0 {
+                    let snap = |value: f64| {
+                        linspace.start
+                            + ((value - linspace.start) / linspace.spacing).round()
+                                * linspace.spacing
+                    };
+                    (snap(min), snap(max))
+                } else {
+                    (min, max)
+                };
+
+                if linspace.spacing < 0.0 {
+                    linspace.start = max;
+                    linspace.end = min;
+                } else {
+                    linspace.start = min;
+                    linspace.end = max;
+                }
             }
         }
-        false
     }
 
     /// Returns map borders as a [Rect]angle, with coordinates in decimal degrees.
@@ -655,6 +2071,75 @@ impl IONEX {
         Ok(ionex)
     }
 
+    /// Extracts a coarser sub-grid of this [IONEX], which remains aligned with the original grid
+    /// (every node of the sub-grid is an original node), so both can be overlaid.
+    ///
+    /// ## Input
+    /// - lat_every: keep one latitude every `lat_every` nodes (0 is considered as 1)
+    /// - long_every: keep one longitude every `long_every` nodes (0 is considered as 1)
+    /// - bounds: optional bounding [Rect] (in decimal degrees): only the nodes
+    /// within it are kept, the first node of each axis (in the grid direction)
+    /// being the first node within the bounds.
+    ///
+    /// The [Header] grid extents and spacing are updated accordingly.
+    /// If no node lies within the bounds, the returned [IONEX] is empty,
+    /// with an unmodified [Header].
+    pub fn subgrid(&self, lat_every: usize, long_every: usize, bounds: Option<Rect>) -> IONEX {
+        let select = |linspace: &Linspace, every: usize, (min, max): (f64, f64)| {
+            let every = every.max(1);
+
+            let nodes = linspace
+                .iter()
+                .filter(|node| (min..=max).contains(node))
+                .step_by(every)
+                .collect::<Vec<_>>();
+
+            Some(Linspace {
+                start: *nodes.first()?,
+                end: *nodes.last()?,
+                spacing: linspace.spacing * every as f64,
+            })
+        };
+
+        let (latitude_bounds, longitude_bounds) = match bounds {
+            Some(bounds) => (
+                (bounds.min().y, bounds.max().y),
+                (bounds.min().x, bounds.max().x),
+            ),
+            None => (
+                (f64::NEG_INFINITY, f64::INFINITY),
+                (f64::NEG_INFINITY, f64::INFINITY),
+            ),
+        };
+
+        let mut s = self.with_record(Record::default());
+
+        let (latitude, longitude) = match (
+            select(&self.header.grid.latitude, lat_every, latitude_bounds),
+            select(&self.header.grid.longitude, long_every, longitude_bounds),
+        ) {
+            (Some(latitude), Some(longitude)) => (latitude, longitude),
+            _ => return s,
+        };
+
+        for (key, tec) in self.record.iter() {
+            if is_linspace_node(&latitude, key.latitude_ddeg())
+                && is_linspace_node(&longitude, key.longitude_ddeg())
+            {
+                s.record.insert(*key, *tec);
+            }
+        }
+
+        s.header.grid.latitude = latitude;
+        s.header.grid.longitude = longitude;
+
+        if bounds.is_some() {
+            s.attributes = Some(self.attributes.clone().unwrap_or_default().regionalized());
+        }
+
+        s
+    }
+
     // /// Modify the grid dimensions by a positive, possibly fractional number,
     // /// and interpolates the TEC values.
     // ///
@@ -723,6 +2208,11 @@ impl IONEX {
     // ///    - < 1.0: downscaling
     // ///    - 0.0: invalid
     // pub fn spatial_stretching_mut(&mut self, axis: Axis, factor: f64) -> Result<(), Error> {
+    //     // unitary stretching is an exact no-op
+    //     if (factor - 1.0).abs() < f64::EPSILON {
+    //         return Ok(());
+    //     }
+    //
     //     if factor > 2.0 {
     //         // the maximal factor supported per iter is 2
     //         // let mut fact = factor.max(2.0);
@@ -797,6 +2287,21 @@ impl IONEX {
         Box::new(self.record.map.keys().map(|k| k.epoch).unique().sorted())
     }
 
+    /// Returns an iterator over each map, in chronological order, as an owned [Record]
+    /// that only contains this [Epoch]. Each [Record] is gathered with a range query,
+    /// which is well suited to map-reduce processing.
+    pub fn epoch_records(&self) -> Box<dyn Iterator<Item = (Epoch, Record)> + '_> {
+        Box::new(self.epoch_iter().map(|epoch| {
+            let map = self
+                .record
+                .synchronous_iter_ref(epoch)
+                .map(|(k, v)| (*k, *v))
+                .collect();
+
+            (epoch, Record { map })
+        }))
+    }
+
     // TODO
     // /// Modify the grid spacing (quantization) while preserving the dimensions,
     // /// and interpolates the TEC values.
@@ -860,25 +2365,136 @@ impl IONEX {
     ///    - >1.0: upscaling case. For example, 2.0 means x2 sample rate increase (+100%).
     ///    - and 1.5 means +50% sample rate increase.
     ///    - <1.0: downscaling case. For example, 0.5 means /2 sample rate decrease (-50%).
+    ///
+    /// The new temporal axis starts at [Header::epoch_of_first_map] and never goes past
+    /// [Header::epoch_of_last_map]. Maps that already exist on the new axis are preserved,
+    /// maps in between are linearly interpolated from the two surrounding maps
+    /// (nodes missing in either map are missing as well), other maps are dropped.
+    /// [Header::number_of_maps] and [Header::epoch_of_last_map] are updated accordingly.
     pub fn temporal_stretching_mut(&mut self, factor: f64) -> Result<(), Error> {
-        if !factor.is_normal() {
+        if !factor.is_normal() || factor < 0.0 {
             return Err(Error::InvalidStretchFactor);
         }
 
-        let new_dt = self.header.sampling_period * factor;
+        // unitary stretching is an exact no-op
+        if (factor - 1.0).abs() < f64::EPSILON {
+            return Ok(());
+        }
+
+        let new_dt = self.header.sampling_period / factor;
 
-        if factor > 1.0 {
-            // temporal upscaling
-        } else {
-            // temporal decimation
+        if new_dt <= Duration::ZERO {
+            return Err(Error::InvalidStretchFactor);
+        }
+
+        let epochs = self.epoch_iter().collect::<Vec<_>>();
+
+        let mut record = Record::default();
+        let (mut num_maps, mut last_map) = (0, self.header.epoch_of_first_map);
+
+        let mut t = self.header.epoch_of_first_map;
+
+        while t <= self.header.epoch_of_last_map {
+            match epochs.binary_search(&t) {
+                Ok(_) => {
+                    // sampled map: preserved
+                    for (k, v) in self.record.synchronous_iter(t) {
+                        record.insert(k, v);
+                    }
+                },
+                Err(index) if index > 0 && index < epochs.len() => {
+                    // in between two maps: interpolated
+                    let (t0, t1) = (epochs[index - 1], epochs[index]);
+                    let weight = (t - t0).to_seconds() / (t1 - t0).to_seconds();
+
+                    for (k, tec0) in self.record.synchronous_iter(t0) {
+                        let tec1 = match self.record.get(&Key {
+                            epoch: t1,
+                            coordinates: k.coordinates,
+                        }) {
+                            Some(tec1) => *tec1,
+                            None => continue,
+                        };
+
+                        record.insert(
+                            Key {
+                                epoch: t,
+                                coordinates: k.coordinates,
+                            },
+                            TEC::interpolated([(tec0, 1.0 - weight), (tec1, weight)]),
+                        );
+                    }
+                },
+                Err(_) => {
+                    // not covered by the record
+                },
+            }
+
+            num_maps += 1;
+            last_map = t;
+            t += new_dt;
         }
 
+        self.record = record;
+
         // update header
         self.header.sampling_period = new_dt;
+        self.header.number_of_maps = num_maps;
+        self.header.epoch_of_last_map = last_map;
 
         Ok(())
     }
 
+    /// Decimates this mutable [IONEX], keeping only every `modulo`-th map
+    /// (by chronological index, starting with the first map).
+    /// [Header::number_of_maps], [Header::epoch_of_last_map] and [Header::sampling_period]
+    /// are updated accordingly. A modulo lower than 2 has no effect.
+    pub fn decimate_by_epoch(&mut self, modulo: usize) {
+        if modulo < 2 {
+            return;
+        }
+
+        let epochs = self.epoch_iter().step_by(modulo).collect::<Vec<_>>();
+        let sampling_period = self.header.sampling_period * modulo as f64;
+
+        self.retain_epochs_mut(epochs, sampling_period);
+    }
+
+    /// Decimates this mutable [IONEX], keeping only the maps aligned to this coarser
+    /// sampling period, starting from [Header::epoch_of_first_map].
+    /// [Header::number_of_maps], [Header::epoch_of_last_map] and [Header::sampling_period]
+    /// are updated accordingly. A period that is not coarser than [Header::sampling_period]
+    /// has no effect.
+    pub fn decimate_by_interval(&mut self, dt: Duration) {
+        if dt <= self.header.sampling_period {
+            return;
+        }
+
+        let t0 = self.header.epoch_of_first_map;
+        let period_nanos = dt.total_nanoseconds();
+
+        let epochs = self
+            .epoch_iter()
+            .filter(|t| (*t - t0).total_nanoseconds() % period_nanos == 0)
+            .collect::<Vec<_>>();
+
+        self.retain_epochs_mut(epochs, dt);
+    }
+
+    /// Only retains the maps at these [Epoch]s (in chronological order),
+    /// and updates the [Header] to this new sampling period.
+    fn retain_epochs_mut(&mut self, epochs: Vec<Epoch>, sampling_period: Duration) {
+        self.record
+            .retain(|k, _| epochs.binary_search(&k.epoch).is_ok());
+
+        self.header.number_of_maps = epochs.len() as u32;
+        self.header.sampling_period = sampling_period;
+
+        if let Some(last) = epochs.last() {
+            self.header.epoch_of_last_map = *last;
+        }
+    }
+
     // /// Stretchs this mutable [IONEX] both in temporal and spatial dimensions,
     // /// modifying both dimensions.
     // /// When factor > 1.0 this is an upscaling operation, when factor < 1.0,
@@ -911,6 +2527,165 @@ impl IONEX {
     //     Ok(s)
     // }
 
+    /// Removes the trailing maps that have no value at all. Some generators over-count
+    /// the maps by one, emitting a final all-missing map. Maps are considered empty when
+    /// they have no finite value: absent, explicit missing (see [TEC::missing]) or NaN values
+    /// only, so such trailing maps are removed from the [Record]. The [Header] epoch of last map
+    /// and number of maps are adjusted, so they match the last map that has values.
+    /// This is conservative: empty maps within the time frame are preserved,
+    /// and nothing is done if the whole [IONEX] is empty.
+    pub fn strip_trailing_empty(&mut self) {
+        let last_epoch = match self
+            .record
+            .iter()
+            .filter(|(_, tec)| tec.tecu().is_finite())
+            .map(|(k, _)| k.epoch)
+            .max()
+        {
+            Some(last_epoch) => last_epoch,
+            None => return,
+        };
+
+        self.record.map.retain(|k, _| k.epoch <= last_epoch);
+
+        if self.header.epoch_of_last_map <= last_epoch {
+            return;
+        }
+
+        if self.header.sampling_period > Duration::ZERO {
+            let num_empty = ((self.header.epoch_of_last_map - last_epoch).to_seconds()
+                / self.header.sampling_period.to_seconds())
+            .round() as u32;
+
+            self.header.number_of_maps = self.header.number_of_maps.saturating_sub(num_empty);
+        }
+
+        self.header.epoch_of_last_map = last_epoch;
+    }
+
+    /// Returns pairs of consecutive [Epoch]s whose spacing exceeds the sampling period
+    /// (with a 1 second tolerance). Each pair bounds a gap, that is one or more missing maps,
+    /// which typically flags an incomplete download.
+    pub fn temporal_gaps(&self) -> Vec<(Epoch, Epoch)> {
+        let tolerance = Duration::from_seconds(1.0);
+
+        self.epoch_iter()
+            .tuple_windows()
+            .filter(|(t0, t1)| *t1 - *t0 > self.header.sampling_period + tolerance)
+            .collect()
+    }
+
+    /// Returns the temporal variance of the TEC (in TECu²) at each grid node, across all maps.
+    /// The returned [Record] describes a single map, at [Header::epoch_of_first_map].
+    /// Missing (non finite) samples are skipped, nodes with less than two samples are not described.
+    /// This is the sample variance (Bessel correction, divided by N-1), because the maps
+    /// are samples of a continuous process rather than the entire population.
+    pub fn temporal_variance(&self) -> Record {
+        let mut samples = BTreeMap::<QuantizedCoordinates, Vec<&TEC>>::new();
+
+        for (key, tec) in self.record.iter() {
+            samples.entry(key.coordinates).or_default().push(tec);
+        }
+
+        let mut record = Record::default();
+
+        for (coordinates, values) in samples {
+            let variance = match variance(finite_tecu(values.into_iter())) {
+                Some(variance) => variance,
+                None => continue,
+            };
+
+            record.insert(
+                Key {
+                    epoch: self.header.epoch_of_first_map,
+                    coordinates,
+                },
+                TEC::from_tecu(variance),
+            );
+        }
+
+        record
+    }
+
+    /// Returns the magnitude of the horizontal TEC gradient (in TECu/°) at each node
+    /// of the map at this [Epoch]: `sqrt((dTEC/dlat)² + (dTEC/dlong)²)`, which highlights
+    /// sharp features like storm fronts. Partial derivatives use central differences
+    /// with the neighboring nodes, and one-sided differences at the edges of the grid
+    /// (or next to a missing node). Nodes that have no neighbor along one axis are not described.
+    /// The returned [Record] describes a single map, at this [Epoch].
+    pub fn gradient_magnitude_map(&self, epoch: Epoch) -> Record {
+        let grid = self.header.grid;
+
+        let latitudes = grid.latitude.iter().collect::<Vec<_>>();
+        let longitudes = grid.longitude.iter().collect::<Vec<_>>();
+
+        // partial derivative from the (coordinate, TECu) samples surrounding the center
+        let derivative =
+            |prev: Option<(f64, f64)>, center: (f64, f64), next: Option<(f64, f64)>| {
+                let ((x0, y0), (x1, y1)) = match (prev, next) {
+                    (Some(prev), Some(next)) => (prev, next),
+                    (Some(prev), None) => (prev, center),
+                    (None, Some(next)) => (center, next),
+                    (None, None) => return None,
+                };
+
+                Some((y1 - y0) / (x1 - x0))
+            };
+
+        let mut record = Record::default();
+
+        for altitude_km in grid.altitude.iter() {
+            let tecu = |i: usize, j: usize| {
+                let key =
+                    Key::from_decimal_degrees_km(epoch, latitudes[i], longitudes[j], altitude_km);
+
+                self.record.get(&key).map(|tec| tec.tecu())
+            };
+
+            for (i, lat_ddeg) in latitudes.iter().enumerate() {
+                for (j, long_ddeg) in longitudes.iter().enumerate() {
+                    let center = match tecu(i, j) {
+                        Some(center) => center,
+                        None => continue,
+                    };
+
+                    let north_south = derivative(
+                        i.checked_sub(1)
+                            .and_then(|i| tecu(i, j).map(|tecu| (latitudes[i], tecu))),
+                        (*lat_ddeg, center),
+                        (i + 1 < latitudes.len())
+                            .then(|| tecu(i + 1, j).map(|tecu| (latitudes[i + 1], tecu)))
+                            .flatten(),
+                    );
+
+                    let east_west = derivative(
+                        j.checked_sub(1)
+                            .and_then(|j| tecu(i, j).map(|tecu| (longitudes[j], tecu))),
+                        (*long_ddeg, center),
+                        (j + 1 < longitudes.len())
+                            .then(|| tecu(i, j + 1).map(|tecu| (longitudes[j + 1], tecu)))
+                            .flatten(),
+                    );
+
+                    if let (Some(dlat), Some(dlong)) = (north_south, east_west) {
+                        record.insert(
+                            Key::from_decimal_degrees_km(epoch, *lat_ddeg, *long_ddeg, altitude_km),
+                            TEC::from_tecu((dlat.powi(2) + dlong.powi(2)).sqrt()),
+                        );
+                    }
+                }
+            }
+        }
+
+        record
+    }
+
+    /// Returns the total [Duration] covered by this [IONEX],
+    /// from the first to the last map described in [Header].
+    pub fn duration(&self) -> Duration {
+        self.header.duration()
+    }
+
     /// Produces a [TimeSeries] from this [IONEX], describing the temporal axis.
     pub fn timeseries(&self) -> TimeSeries {
         self.header.timeseries()
@@ -918,16 +2693,109 @@ impl IONEX {
 
     /// Designs a [MapCell] iterator (micro ROI following the grid quantization)
     /// that allows micro interpolation.
+    /// Incomplete cells (at least one corner is missing) are dropped by this iterator,
+    /// which is common at the edges of sparse regional maps.
+    /// Use [Self::map_cell_iter_lossy] if you need to be aware of them.
+    /// This assumes a 2D map, see [Self::fixed_altitude_km].
     pub fn map_cell_iter(&self) -> Box<dyn Iterator<Item = MapCell> + '_> {
         let lat_pairs = self.header.grid.latitude.quantize().tuple_windows();
         let long_pairs = self.header.grid.longitude.quantize().tuple_windows();
 
+        self.windowed_map_cell_iter(lat_pairs.collect(), long_pairs.collect())
+    }
+
+    /// Designs a [MapCell] iterator like [Self::map_cell_iter] that, when the grid longitudes
+    /// span the whole globe once the wraparound step is accounted for (worldwide maps),
+    /// also returns the wraparound cells bridging the easternmost longitude and the
+    /// westernmost longitude, when the grid does not already close the globe.
+    /// The westernmost longitude is then expressed as easternmost longitude + spacing
+    /// (for example, -180° becomes 180°),
+    /// so the returned cells are continuous. Wraparound cells are returned
+    /// after all the regular cells.
+    pub fn map_cell_iter_wrapped(&self) -> Box<dyn Iterator<Item = MapCell> + '_> {
+        // tolerates the rounding errors of the grid nodes
+        const TOLERANCE: f64 = 1.0E-6;
+
+        let longitude = &self.header.grid.longitude;
+        let (min, max) = longitude.minmax();
+
+        if !wraps_around_globe(longitude) || max >= min + 360.0 - TOLERANCE {
+            return self.map_cell_iter();
+        }
+
+        let fixed_altitude_km = self.header.grid.altitude.start;
+        let (lat_windows, _) = self.ascending_cell_windows();
+
+        let wraparound = self.timeseries().cartesian_product(lat_windows).filter_map(
+            move |(epoch, (south, north))| {
+                let tec_point = |lat_ddeg: f64, long_ddeg: f64, wrapped_ddeg: f64| {
+                    let key =
+                        Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, fixed_altitude_km);
+
+                    self.record.get(&key).map(|tec| TecPoint {
+                        tec: *tec,
+                        point: Point::new(wrapped_ddeg, lat_ddeg),
+                    })
+                };
+
+                Some(MapCell::from_cardinal_points(
+                    epoch,
+                    tec_point(north, min, min + 360.0)?,
+                    tec_point(north, max, max)?,
+                    tec_point(south, min, min + 360.0)?,
+                    tec_point(south, max, max)?,
+                ))
+            },
+        );
+
+        Box::new(self.map_cell_iter().chain(wraparound))
+    }
+
+    /// Designs a [MapCell] iterator like [Self::map_cell_iter], but restricted to the
+    /// cells that intersect these bounds (in decimal degrees). The grid windows are
+    /// selected before browsing the maps, which is much faster than filtering
+    /// [Self::map_cell_iter] for regional queries on worldwide maps.
+    pub fn map_cell_iter_in(&self, bounds: Rect) -> Box<dyn Iterator<Item = MapCell> + '_> {
+        let (min, max) = (bounds.min(), bounds.max());
+
+        let intersects = |(a, b): &(Quantized, Quantized), min: f64, max: f64| {
+            let (a, b) = (a.real_value(), b.real_value());
+            a.min(b) <= max && a.max(b) >= min
+        };
+
+        let lat_pairs = self
+            .header
+            .grid
+            .latitude
+            .quantize()
+            .tuple_windows()
+            .filter(|pair| intersects(pair, min.y, max.y))
+            .collect();
+
+        let long_pairs = self
+            .header
+            .grid
+            .longitude
+            .quantize()
+            .tuple_windows()
+            .filter(|pair| intersects(pair, min.x, max.x))
+            .collect();
+
+        self.windowed_map_cell_iter(lat_pairs, long_pairs)
+    }
+
+    /// [MapCell] iterator over these latitude and longitude windows.
+    fn windowed_map_cell_iter(
+        &self,
+        lat_pairs: Vec<(Quantized, Quantized)>,
+        long_pairs: Vec<(Quantized, Quantized)>,
+    ) -> Box<dyn Iterator<Item = MapCell> + '_> {
         let fixed_altitude_km = self.header.grid.altitude.start;
         let fixed_altitude_q = Quantized::auto_scaled(fixed_altitude_km);
 
         Box::new(
             self.timeseries()
-                .cartesian_product(lat_pairs.cartesian_product(long_pairs))
+                .cartesian_product(lat_pairs.into_iter().cartesian_product(long_pairs))
                 .filter_map(move |(epoch, ((lat1, lat2), (long1, long2)))| {
                     let northeast = Key {
                         epoch,
@@ -955,7 +2823,7 @@ impl IONEX {
                         epoch,
                         coordinates: QuantizedCoordinates::from_quantized(
                             lat2,
-                            long2,
+                            long1,
                             fixed_altitude_q,
                         ),
                     };
@@ -996,6 +2864,206 @@ impl IONEX {
         )
     }
 
+    /// Designs a [MapCell] iterator (micro ROI following the grid quantization) that,
+    /// unlike [Self::map_cell_iter], also returns incomplete cells.
+    /// Missing corners are filled with the mean TEC value of the corners that are present.
+    /// Each [MapCell] is returned with a flag, set to true when the cell is complete
+    /// (no corner was filled). Cells with no corner at all are never returned.
+    pub fn map_cell_iter_lossy(&self) -> Box<dyn Iterator<Item = (MapCell, bool)> + '_> {
+        let fixed_altitude_km = self.header.grid.altitude.start;
+
+        let lat_pairs = self
+            .header
+            .grid
+            .latitude
+            .iter()
+            .sorted_by(|a, b| a.total_cmp(b))
+            .tuple_windows::<(f64, f64)>()
+            .collect::<Vec<_>>();
+
+        let long_pairs = self
+            .header
+            .grid
+            .longitude
+            .iter()
+            .sorted_by(|a, b| a.total_cmp(b))
+            .tuple_windows::<(f64, f64)>()
+            .collect::<Vec<_>>();
+
+        Box::new(
+            self.epoch_iter()
+                .cartesian_product(lat_pairs.into_iter().cartesian_product(long_pairs))
+                .filter_map(move |(epoch, ((south, north), (west, east)))| {
+                    let corners = [(north, east), (north, west), (south, east), (south, west)].map(
+                        |(lat_ddeg, long_ddeg)| {
+                            let key = Key::from_decimal_degrees_km(
+                                epoch,
+                                lat_ddeg,
+                                long_ddeg,
+                                fixed_altitude_km,
+                            );
+
+                            self.record.get(&key).copied()
+                        },
+                    );
+
+                    let present = corners.iter().flatten().collect::<Vec<_>>();
+
+                    if present.is_empty() {
+                        return None;
+                    }
+
+                    let complete = present.len() == corners.len();
+
+                    let mean_tecu =
+                        present.iter().map(|tec| tec.tecu()).sum::<f64>() / present.len() as f64;
+
+                    let [north_east, north_west, south_east, south_west] =
+                        corners.map(|tec| tec.unwrap_or(TEC::from_tecu(mean_tecu)));
+
+                    let cell = MapCell::from_cardinal_points(
+                        epoch,
+                        TecPoint {
+                            tec: north_east,
+                            point: Point::new(east, north),
+                        },
+                        TecPoint {
+                            tec: north_west,
+                            point: Point::new(west, north),
+                        },
+                        TecPoint {
+                            tec: south_east,
+                            point: Point::new(east, south),
+                        },
+                        TecPoint {
+                            tec: south_west,
+                            point: Point::new(west, south),
+                        },
+                    );
+
+                    Some((cell, complete))
+                }),
+        )
+    }
+
+    /// Returns all the [MapCell]s of the map at this [Epoch], addressed as `[row][column]`:
+    /// rows go from south to north, and columns from west to east.
+    /// Incomplete cells (at least one corner is missing) are set to None.
+    /// This addressing is shared by [Self::cell3x3_iter] and [Self::cell_at],
+    /// which helps finding the neighbors of a cell.
+    pub fn cell_grid(&self, epoch: Epoch) -> Vec<Vec<Option<MapCell>>> {
+        let (lat_pairs, long_pairs) = self.ascending_cell_windows();
+
+        lat_pairs
+            .iter()
+            .map(|(south, north)| {
+                long_pairs
+                    .iter()
+                    .map(|(west, east)| self.grid_cell(epoch, *south, *north, *west, *east))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the [MapCell] that contains this point (in decimal degrees) at this [Epoch],
+    /// with its (row, column) position in [Self::cell_grid]. A point lying on the boundary
+    /// of two cells is attributed to the southern (or western) one.
+    /// Returns None if this point is outside the grid, or if the cell is incomplete.
+    pub fn cell_at(&self, epoch: Epoch, point: Point<f64>) -> Option<(MapCell, usize, usize)> {
+        let (lat_pairs, long_pairs) = self.ascending_cell_windows();
+
+        let row = lat_pairs
+            .iter()
+            .position(|(south, north)| (*south..=*north).contains(&point.y()))?;
+
+        let column = long_pairs
+            .iter()
+            .position(|(west, east)| (*west..=*east).contains(&point.x()))?;
+
+        let ((south, north), (west, east)) = (lat_pairs[row], long_pairs[column]);
+
+        let cell = self.grid_cell(epoch, south, north, west, east)?;
+
+        Some((cell, row, column))
+    }
+
+    /// Returns the (south, north) latitude and (west, east) longitude windows
+    /// of the grid cells, in ascending order.
+    fn ascending_cell_windows(&self) -> (Vec<(f64, f64)>, Vec<(f64, f64)>) {
+        let windows = |linspace: &Linspace| {
+            linspace
+                .iter()
+                .sorted_by(|a, b| a.total_cmp(b))
+                .tuple_windows::<(f64, f64)>()
+                .collect::<Vec<_>>()
+        };
+
+        (
+            windows(&self.header.grid.latitude),
+            windows(&self.header.grid.longitude),
+        )
+    }
+
+    /// Returns the [MapCell] delimited by these coordinates (in decimal degrees),
+    /// at this [Epoch], if all four corners exist.
+    fn grid_cell(
+        &self,
+        epoch: Epoch,
+        south: f64,
+        north: f64,
+        west: f64,
+        east: f64,
+    ) -> Option<MapCell> {
+        let fixed_altitude_km = self.header.grid.altitude.start;
+
+        let tec_point = |lat_ddeg: f64, long_ddeg: f64| {
+            let key = Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, fixed_altitude_km);
+
+            self.record.get(&key).map(|tec| TecPoint {
+                tec: *tec,
+                point: Point::new(long_ddeg, lat_ddeg),
+            })
+        };
+
+        Some(MapCell::from_cardinal_points(
+            epoch,
+            tec_point(north, east)?,
+            tec_point(north, west)?,
+            tec_point(south, east)?,
+            tec_point(south, west)?,
+        ))
+    }
+
+    /// Returns a [Cell3x3] iterator over the map at this [Epoch].
+    /// Each [Cell3x3] is centered on one grid cell and is only returned when this cell
+    /// and its 8 neighbors are complete (all corners present). That means cells at the
+    /// map borders, or next to missing values, are never returned as central element.
+    pub fn cell3x3_iter(&self, epoch: Epoch) -> Box<dyn Iterator<Item = Cell3x3> + '_> {
+        let cells = self.cell_grid(epoch);
+
+        let (num_lat, num_long) = (cells.len(), cells.first().map(Vec::len).unwrap_or(0));
+
+        Box::new(
+            (1..num_lat.saturating_sub(1))
+                .cartesian_product(1..num_long.saturating_sub(1))
+                .filter_map(move |(i, j)| {
+                    let cell = |di: usize, dj: usize| cells[i + di - 1][j + dj - 1];
+
+                    Some(Cell3x3 {
+                        center: cell(1, 1)?,
+                        north: cell(2, 1)?,
+                        northeast: cell(2, 2)?,
+                        east: cell(1, 2)?,
+                        southeast: cell(0, 2)?,
+                        south: cell(0, 1)?,
+                        southwest: cell(0, 0)?,
+                        west: cell(1, 0)?,
+                        northwest: cell(2, 0)?,
+                    })
+                }),
+        )
+    }
+
     // /// Returns a [Cell3x3] iterator for each 3x3 region at a specific point in time
     // pub fn synchronous_map_cell3x3_iter(&self, epoch: Epoch) -> Box<dyn Iterator<Item = Cell3x3> + '_> {
     //     Box::new(
@@ -1017,6 +3085,11 @@ impl IONEX {
     /// and before the last [Epoch] described in [Header].
     ///
     /// - roi: [Geometry] defining the local region we want to wrap (fully contained by returned cell).
+    ///
+    /// The returned [MapCell] is the smallest grid aligned rectangle wrapping the ROI,
+    /// its corners are grid nodes, so no spatial interpolation is involved.
+    /// [Error::IncompleteCell] is returned when one of its corners is missing.
+    /// This assumes a 2D map, see [Self::fixed_altitude_km].
     pub fn roi_at(&self, epoch: Epoch, roi: Geometry) -> Result<MapCell, Error> {
         // determine whether this is within the temporal axis
         if epoch < self.header.epoch_of_first_map || epoch > self.header.epoch_of_last_map {
@@ -1039,58 +3112,47 @@ impl IONEX {
             return Err(Error::OutsideSpatialBoundaries);
         }
 
-        // determine whether this lies within a single element or not
-        let mut unitary_element = true;
+        // smallest grid aligned rectangle wrapping the ROI:
+        // a single map cell, or the combination of several.
+        let (south, north) = wrapping_nodes(&self.header.grid.latitude, roi.min().y, roi.max().y)
+            .ok_or(Error::OutsideSpatialBoundaries)?;
 
-        if roi.width() > self.header.grid.longitude.spacing {
-            unitary_element = false;
-        }
+        let (west, east) = wrapping_nodes(&self.header.grid.longitude, roi.min().x, roi.max().x)
+            .ok_or(Error::OutsideSpatialBoundaries)?;
 
-        if roi.height() > self.header.grid.latitude.spacing {
-            unitary_element = false;
-        }
+        // surrounding maps
+        let (mut t0, mut t1) = (None, None);
 
-        // determine whether we need temporal interpolation or not
-        let mut needs_temporal_interp = true;
-        let mut t = self.header.epoch_of_first_map;
+        for t in self.epoch_iter() {
+            if t <= epoch {
+                t0 = Some(t);
+            }
 
-        while t < self.header.epoch_of_last_map {
-            if t == epoch {
-                needs_temporal_interp = false;
+            if t >= epoch {
+                t1 = Some(t);
                 break;
             }
-
-            t += self.header.sampling_period;
         }
 
-        // determine whether we need spatial interpolation or not.
-        // we don't need spatial interpolation when the ROI height and width
-        // perfectly line up with the IONEX grid.
-        let (width, height) = (roi.width(), roi.height());
+        let (t0, t1) = match (t0, t1) {
+            (Some(t0), Some(t1)) => (t0, t1),
+            _ => return Err(Error::OutsideTemporalBoundaries),
+        };
 
-        let (needs_lat_interp, needs_long_interp) = (
-            height.rem_euclid(self.header.grid.latitude.spacing) == 0.0,
-            width.rem_euclid(self.header.grid.longitude.spacing) == 0.0,
-        );
+        let cell0 = self
+            .grid_cell(t0, south, north, west, east)
+            .ok_or(Error::IncompleteCell)?;
 
-        if unitary_element {
-            // this can be reduced to a unitary MapCell
-        } else {
-            if needs_temporal_interp {
-                if needs_lat_interp || needs_long_interp {
-                    panic!("not supported yet");
-                } else {
-                }
-            } else {
-                if needs_lat_interp || needs_long_interp {
-                    panic!("not supported yet");
-                } else {
-                }
-            }
+        if t0 == t1 {
+            // lines up with the temporal axis
+            return Ok(cell0);
         }
 
-        // TODO
-        Err(Error::TemporalMismatch)
+        let cell1 = self
+            .grid_cell(t1, south, north, west, east)
+            .ok_or(Error::IncompleteCell)?;
+
+        cell0.temporally_interpolated(epoch, &cell1)
     }
 
     /// Obtain the [MapCell] (smallest map ROI) at provided point in time and containing provided coordinates.
@@ -1108,7 +3170,8 @@ impl IONEX {
         let mut needs_temporal_interp = true;
         let mut t = self.header.epoch_of_first_map;
 
-        while t < self.header.epoch_of_last_map {
+        // a non positive sampling period would never complete
+        while t < self.header.epoch_of_last_map && self.header.sampling_period > Duration::ZERO {
             if t == epoch {
                 needs_temporal_interp = false;
                 break;
@@ -1127,10 +3190,7 @@ impl IONEX {
                         .zip(self.synchronous_map_cell_iter(t1))
                     {
                         if cell0.contains(&coordinates) && cell1.contains(&coordinates) {
-                            // TODO
-                            // if let Ok(interpolated) = cell0.temporally_interpolated(epoch, &cell1)? {
-                            //     return Some(interpolated);
-                            // }
+                            return cell0.temporally_interpolated(epoch, &cell1).ok();
                         }
                     }
                 }
@@ -1282,6 +3342,7 @@ impl IONEX {
 /// - reference systems must match
 /// - maps dimension must match
 /// - both must use the same mapping function
+/// - both grids must have the same latitude and longitude spacing
 ///
 /// Different sampling rate are supported, because the IONEX
 /// description allows to describe that, but you will windup with
@@ -1295,8 +3356,32 @@ impl gnss_qc_traits::Merge for IONEX {
     }
 
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), gnss_qc_traits::MergeError> {
+        self.merge_with_strategy_mut(rhs, MergeStrategy::KeepLeft)
+    }
+}
+
+#[cfg(feature = "qc")]
+impl IONEX {
+    /// Merges two [IONEX] into one, like [gnss_qc_traits::Merge::merge], but coincident
+    /// TEC values (for example, overlapping solutions from different analysis centers)
+    /// are averaged and their RMS combined in quadrature, see [MergeStrategy::Average].
+    pub fn merge_averaged(&self, rhs: &Self) -> Result<Self, gnss_qc_traits::MergeError> {
+        let mut s = self.clone();
+        s.merge_with_strategy_mut(rhs, MergeStrategy::Average)?;
+        Ok(s)
+    }
+
+    /// Merges the right-hand side [IONEX] into ourselves,
+    /// combining coincident TEC values following this [MergeStrategy].
+    fn merge_with_strategy_mut(
+        &mut self,
+        rhs: &Self,
+        strategy: MergeStrategy,
+    ) -> Result<(), gnss_qc_traits::MergeError> {
+        use gnss_qc_traits::Merge;
+
         self.header.merge_mut(&rhs.header)?;
-        self.record.merge_mut(&rhs.record)?;
+        self.record.merge_with_strategy(&rhs.record, strategy)?;
 
         match self.attributes {
             Some(ref mut prods) => {
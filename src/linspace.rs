@@ -149,9 +149,30 @@ impl Linspace {
         self.end - self.start
     }
 
-    /// Returns grid length, in terms of data points
+    /// Returns grid length, in terms of data points (both ends included).
+    /// A single point [Linspace] has a length of 1.
     pub fn length(&self) -> usize {
-        (self.end / self.spacing).floor() as usize
+        if self.spacing == 0.0 {
+            1
+        } else {
+            ((self.end - self.start) / self.spacing).abs().round() as usize + 1
+        }
+    }
+
+    /// Iterates over the exact grid nodes, from [Self::start] to [Self::end] (included),
+    /// whatever the sign of [Self::spacing]. For example, the worldwide latitude grid
+    /// goes from 87.5 to -87.5 by -2.5. A single point [Linspace] yields a single value.
+    pub fn iter(&self) -> impl Iterator<Item = f64> {
+        let start = self.start;
+
+        // walk towards the end, even if the spacing sign does not
+        let spacing = if (self.end - self.start) * self.spacing < 0.0 {
+            -self.spacing
+        } else {
+            self.spacing
+        };
+
+        (0..self.length()).map(move |i| start + i as f64 * spacing)
     }
 
     /// Returns true if self is a single point space
@@ -159,23 +180,35 @@ impl Linspace {
         (self.end == self.start) && self.spacing == 0.0
     }
 
-    /// Returns nearest lower bound from point p in the [Linspace]
+    /// Returns nearest lower bound from point p in the [Linspace]:
+    /// the largest grid node that is lower than or equal to p,
+    /// whatever the direction of the [Linspace].
+    /// Returns None if p lies outside of the [Linspace].
     pub fn nearest_lower(&self, p: f64) -> Option<f64> {
-        let mut start = self.start;
-        while start < self.end {
-            if start > p {
-                return Some(start - self.spacing);
-            }
-            start += self.spacing;
+        // tolerates the rounding errors of grid aligned points
+        const TOLERANCE: f64 = 1.0E-9;
+
+        let (min, max) = self.minmax();
+
+        if p < min || p > max {
+            return None;
+        }
+
+        let spacing = self.spacing.abs();
+
+        if spacing == 0.0 {
+            return Some(min);
         }
 
-        None
+        let index = ((p - min) / spacing + TOLERANCE).floor();
+        Some(min + index * spacing)
     }
 
-    /// Returns nearest lower bound from point p in the [Linspace]
+    /// Returns nearest upper bound from point p in the [Linspace],
+    /// which is the grid node following [Self::nearest_lower].
     pub fn nearest_above(&self, p: f64) -> Option<f64> {
         let lower = self.nearest_lower(p)?;
-        Some(lower + self.spacing)
+        Some(lower + self.spacing.abs())
     }
 }
 
@@ -200,11 +233,27 @@ mod test {
         assert!(!linspace.is_single_point());
 
         let linspace = Linspace::new(1.0, 180.0, 0.5).unwrap();
-        assert_eq!(linspace.length(), 180 * 2);
+        assert_eq!(linspace.length(), 179 * 2 + 1);
         assert!(!linspace.is_single_point());
 
         let linspace = Linspace::new(350.0, 350.0, 0.0).unwrap();
         assert!(linspace.is_single_point());
+        assert_eq!(linspace.length(), 1);
+    }
+
+    #[test]
+    fn linspace_length() {
+        let latitude = Linspace::from((87.5, -87.5, -2.5));
+        assert_eq!(latitude.length(), 71);
+
+        let latitude = Linspace::new(-87.5, 87.5, 2.5).unwrap();
+        assert_eq!(latitude.length(), 71);
+
+        let longitude = Linspace::new(-180.0, 180.0, 5.0).unwrap();
+        assert_eq!(longitude.length(), 73);
+
+        let altitude = Linspace::new(450.0, 450.0, 0.0).unwrap();
+        assert_eq!(altitude.length(), 1);
     }
 
     #[test]
@@ -217,6 +266,50 @@ mod test {
     fn longitude_linspace() {
         let linspace = Linspace::new(-180.0, 180.0, 5.0).unwrap();
         assert_eq!(linspace.nearest_lower(-179.0), Some(-180.0));
+        assert_eq!(linspace.nearest_lower(12.5), Some(10.0));
+        assert_eq!(linspace.nearest_lower(180.0), Some(180.0));
+        assert_eq!(linspace.nearest_above(12.5), Some(15.0));
+        assert_eq!(linspace.nearest_lower(-180.1), None);
+        assert_eq!(linspace.nearest_lower(180.1), None);
+    }
+
+    #[test]
+    fn descending_latitude_linspace() {
+        let linspace = Linspace::from((87.5, -87.5, -2.5));
+
+        assert_eq!(linspace.nearest_lower(-85.0), Some(-85.0));
+        assert_eq!(linspace.nearest_lower(-86.0), Some(-87.5));
+        assert_eq!(linspace.nearest_lower(1.0), Some(0.0));
+        assert_eq!(linspace.nearest_lower(87.5), Some(87.5));
+        assert_eq!(linspace.nearest_above(1.0), Some(2.5));
+        assert_eq!(linspace.nearest_lower(88.0), None);
+        assert_eq!(linspace.nearest_lower(-90.0), None);
+    }
+
+    #[test]
+    fn linspace_iter() {
+        let longitude = Linspace::new(-180.0, 180.0, 5.0).unwrap();
+        let nodes = longitude.iter().collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 73);
+        assert_eq!(nodes[..3], [-180.0, -175.0, -170.0]);
+        assert_eq!(nodes.last(), Some(&180.0));
+
+        let latitude = Linspace::from((87.5, -87.5, -2.5));
+        let nodes = latitude.iter().collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 71);
+        assert_eq!(nodes[..3], [87.5, 85.0, 82.5]);
+        assert_eq!(nodes[35], 0.0);
+        assert_eq!(nodes.last(), Some(&-87.5));
+
+        // spacing sign does not follow the direction
+        let latitude = Linspace::from((87.5, -87.5, 2.5));
+        assert_eq!(latitude.iter().nth(1), Some(85.0));
+        assert_eq!(latitude.iter().last(), Some(-87.5));
+
+        let altitude = Linspace::new(350.0, 350.0, 0.0).unwrap();
+        assert_eq!(altitude.iter().collect::<Vec<_>>(), vec![350.0]);
     }
 
     #[test]
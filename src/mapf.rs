@@ -18,6 +18,38 @@ pub enum MappingFunction {
     QFactor,
 }
 
+impl MappingFunction {
+    /// Returns the obliquity factor (slant to vertical TEC ratio) of this [MappingFunction],
+    /// for a line of sight at this elevation angle (in degrees). This uses the single layer model
+    /// of the ionosphere: a thin shell located `shell_height_km` above the `base_radius_km` sphere.
+    /// - [MappingFunction::None]: no mapping, the obliquity factor is 1.0.
+    /// - [MappingFunction::CosZ]: 1/cos(z') where sin(z') = R/(R+H) sin(z), z being the zenith angle.
+    /// - [MappingFunction::QFactor]: is not modeled and approximated by the 1/cos(z') model.
+    pub fn obliquity(&self, elevation_deg: f64, base_radius_km: f64, shell_height_km: f64) -> f64 {
+        match self {
+            Self::None => 1.0,
+            Self::CosZ | Self::QFactor => {
+                let zenith_rad = (90.0 - elevation_deg).to_radians();
+                let sin_z = base_radius_km / (base_radius_km + shell_height_km) * zenith_rad.sin();
+                1.0 / (1.0 - sin_z.powi(2)).sqrt()
+            },
+        }
+    }
+
+    /// Evaluates the obliquity factor (slant to vertical TEC ratio) of this [MappingFunction],
+    /// for a line of sight at this elevation angle (in radians). This is the flat-Earth limit
+    /// of [Self::obliquity], where the height of the ionospheric shell is neglected:
+    /// the standard 1/cos(z) factor, z being the zenith angle. Prefer [Self::obliquity]
+    /// when the shell height is known. The elevation angle should be strictly positive.
+    /// - [MappingFunction::None]: no mapping, the obliquity factor is 1.0.
+    /// - [MappingFunction::CosZ]: 1/cos(z) = 1/sin(elevation).
+    /// - [MappingFunction::QFactor]: is not modeled and approximated by the 1/cos(z) model.
+    pub fn evaluate(&self, elevation_rad: f64) -> f64 {
+        // null shell height: sin(z') = sin(z), whatever the base radius
+        self.obliquity(elevation_rad.to_degrees(), 1.0, 0.0)
+    }
+}
+
 impl std::str::FromStr for MappingFunction {
     type Err = ParsingError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -62,4 +94,36 @@ mod test {
             assert_eq!(formatted, content);
         }
     }
+
+    #[test]
+    fn evaluate() {
+        for (elevation_deg, expected) in [(30.0_f64, 2.0), (45.0, 2.0_f64.sqrt()), (90.0, 1.0)] {
+            let elevation_rad = elevation_deg.to_radians();
+
+            for mapf in [MappingFunction::CosZ, MappingFunction::QFactor] {
+                let factor = mapf.evaluate(elevation_rad);
+
+                assert!(
+                    (factor - expected).abs() < 1.0E-9,
+                    "{} at {}°: {} vs {}",
+                    mapf,
+                    elevation_deg,
+                    factor,
+                    expected
+                );
+            }
+
+            assert_eq!(MappingFunction::None.evaluate(elevation_rad), 1.0);
+        }
+
+        // flat-Earth limit of the single layer model
+        let (radius_km, elevation_deg) = (6371.0, 30.0_f64);
+        let evaluated = MappingFunction::CosZ.evaluate(elevation_deg.to_radians());
+
+        let obliquity = MappingFunction::CosZ.obliquity(elevation_deg, radius_km, 0.0);
+        assert!((evaluated - obliquity).abs() < 1.0E-9);
+
+        let obliquity = MappingFunction::CosZ.obliquity(elevation_deg, radius_km, 350.0);
+        assert!(obliquity < evaluated);
+    }
 }
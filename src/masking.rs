@@ -0,0 +1,41 @@
+use geo::{Contains, Point, Polygon};
+
+use crate::prelude::{IONEX, Key, TEC};
+
+/// [MaskAction] describes how grid nodes that lie within a masked region are handled.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum MaskAction {
+    /// Nodes are removed from the record and the [Header](crate::prelude::Header) grid
+    /// is reduced to the remaining data, when the masked region covers complete rows or columns.
+    Remove,
+
+    /// Nodes are preserved but replaced by explicit missing values (see [TEC::missing]),
+    /// and the [Header](crate::prelude::Header) grid is preserved:
+    /// masked nodes are formatted as missing values.
+    #[default]
+    SetMissing,
+}
+
+impl IONEX {
+    /// Masks all grid nodes that lie strictly inside this [Polygon] (expressed in decimal degrees),
+    /// at all points in time. Unlike cropping, that preserves a rectangle, this blanks out
+    /// a possibly complex region and preserves the rest of the map.
+    /// Refer to [MaskAction] for the available options.
+    pub fn mask_region(&mut self, region: &Polygon, action: MaskAction) {
+        let masked = |k: &Key| region.contains(&Point::new(k.longitude_ddeg(), k.latitude_ddeg()));
+
+        match action {
+            MaskAction::Remove => {
+                self.record.map.retain(|k, _| !masked(k));
+                self.shrink_grid_mut();
+            },
+            MaskAction::SetMissing => {
+                for (k, tec) in self.record.map.iter_mut() {
+                    if masked(k) {
+                        *tec = TEC::missing();
+                    }
+                }
+            },
+        }
+    }
+}
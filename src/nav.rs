@@ -0,0 +1,89 @@
+use geo::Point;
+
+use crate::prelude::{Epoch, IONEX, SV};
+
+/// Returns the ionospheric pierce point (IPP) coordinates, in decimal degrees,
+/// of a line of sight observed from this receiver (longitude, latitude in decimal degrees)
+/// at these elevation and azimuth angles (in degrees). This uses the single layer model
+/// of the ionosphere: a thin shell located `shell_height_km` above the `base_radius_km` sphere.
+fn pierce_point(
+    receiver: Point<f64>,
+    elevation_deg: f64,
+    azimuth_deg: f64,
+    base_radius_km: f64,
+    shell_height_km: f64,
+) -> Point<f64> {
+    let (latitude_rad, longitude_rad) = (receiver.y().to_radians(), receiver.x().to_radians());
+    let (elevation_rad, azimuth_rad) = (elevation_deg.to_radians(), azimuth_deg.to_radians());
+
+    // earth centered angle
+    let psi = std::f64::consts::FRAC_PI_2
+        - elevation_rad
+        - (base_radius_km / (base_radius_km + shell_height_km) * elevation_rad.cos()).asin();
+
+    let ipp_latitude_rad = (latitude_rad.sin() * psi.cos()
+        + latitude_rad.cos() * psi.sin() * azimuth_rad.cos())
+    .asin();
+
+    let ipp_longitude_rad =
+        longitude_rad + (psi.sin() * azimuth_rad.sin() / ipp_latitude_rad.cos()).asin();
+
+    let ipp_longitude_ddeg = (ipp_longitude_rad.to_degrees() + 180.0).rem_euclid(360.0) - 180.0;
+
+    Point::new(ipp_longitude_ddeg, ipp_latitude_rad.to_degrees())
+}
+
+impl IONEX {
+    /// Returns the slant TEC (in TECu) along the line of sight of each satellite,
+    /// observed from this receiver (longitude, latitude in decimal degrees) at this [Epoch].
+    /// This is the ionospheric correction the nav-solutions framework applies to RINEX based positioning.
+    ///
+    /// For each satellite, the ionospheric pierce point (IPP) is determined with the single layer model
+    /// (thin shell at the fixed altitude of this map, above the [Header](crate::prelude::Header) base radius),
+    /// the vertical TEC is interpolated at the IPP, then converted to slant TEC
+    /// with the [Header](crate::prelude::Header) [MappingFunction](crate::prelude::MappingFunction),
+    /// see [MappingFunction::obliquity](crate::prelude::MappingFunction::obliquity).
+    ///
+    /// ## Input
+    /// - epoch: [Epoch] that must fit within the temporal axis
+    /// - sv_azel: (satellite, azimuth angle in degrees, elevation angle in degrees) for each line of sight
+    /// - receiver: receiver coordinates, as longitude (x) and latitude (y) in decimal degrees
+    ///
+    /// ## Output
+    /// - (satellite, slant TEC in TECu) for each line of sight.
+    /// Satellites below the horizon, or whose IPP cannot be interpolated (outside of this map),
+    /// are not returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+    pub fn vtec_correction(
+        &self,
+        epoch: Epoch,
+        sv_azel: &[(SV, f64, f64)],
+        receiver: Point<f64>,
+    ) -> Vec<(SV, f64)> {
+        let base_radius_km = self.header.base_radius_km as f64;
+        let shell_height_km = self.header.grid.altitude.start;
+
+        sv_azel
+            .iter()
+            .filter(|(_, _, elevation_deg)| *elevation_deg > 0.0)
+            .filter_map(|(sv, azimuth_deg, elevation_deg)| {
+                let ipp = pierce_point(
+                    receiver,
+                    *elevation_deg,
+                    *azimuth_deg,
+                    base_radius_km,
+                    shell_height_km,
+                );
+
+                let vertical_tecu = self.interpolated_tecu(epoch, ipp).ok()?;
+
+                let obliquity =
+                    self.header
+                        .mapf
+                        .obliquity(*elevation_deg, base_radius_km, shell_height_km);
+
+                Some((*sv, vertical_tecu * obliquity))
+            })
+            .collect()
+    }
+}
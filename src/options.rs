@@ -0,0 +1,32 @@
+/// [MissingPolicy] describes how missing TEC values (`9999` in the TEC maps) are parsed.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum MissingPolicy {
+    /// Missing values are not stored in the [Record](crate::prelude::Record).
+    #[default]
+    Skip,
+
+    /// Missing values are stored as explicit missing [TEC](crate::prelude::TEC),
+    /// see [TEC::missing](crate::prelude::TEC::missing). This gives a dense record.
+    InsertMissing,
+
+    /// Missing values are stored as NaN [TEC](crate::prelude::TEC) values.
+    /// This gives a dense record.
+    InsertNaN,
+}
+
+/// [ParseOptions] to customize the parsing process,
+/// see [IONEX::parse_with](crate::prelude::IONEX::parse_with).
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// [MissingPolicy] applied to the TEC maps
+    pub missing_policy: MissingPolicy,
+}
+
+impl ParseOptions {
+    /// Copies and returns [ParseOptions] with updated [MissingPolicy].
+    pub fn with_missing_policy(&self, missing_policy: MissingPolicy) -> Self {
+        let mut s = *self;
+        s.missing_policy = missing_policy;
+        s
+    }
+}
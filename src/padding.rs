@@ -0,0 +1,162 @@
+use geo::Point;
+
+use crate::prelude::{Epoch, IONEX, Key, TEC};
+
+/// [FillPolicy] describes how missing grid nodes are valued when padding a map.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum FillPolicy {
+    /// Missing nodes are inserted as explicit missing values (see [TEC::missing]):
+    /// the grid is complete, but no value is made up.
+    Missing,
+
+    /// Missing nodes are valued with a null TEC.
+    /// Note that this cannot be told apart from an actual measurement.
+    Null,
+
+    /// Missing nodes are valued with the TEC of the nearest node, at the same point in time.
+    Nearest,
+
+    /// Missing nodes are interpolated from all nodes at the same point in time,
+    /// using inverse squared distance weighting.
+    #[default]
+    InverseDistance,
+}
+
+impl FillPolicy {
+    /// Values this missing point (in decimal degrees), from the (point in decimal degrees, TECu)
+    /// nodes that are present at the same point in time.
+    fn fill_tec(&self, present: &[(Point<f64>, f64)], point: Point<f64>) -> TEC {
+        match self {
+            Self::Missing => TEC::missing(),
+            _ => TEC::from_tecu(self.fill_tecu(present, point)),
+        }
+    }
+
+    /// Values this missing point (in decimal degrees) in TECu, see [Self::fill_tec].
+    fn fill_tecu(&self, present: &[(Point<f64>, f64)], point: Point<f64>) -> f64 {
+        let distance = |rhs: &Point<f64>| (rhs.x() - point.x()).hypot(rhs.y() - point.y());
+
+        match self {
+            Self::Missing => f64::NAN,
+            Self::Null => 0.0,
+            Self::Nearest => present
+                .iter()
+                .min_by(|(a, _), (b, _)| distance(a).total_cmp(&distance(b)))
+                .map(|(_, tecu)| *tecu)
+                .unwrap_or_default(),
+            Self::InverseDistance => {
+                let (mut weighted, mut weights) = (0.0, 0.0);
+
+                for (node, tecu) in present.iter() {
+                    let weight = distance(node).powi(2).recip();
+                    weighted += weight * tecu;
+                    weights += weight;
+                }
+
+                if weights > 0.0 {
+                    weighted / weights
+                } else {
+                    0.0
+                }
+            },
+        }
+    }
+}
+
+impl IONEX {
+    /// Returns the fraction of grid nodes (described by the [Header](crate::prelude::Header))
+    /// that have a TEC value, across all [Epoch](crate::prelude::Epoch)s of the record.
+    /// 1.0 means that all maps are complete.
+    pub fn coverage_fraction(&self) -> f64 {
+        let num_nodes = self.header.grid.latitude.length()
+            * self.header.grid.longitude.length()
+            * self.epoch_iter().count();
+
+        if num_nodes == 0 {
+            return 0.0;
+        }
+
+        (num_nodes - self.missing_count()) as f64 / num_nodes as f64
+    }
+
+    /// Pads this [IONEX] so each map is a complete rectangular grid, that matches the
+    /// [Header](crate::prelude::Header) description. Missing nodes are inserted
+    /// and valued according to the [FillPolicy]: with [FillPolicy::Missing], they remain
+    /// missing values, so the coverage is not improved. RMS values are not padded.
+    pub fn pad_to_grid(&mut self, fill: FillPolicy) {
+        let fixed_altitude_km = self.header.grid.altitude.start;
+
+        let epochs = self.epoch_iter().collect::<Vec<_>>();
+
+        for epoch in epochs {
+            let missing = self.missing_coordinates(epoch);
+
+            if missing.is_empty() {
+                continue;
+            }
+
+            let present = self
+                .record
+                .synchronous_iter(epoch)
+                .filter(|(_, v)| !v.is_missing())
+                .map(|(k, v)| (Point::new(k.longitude_ddeg(), k.latitude_ddeg()), v.tecu()))
+                .collect::<Vec<_>>();
+
+            for point in missing {
+                let tec = fill.fill_tec(&present, point);
+
+                let key =
+                    Key::from_decimal_degrees_km(epoch, point.y(), point.x(), fixed_altitude_km);
+
+                self.record.insert(key, tec);
+            }
+        }
+    }
+
+    /// Returns a dense view of the map at this [Epoch], without modifying the [Record]:
+    /// (latitude in decimal degrees, longitude in decimal degrees, TECu) is returned
+    /// for every grid node described by the [Header](crate::prelude::Header),
+    /// following the grid directions (latitude major). Missing nodes are valued on the fly,
+    /// according to the [FillPolicy] (NaN for [FillPolicy::Missing]),
+    /// see [Self::pad_to_grid] to pad the [Record] itself.
+    pub fn dense_points(
+        &self,
+        epoch: Epoch,
+        fill: FillPolicy,
+    ) -> Box<dyn Iterator<Item = (f64, f64, f64)> + '_> {
+        let fixed_altitude_km = self.header.grid.altitude.start;
+
+        let present = self
+            .record
+            .synchronous_iter(epoch)
+            .filter(|(_, v)| !v.is_missing())
+            .map(|(k, v)| (Point::new(k.longitude_ddeg(), k.latitude_ddeg()), v.tecu()))
+            .collect::<Vec<_>>();
+
+        let longitudes = self.header.grid.longitude.iter().collect::<Vec<_>>();
+
+        Box::new(
+            self.header
+                .grid
+                .latitude
+                .iter()
+                .flat_map(move |lat_ddeg| {
+                    longitudes
+                        .clone()
+                        .into_iter()
+                        .map(move |long_ddeg| (lat_ddeg, long_ddeg))
+                })
+                .map(move |(lat_ddeg, long_ddeg)| {
+                    let key =
+                        Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, fixed_altitude_km);
+
+                    let tecu = match self.record.get(&key) {
+                        Some(tec) if !tec.is_missing() => tec.tecu(),
+                        _ => fill.fill_tecu(&present, Point::new(long_ddeg, lat_ddeg)),
+                    };
+
+                    (lat_ddeg, long_ddeg, tecu)
+                }),
+        )
+    }
+}
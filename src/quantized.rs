@@ -6,6 +6,15 @@ use serde::{Deserialize, Serialize};
 
 use std::cmp::Ordering;
 
+/// Quantized value that represents non finite values (NaN, infinite).
+const NON_FINITE: i64 = i64::MIN;
+
+/// Quantized value that represents explicit missing values.
+const MISSING: i64 = i64::MIN + 1;
+
+/// Scaling of canonical [Quantized] values, see [Quantized::canonical].
+const CANONICAL_EXPONENT: i8 = 6;
+
 /// [Quantized] value representing either a [TEC] estimate,
 /// or discrete coordinates as [QuantizedCoordinates].
 #[derive(Debug, Default, Copy, Clone, Hash)]
@@ -18,15 +27,17 @@ pub struct Quantized {
     pub value: i64,
 }
 
+// Equality and ordering follow [Ord], so missing and non finite values
+// are equal to themselves, as required by [Eq].
 impl PartialEq for Quantized {
     fn eq(&self, rhs: &Self) -> bool {
-        self.real_value().eq(&rhs.real_value())
+        self.cmp(rhs) == Ordering::Equal
     }
 }
 
 impl PartialOrd for Quantized {
     fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
-        self.real_value().partial_cmp(&rhs.real_value())
+        Some(self.cmp(rhs))
     }
 }
 
@@ -41,6 +52,11 @@ impl Ord for Quantized {
 impl Quantized {
     /// Determines best suited exponent to quantize given value
     pub(crate) fn find_exponent(value: f64) -> i8 {
+        // would never complete
+        if !value.is_finite() {
+            return 0;
+        }
+
         let mut val = value;
         let mut exponent = 0;
 
@@ -52,7 +68,16 @@ impl Quantized {
         exponent
     }
 
+    /// Quantizes given value, using this scaling.
+    /// Non finite values (NaN, infinite) are preserved as NaN.
     pub fn new(value: f64, exponent: i8) -> Self {
+        if !value.is_finite() {
+            return Self {
+                value: NON_FINITE,
+                exponent: 0,
+            };
+        }
+
         let value = (value * 10.0_f64.powi(exponent as i32)).round() as i64;
         Self { value, exponent }
     }
@@ -64,8 +89,52 @@ impl Quantized {
         Self::new(value, exponent)
     }
 
+    /// Returns the canonical representation of this [Quantized] value:
+    /// rounded to [CANONICAL_EXPONENT] decimals at most, without trailing zeros.
+    /// Values that only differ by floating point noise share the same canonical representation.
+    pub(crate) fn canonical(&self) -> Self {
+        if self.value == NON_FINITE || self.value == MISSING {
+            return *self;
+        }
+
+        let mut canonical = Self::new(self.real_value(), CANONICAL_EXPONENT);
+
+        while canonical.exponent > 0 && canonical.value % 10 == 0 {
+            canonical.value /= 10;
+            canonical.exponent -= 1;
+        }
+
+        canonical
+    }
+
+    /// Returns an explicit missing value, whose real value is NaN.
+    pub(crate) fn missing() -> Self {
+        Self {
+            value: MISSING,
+            exponent: 0,
+        }
+    }
+
+    /// Returns the lowest finite [Quantized] value, which sorts before any other
+    /// finite value. Unlike the sentinel values, it is suitable as a range query bound.
+    pub(crate) fn lowest() -> Self {
+        Self {
+            value: MISSING + 1,
+            exponent: 0,
+        }
+    }
+
+    /// Returns true if this is an explicit missing value.
+    pub(crate) fn is_missing(&self) -> bool {
+        self.value == MISSING
+    }
+
     /// Returns quantized value
     pub fn real_value(&self) -> f64 {
+        if self.value == NON_FINITE || self.value == MISSING {
+            return f64::NAN;
+        }
+
         self.value as f64 / 10.0_f64.powi(self.exponent as i32)
     }
 }
@@ -74,6 +143,31 @@ impl Quantized {
 mod test {
     use super::Quantized;
 
+    #[test]
+    fn sentinels_equality() {
+        let missing = Quantized::missing();
+        let nan = Quantized::new(f64::NAN, 2);
+
+        assert_eq!(missing, Quantized::missing());
+        assert_eq!(nan, Quantized::new(f64::INFINITY, 1));
+        assert_eq!(
+            missing.cmp(&Quantized::missing()),
+            std::cmp::Ordering::Equal
+        );
+        assert_ne!(missing, Quantized::auto_scaled(0.0));
+    }
+
+    #[test]
+    fn lowest_bound() {
+        let lowest = Quantized::lowest();
+
+        assert!(lowest.real_value().is_finite());
+
+        for value in [-180.0, -87.5, 0.0, 350.0] {
+            assert!(lowest < Quantized::auto_scaled(value));
+        }
+    }
+
     #[test]
     fn test_exponent_finder() {
         assert_eq!(Quantized::find_exponent(5.0), 0);
@@ -84,6 +178,15 @@ mod test {
         assert_eq!(Quantized::find_exponent(0.333), 3);
     }
 
+    #[test]
+    fn test_non_finite() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(Quantized::find_exponent(value), 0);
+            assert!(Quantized::new(value, 2).real_value().is_nan());
+            assert!(Quantized::auto_scaled(value).real_value().is_nan());
+        }
+    }
+
     #[test]
     fn test_quantized_ordering() {
         assert!(Quantized::new(1.0, 0) > Quantized::new(0.1, 0));
@@ -0,0 +1,113 @@
+use crate::{
+    coordinates::QuantizedCoordinates,
+    error::{FormattingError, ParsingError},
+    prelude::{Epoch, Key, Record, TEC},
+};
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    str::FromStr,
+};
+
+/// CSV header (columns) line
+const CSV_HEADER: &str = "epoch,lat_ddeg,long_ddeg,alt_km,tecu,rms";
+
+impl Record {
+    /// Dumps this [Record] as CSV, one row per entry, in chronological order.
+    /// The columns are `epoch,lat_ddeg,long_ddeg,alt_km,tecu,rms`, the first line
+    /// being this header. `tecu` is empty for explicit missing values and `rms` is empty
+    /// when not determined. This is convenient to import the maps into dataframes
+    /// or spreadsheets, see [Record::from_csv] for the opposite operation.
+    pub fn to_csv<W: Write>(&self, w: &mut W) -> Result<(), FormattingError> {
+        writeln!(w, "{}", CSV_HEADER)?;
+
+        for (k, v) in self.map.iter() {
+            let tecu = if v.is_missing() {
+                String::new()
+            } else {
+                v.tecu().to_string()
+            };
+
+            let rms = v
+                .root_mean_square()
+                .map(|rms| rms.to_string())
+                .unwrap_or_default();
+
+            writeln!(
+                w,
+                "{},{},{},{},{},{}",
+                k.epoch,
+                k.latitude_ddeg(),
+                k.longitude_ddeg(),
+                k.altitude_km(),
+                tecu,
+                rms
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses a [Record] from CSV content, as formatted by [Record::to_csv].
+    /// The header line is optional and empty lines are ignored.
+    pub fn from_csv<R: Read>(r: &mut R) -> Result<Record, ParsingError> {
+        let mut record = Record::default();
+
+        for line in BufReader::new(r).lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line == CSV_HEADER {
+                continue;
+            }
+
+            let columns = line.split(',').map(str::trim).collect::<Vec<_>>();
+
+            if columns.len() != 6 {
+                return Err(ParsingError::CsvRecord(line.to_string()));
+            }
+
+            let epoch = Epoch::from_str(columns[0]).map_err(|_| ParsingError::EpochParsing)?;
+
+            let mut coordinates = [0.0; 3];
+
+            for (coordinate, column) in coordinates.iter_mut().zip(&columns[1..4]) {
+                *coordinate = column
+                    .parse::<f64>()
+                    .map_err(|_| ParsingError::CoordinatesParsing)?;
+            }
+
+            let [lat_ddeg, long_ddeg, alt_km] = coordinates;
+
+            let mut tec = if columns[4].is_empty() {
+                TEC::missing()
+            } else {
+                let tecu = columns[4]
+                    .parse::<f64>()
+                    .map_err(|_| ParsingError::CsvRecord(line.to_string()))?;
+
+                TEC::from_tecu(tecu)
+            };
+
+            if !columns[5].is_empty() {
+                let rms = columns[5]
+                    .parse::<f64>()
+                    .map_err(|_| ParsingError::CsvRecord(line.to_string()))?;
+
+                tec = tec.with_rms(rms);
+            }
+
+            record.insert(
+                Key {
+                    epoch,
+                    coordinates: QuantizedCoordinates::from_decimal_degrees(
+                        lat_ddeg, long_ddeg, alt_km,
+                    ),
+                },
+                tec,
+            );
+        }
+
+        Ok(record)
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     epoch::format_body as format_epoch,
     error::FormattingError,
     fmt_ionex,
-    prelude::{Header, Key, Record},
+    prelude::{Epoch, Header, Key, Record, TEC},
 };
 
 use std::io::{BufWriter, Write};
@@ -16,41 +16,77 @@ impl Record {
         header: &Header,
         w: &mut BufWriter<W>,
     ) -> Result<(), FormattingError> {
-        const FORMATTED_OFFSET: usize = 5;
-
         // NB: this will not work if
         // - grid accuracy changes between regions or epochs
-        // - map is not 2D
         // - does not support scaling update very smoothly
 
-        let (latitude_min, latitude_max) = header.grid.latitude.minmax();
-        let (longitude_min, longitude_max) = header.grid.longitude.minmax();
-
-        let mut line_offset;
-        let mut longitude_ptr_ddeg;
-        let mut has_rms = false;
+        let has_rms = self.map.values().any(|tec| tec.rms.is_some());
+        let has_height = self.map.values().any(|tec| tec.height.is_some());
 
-        // TEC MAPs. Grid browsing:
-        // - browse latitude (from southernmost to northernmost)
-        // - browse longitude (from westernmost to easternmost)
+        // TEC MAPs (missing and non finite values are formatted as missing)
         for (nth_map, epoch) in self.epochs_iter().enumerate() {
-            writeln!(
-                w,
-                "{}",
-                fmt_ionex(&format!("{:6}", nth_map + 1), "START OF TEC MAP")
-            )?;
+            self.format_map(header, w, "TEC MAP", nth_map, epoch, |tec| {
+                Some(tec.quantized_tecu(header.exponent)).filter(|_| tec.tecu().is_finite())
+            })?;
+        }
+
+        // RMS MAPs (if any)
+        if has_rms {
+            for (nth_map, epoch) in self.epochs_iter().enumerate() {
+                self.format_map(header, w, "RMS MAP", nth_map, epoch, |tec| {
+                    tec.quantized_root_mean_square(header.exponent)
+                })?;
+            }
+        }
 
-            writeln!(
-                w,
-                "{}",
-                fmt_ionex(&format_epoch(epoch), "EPOCH OF CURRENT MAP")
-            )?;
+        // HEIGHT MAPs (if any)
+        if has_height {
+            for (nth_map, epoch) in self.epochs_iter().enumerate() {
+                self.format_map(header, w, "HEIGHT MAP", nth_map, epoch, |tec| {
+                    tec.quantized_height(header.exponent)
+                })?;
+            }
+        }
 
-            let mut latitude_ptr_ddeg = latitude_max;
+        // mark END of file
+        writeln!(w, "{}", fmt_ionex("", "END OF FILE"))?;
 
-            while latitude_ptr_ddeg >= latitude_min {
+        Ok(())
+    }
+
+    /// Formats one map of this kind ("TEC MAP", "RMS MAP" or "HEIGHT MAP"),
+    /// using this quantized value formatter. Grid browsing follows the header definition:
+    /// - browse altitude (from HGT1 to HGT2)
+    /// - browse latitude (from LAT1 to LAT2)
+    /// - browse longitude (from LON1 to LON2)
+    fn format_map<W: Write, F: Fn(&TEC) -> Option<i64>>(
+        &self,
+        header: &Header,
+        w: &mut BufWriter<W>,
+        kind: &str,
+        nth_map: usize,
+        epoch: Epoch,
+        value: F,
+    ) -> Result<(), FormattingError> {
+        const FORMATTED_OFFSET: usize = 5;
+
+        let mut line_offset;
+
+        writeln!(
+            w,
+            "{}",
+            fmt_ionex(&format!("{:6}", nth_map + 1), &format!("START OF {}", kind))
+        )?;
+
+        writeln!(
+            w,
+            "{}",
+            fmt_ionex(&format_epoch(epoch), "EPOCH OF CURRENT MAP")
+        )?;
+
+        for altitude_ptr_km in header.grid.altitude.iter() {
+            for latitude_ptr_ddeg in header.grid.latitude.iter() {
                 line_offset = 0;
-                longitude_ptr_ddeg = longitude_min;
 
                 // grid specs
                 writeln!(
@@ -63,29 +99,26 @@ impl Record {
                             header.grid.longitude.start,
                             header.grid.longitude.end,
                             header.grid.longitude.spacing,
-                            header.grid.altitude.start,
+                            altitude_ptr_km,
                         ),
                         "LAT/LON1/LON2/DLON/H"
                     )
                 )?;
 
-                while longitude_ptr_ddeg <= longitude_max {
+                for longitude_ptr_ddeg in header.grid.longitude.iter() {
                     // obtain coordinates
                     let coordinates = QuantizedCoordinates::from_decimal_degrees(
                         latitude_ptr_ddeg,
                         longitude_ptr_ddeg,
-                        header.grid.altitude.start,
+                        altitude_ptr_km,
                     );
 
                     let key = Key { epoch, coordinates };
 
                     // format map
-                    if let Some(tec) = self.get(&key) {
-                        has_rms |= tec.rms.is_some();
-
-                        write!(w, "{:5}", tec.tecu.value)?;
-                    } else {
-                        write!(w, " 9999")?; // standardized
+                    match self.get(&key).and_then(&value) {
+                        Some(value) => write!(w, "{:5}", value)?,
+                        None => write!(w, " 9999")?, // standardized
                     }
 
                     line_offset += FORMATTED_OFFSET;
@@ -94,114 +127,20 @@ impl Record {
                         write!(w, "{}", '\n')?;
                         line_offset = 0;
                     }
-
-                    longitude_ptr_ddeg += header.grid.longitude.spacing;
                 }
 
                 if line_offset != 80 {
                     // needs termination
                     write!(w, "{}", '\n')?;
                 }
-
-                latitude_ptr_ddeg += header.grid.latitude.spacing;
-            }
-
-            writeln!(
-                w,
-                "{}",
-                fmt_ionex(&format!("{:6}", nth_map + 1), "END OF TEC MAP")
-            )?;
-        }
-
-        // RMS MAP (if any) Grid browsing:
-        // - browse latitude (from southernmost to northernmost)
-        // - browse longitude (from westernmost to easternmost)
-        if has_rms {
-            for (nth_map, epoch) in self.epochs_iter().enumerate() {
-                writeln!(
-                    w,
-                    "{}",
-                    fmt_ionex(&format!("{:6}", nth_map + 1), "START OF RMS MAP")
-                )?;
-
-                writeln!(
-                    w,
-                    "{}",
-                    fmt_ionex(&format_epoch(epoch), "EPOCH OF CURRENT MAP")
-                )?;
-
-                let mut latitude_ptr_ddeg = latitude_max;
-
-                while latitude_ptr_ddeg >= latitude_min {
-                    line_offset = 0;
-                    longitude_ptr_ddeg = longitude_min;
-
-                    // grid specs
-                    writeln!(
-                        w,
-                        "{}",
-                        fmt_ionex(
-                            &format!(
-                                "  {:6.1}{:6.1}{:6.1}{:6.1}{:6.1}",
-                                latitude_ptr_ddeg,
-                                header.grid.longitude.start,
-                                header.grid.longitude.end,
-                                header.grid.longitude.spacing,
-                                header.grid.altitude.start,
-                            ),
-                            "LAT/LON1/LON2/DLON/H"
-                        )
-                    )?;
-
-                    while longitude_ptr_ddeg <= longitude_max {
-                        // obtain coordinates
-                        let coordinates = QuantizedCoordinates::from_decimal_degrees(
-                            latitude_ptr_ddeg,
-                            longitude_ptr_ddeg,
-                            header.grid.altitude.start,
-                        );
-
-                        let key = Key { epoch, coordinates };
-
-                        // format map
-                        if let Some(tec) = self.get(&key) {
-                            if let Some(rms) = tec.rms {
-                                write!(w, "{:5}", rms.value)?;
-                            } else {
-                                write!(w, " 9999")?; // standardized
-                            }
-                        } else {
-                            write!(w, " 9999")?; // standardized
-                        }
-
-                        line_offset += FORMATTED_OFFSET;
-
-                        if line_offset >= 80 {
-                            write!(w, "{}", '\n')?;
-                            line_offset = 0;
-                        }
-
-                        longitude_ptr_ddeg += header.grid.longitude.spacing;
-                    }
-
-                    if line_offset != 80 {
-                        // needs termination
-                        write!(w, "{}", '\n')?;
-                    }
-
-                    latitude_ptr_ddeg += header.grid.latitude.spacing;
-                }
-
-                writeln!(
-                    w,
-                    "{}",
-                    fmt_ionex(&format!("{:6}", nth_map + 1), "END OF RMS MAP")
-                )?;
             }
         }
 
-        // mark END of file
-        writeln!(w, "{}", fmt_ionex("", "END OF FILE"))?;
+        writeln!(
+            w,
+            "{}",
+            fmt_ionex(&format!("{:6}", nth_map + 1), &format!("END OF {}", kind))
+        )?;
 
         Ok(())
     }
@@ -1,14 +1,30 @@
+mod csv;
 mod formatting;
 mod parsing;
 
 #[cfg(feature = "qc")]
 mod qc;
 
+#[cfg(feature = "qc")]
+pub use qc::MergeStrategy;
+
 use std::collections::{BTreeMap, btree_map::Iter};
 
 use itertools::Itertools;
 
-use crate::prelude::{Epoch, Key, MapCell, TEC};
+use geo::Point;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    cell::TecPoint,
+    coordinates::QuantizedCoordinates,
+    grid::Grid,
+    linspace_node_index,
+    prelude::{Epoch, Error, Key, MapCell, TEC},
+    quantized::Quantized,
+};
 
 /// IONEX [Record] contains [MapCell]s in chronological order.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -16,6 +32,26 @@ pub struct Record {
     pub(crate) map: BTreeMap<Key, TEC>,
 }
 
+/// [Record] is serialized as a list of ([Key], [TEC]) pairs in chronological order,
+/// because [Key] is not a valid map key in most formats (JSON for example).
+#[cfg(feature = "serde")]
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.map.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = Vec::<(Key, TEC)>::deserialize(deserializer)?;
+
+        Ok(Self {
+            map: entries.into_iter().collect(),
+        })
+    }
+}
+
 impl Record {
     /// Insert new [TEC] value into IONEX [Record]
     pub fn insert(&mut self, key: Key, tec: TEC) {
@@ -32,26 +68,72 @@ impl Record {
         Box::new(self.map.iter_mut().map(|(k, v)| (*k, v)))
     }
 
-    /// Obtain [Record] Iterator at specific point in time
+    /// Obtain [Record] Iterator at specific point in time.
+    /// Only this [Epoch] is browsed (range query), because [Key]s are sorted by [Epoch] first.
     pub fn synchronous_iter(&self, epoch: Epoch) -> Box<dyn Iterator<Item = (Key, TEC)> + '_> {
-        Box::new(self.iter().filter_map(move |(k, v)| {
-            if k.epoch == epoch {
-                Some((*k, *v))
-            } else {
-                None
-            }
-        }))
+        Box::new(self.synchronous_iter_ref(epoch).map(|(k, v)| (*k, *v)))
+    }
+
+    /// Obtain a borrowing [Record] iterator at specific point in time.
+    /// Unlike [Self::synchronous_iter], nothing is copied, and only this [Epoch]
+    /// is browsed (range query), because [Key]s are sorted by [Epoch] first.
+    pub fn synchronous_iter_ref(
+        &self,
+        epoch: Epoch,
+    ) -> Box<dyn Iterator<Item = (&Key, &TEC)> + '_> {
+        Box::new(
+            self.map
+                .range(Self::synchronous_start(epoch)..)
+                .take_while(move |(k, _)| k.epoch == epoch),
+        )
+    }
+
+    /// Returns the first possible [Key] at this [Epoch], which starts the range queries.
+    fn synchronous_start(epoch: Epoch) -> Key {
+        Key {
+            epoch,
+            coordinates: QuantizedCoordinates::min(),
+        }
     }
 
-    /// Obtain mutable synchronous [Record] iterator
+    /// Obtain mutable synchronous [Record] iterator.
+    /// Only this [Epoch] is browsed (range query), because [Key]s are sorted by [Epoch] first.
     pub fn synchronous_iter_mut(
         &mut self,
         epoch: Epoch,
     ) -> Box<dyn Iterator<Item = (Key, &mut TEC)> + '_> {
-        Box::new(self.iter_mut().filter_map(
-            move |(k, v)| {
-                if k.epoch == epoch { Some((k, v)) } else { None }
-            },
+        Box::new(
+            self.map
+                .range_mut(Self::synchronous_start(epoch)..)
+                .take_while(move |(k, _)| k.epoch == epoch)
+                .map(|(k, v)| (*k, v)),
+        )
+    }
+
+    /// Locates the value at these coordinates (decimal degrees) and [Epoch], returning its
+    /// (latitude, longitude) indexes in the canonical grid order, as formatted in IONEX maps:
+    /// from the northernmost latitude and from the westernmost longitude.
+    /// This helps correlating a value with its position in the source file.
+    /// The grid is inferred from this [Record], see [Grid::from_record].
+    /// Returns None if there is no value at these coordinates and [Epoch].
+    pub fn locate(&self, epoch: Epoch, lat_ddeg: f64, long_ddeg: f64) -> Option<(usize, usize)> {
+        let (lat_ddeg, long_ddeg) = (
+            Quantized::auto_scaled(lat_ddeg).real_value(),
+            Quantized::auto_scaled(long_ddeg).real_value(),
+        );
+
+        if !self
+            .synchronous_iter_ref(epoch)
+            .any(|(k, _)| k.latitude_ddeg() == lat_ddeg && k.longitude_ddeg() == long_ddeg)
+        {
+            return None;
+        }
+
+        let grid = Grid::from_record(self).ok()?;
+
+        Some((
+            linspace_node_index(&grid.latitude, lat_ddeg),
+            linspace_node_index(&grid.longitude, long_ddeg),
         ))
     }
 
@@ -62,7 +144,38 @@ impl Record {
     }
 
     /// Obtain a [MapCell] (4 single points) from IONEX [Record], at specified point in time and coordinates.
-    /// The coordinates
+    /// The cell is defined by its southwestern corner (longitude, latitude in decimal degrees),
+    /// and extends by the latitude and longitude spacings (in decimal degrees, regardless of their sign)
+    /// towards the north and the east. Corners are expressed in decimal degrees, like any [TecPoint].
+    /// This is an indexing method: returns None if any corner is missing.
+    pub fn get_cell(
+        &self,
+        epoch: Epoch,
+        sw: Point<f64>,
+        spacing_lat: f64,
+        spacing_long: f64,
+        altitude_km: f64,
+    ) -> Option<MapCell> {
+        let (south, west) = (sw.y(), sw.x());
+        let (north, east) = (south + spacing_lat.abs(), west + spacing_long.abs());
+
+        let tec_point = |lat_ddeg: f64, long_ddeg: f64| {
+            let key = Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, altitude_km);
+
+            self.get(&key).map(|tec| TecPoint {
+                tec: *tec,
+                point: Point::new(long_ddeg, lat_ddeg),
+            })
+        };
+
+        Some(MapCell::from_cardinal_points(
+            epoch,
+            tec_point(north, east)?,
+            tec_point(north, west)?,
+            tec_point(south, east)?,
+            tec_point(south, west)?,
+        ))
+    }
 
     /// Obtain mutable [TEC] reference from IONEX [Record], at specified spatial and temporal coordinates that must exist.
     /// This is an indexing method, not an interpolation method.  
@@ -71,6 +184,12 @@ impl Record {
         self.map.get_mut(key)
     }
 
+    /// Retains only the entries of this [Record] for which the predicate returns true,
+    /// for example to apply a spatial mask or a TEC threshold.
+    pub fn retain<F: FnMut(&Key, &TEC) -> bool>(&mut self, mut f: F) {
+        self.map.retain(|k, v| f(k, v));
+    }
+
     /// Collect IONEX [Record] from a list of [MapCell]s.
     /// This is particularly useful to reconstruct a [IONEX] file from a possibly processed
     /// and modified slice of [MapCell]s.
@@ -81,6 +200,7 @@ impl Record {
     /// and we do not verify it!
     /// - fixed_altitude_km: the fixed altitude in kilometers,
     /// use to represent the IONEX plane from the slice of planar [MapCell]s
+    /// (see [IONEX::fixed_altitude_km](crate::IONEX::fixed_altitude_km)).
     pub fn from_map_cells(slice: &[MapCell], fixed_altitude_km: f64) -> Self {
         let mut map = BTreeMap::<Key, TEC>::default();
 
@@ -139,6 +259,87 @@ impl Record {
         Self { map }
     }
 
+    /// Blends this [Record] with another [Record], typically another estimate of the same maps.
+    /// Coincident [TEC] values are combined as `w * lhs + (1 - w) * rhs`, where `w` is the weight
+    /// of this [Record], which must be within [0; 1].
+    /// Both estimates are assumed to be statistically independent, so coincident RMS are
+    /// combined in quadrature: `sqrt(w² σ1² + (1 - w)² σ2²)`. When a single RMS is known,
+    /// it is kept as is: the quality of the other estimate being unknown, the blended value
+    /// cannot be considered more accurate than the known one.
+    /// Missing (non finite) values do not take part in the blending: the valid estimate
+    /// is then kept as is. Values that only exist in `rhs` are inserted as is.
+    pub fn blend_mut(&mut self, rhs: &Self, weight: f64) -> Result<(), Error> {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(Error::InvalidWeight);
+        }
+
+        for (key, rhs_tec) in rhs.map.iter() {
+            let lhs_tec = match self.map.get_mut(key) {
+                Some(lhs_tec) => lhs_tec,
+                None => {
+                    self.map.insert(*key, *rhs_tec);
+                    continue;
+                },
+            };
+
+            if !rhs_tec.tecu().is_finite() {
+                continue;
+            }
+
+            if !lhs_tec.tecu().is_finite() {
+                *lhs_tec = *rhs_tec;
+                continue;
+            }
+
+            let tecu = weight * lhs_tec.tecu() + (1.0 - weight) * rhs_tec.tecu();
+
+            let (lhs_rms, rhs_rms) = (
+                lhs_tec.root_mean_square().filter(|rms| rms.is_finite()),
+                rhs_tec.root_mean_square().filter(|rms| rms.is_finite()),
+            );
+
+            let rms = match (lhs_rms, rhs_rms) {
+                (Some(lhs_rms), Some(rhs_rms)) => {
+                    Some(((weight * lhs_rms).powi(2) + ((1.0 - weight) * rhs_rms).powi(2)).sqrt())
+                },
+                (Some(lhs_rms), None) => Some(lhs_rms),
+                (None, Some(rhs_rms)) => Some(rhs_rms),
+                (None, None) => None,
+            };
+
+            *lhs_tec = lhs_tec.with_tecu(tecu);
+
+            if let Some(rms) = rms {
+                *lhs_tec = lhs_tec.with_rms(rms);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds this [Record], re-normalizing all [Key]s to their canonical quantization.
+    /// [Key]s that are logically identical but were quantized differently (for example,
+    /// coordinates that only differ by floating point noise) are collapsed into a single entry,
+    /// the last one (in [Key] order) being preserved.
+    /// Returns the number of collapsed entries.
+    pub fn rebuild(&mut self) -> usize {
+        let size = self.map.len();
+
+        self.map = std::mem::take(&mut self.map)
+            .into_iter()
+            .map(|(key, tec)| {
+                let key = Key {
+                    epoch: key.epoch,
+                    coordinates: key.coordinates.canonical(),
+                };
+
+                (key, tec)
+            })
+            .collect();
+
+        size - self.map.len()
+    }
+
     /// Obtain [Epoch]s Iterator in chronological order.
     pub fn epochs_iter(&self) -> Box<dyn Iterator<Item = Epoch> + '_> {
         Box::new(self.map.keys().map(|k| k.epoch).unique())
@@ -148,6 +349,11 @@ impl Record {
     pub fn first_epoch(&self) -> Option<Epoch> {
         self.epochs_iter().nth(0)
     }
+
+    /// Returns last [Epoch] in chronological order
+    pub fn last_epoch(&self) -> Option<Epoch> {
+        self.map.last_key_value().map(|(k, _)| k.epoch)
+    }
 }
 
 #[cfg(test)]
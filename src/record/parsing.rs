@@ -3,11 +3,12 @@ use crate::{
     epoch::parse_utc as parse_utc_epoch,
     error::ParsingError,
     grid::GridSpecs,
-    prelude::{Comments, Header, Key, Record, TEC},
+    prelude::{Comments, Header, Key, MissingPolicy, ParseOptions, Record, TEC},
     quantized::Quantized,
 };
 
 use std::{
+    collections::BTreeSet,
     io::{BufRead, BufReader, Read},
     str::FromStr,
 };
@@ -19,12 +20,27 @@ use log::{
     trace,
 };
 
+/// Longitude spacing deviation tolerated between two grid specs (in decimal degrees)
+const SPACING_TOLERANCE: f64 = 1.0E-3;
+
 impl Record {
     /// Parse [Record] by consuming [BufReader] until end of file.
     /// This requires reference to previous [Header] section.
     pub fn parse<R: Read>(
         header: &Header,
         reader: &mut BufReader<R>,
+    ) -> Result<(Self, Comments), ParsingError> {
+        Self::parse_with(header, reader, ParseOptions::default())
+    }
+
+    /// Parse [Record] by consuming [BufReader] until end of file, like [Self::parse],
+    /// using custom [ParseOptions]. The [MissingPolicy] is applied once all maps are parsed,
+    /// so the result does not depend on the order of the TEC, RMS and height maps:
+    /// skipped values are removed along with their RMS and height.
+    pub fn parse_with<R: Read>(
+        header: &Header,
+        reader: &mut BufReader<R>,
+        options: ParseOptions,
     ) -> Result<(Self, Comments), ParsingError> {
         let mut eos = false;
         let mut rms_map = false;
@@ -39,9 +55,15 @@ impl Record {
         let mut long_ptr;
         let mut longitude_exponent = 0i8;
 
+        // first longitude spacing encountered
+        let mut longitude_spacing = Option::<f64>::None;
+
         let mut record = Self::default();
         let mut comments = Comments::default();
 
+        // omitted TEC values, removed once all maps are parsed
+        let mut skipped = BTreeSet::<Key>::new();
+
         let mut line_buf = String::with_capacity(128);
         let mut epoch_buf = String::with_capacity(1024);
 
@@ -61,8 +83,9 @@ impl Record {
             if line_buf.len() > 60 {
                 let (content, marker) = line_buf.split_at(60);
 
-                // COMMENTS are stored as is
-                if marker.contains("COMMENTS") {
+                // Record section comments are stored as is,
+                // wherever they appear (including within a map)
+                if marker.contains("COMMENT") {
                     skip = true;
                     let comment = line_buf.split_at(60).0.trim_end();
                     comments.push(comment.to_string());
@@ -116,6 +139,20 @@ impl Record {
 
                     match GridSpecs::from_str(content) {
                         Ok(specs) => {
+                            let spacing = specs.longitude_space.spacing;
+
+                            // grid accuracy must not change between regions or epochs
+                            match longitude_spacing {
+                                Some(first) => {
+                                    if (spacing - first).abs() > SPACING_TOLERANCE {
+                                        return Err(ParsingError::InconsistentGridSpacing(
+                                            first, spacing,
+                                        ));
+                                    }
+                                },
+                                None => longitude_spacing = Some(spacing),
+                            }
+
                             next_grid_specs = specs;
                             grid_specs_updated = true;
                         },
@@ -142,9 +179,43 @@ impl Record {
                             break;
                         }
 
+                        let (lat, long, alt) = (
+                            Quantized::new(grid_specs.latitude_ddeg, latitude_exponent),
+                            Quantized::new(long_ptr, longitude_exponent),
+                            Quantized::new(grid_specs.altitude_km, altitude_exponent),
+                        );
+
+                        let coordinates = QuantizedCoordinates::from_quantized(lat, long, alt);
+
+                        let key = Key { epoch, coordinates };
+
                         // omitted data
                         if item.eq("9999") {
-                            // skip parsing
+                            // RMS and height maps only complete existing values.
+                            // An RMS or height map that came first leaves a missing placeholder,
+                            // skipped values are removed once all maps are parsed.
+                            if !rms_map && !height_map {
+                                let placeholder = record.get(&key).map(|tec| tec.is_missing());
+
+                                match (options.missing_policy, placeholder) {
+                                    (MissingPolicy::Skip, _) => {
+                                        skipped.insert(key);
+                                    },
+                                    (MissingPolicy::InsertMissing, None) => {
+                                        record.insert(key, TEC::missing());
+                                    },
+                                    (MissingPolicy::InsertNaN, Some(true)) => {
+                                        if let Some(tec) = record.get_mut(&key) {
+                                            *tec = tec.with_tecu(f64::NAN);
+                                        }
+                                    },
+                                    (MissingPolicy::InsertNaN, None) => {
+                                        record.insert(key, TEC::from_tecu(f64::NAN));
+                                    },
+                                    _ => {},
+                                }
+                            }
+
                             long_ptr += grid_specs.longitude_space.spacing;
                             continue;
                         }
@@ -152,34 +223,28 @@ impl Record {
                         // parsing
                         match item.parse::<i64>() {
                             Ok(value) => {
-                                let (lat, long, alt) = (
-                                    Quantized::new(grid_specs.latitude_ddeg, latitude_exponent),
-                                    Quantized::new(long_ptr, longitude_exponent),
-                                    Quantized::new(grid_specs.altitude_km, altitude_exponent),
-                                );
-
-                                let coordinates =
-                                    QuantizedCoordinates::from_quantized(lat, long, alt);
-
-                                let key = Key { epoch, coordinates };
-
                                 if rms_map {
                                     if let Some(tec) = record.get_mut(&key) {
                                         tec.set_quantized_root_mean_square(value, exponent);
                                     } else {
-                                        let mut tec = TEC::default();
+                                        let mut tec = TEC::missing();
                                         tec.set_quantized_root_mean_square(value, exponent);
                                         record.insert(key, tec);
                                     }
                                 } else if height_map {
-                                    // TODO: Height map not supported.
-                                } else {
                                     if let Some(tec) = record.get_mut(&key) {
-                                        *tec = tec.with_tecu(value as f64);
+                                        tec.set_quantized_height(value, exponent);
                                     } else {
-                                        let tec = TEC::from_quantized(value, exponent);
+                                        let mut tec = TEC::missing();
+                                        tec.set_quantized_height(value, exponent);
                                         record.insert(key, tec);
                                     }
+                                } else if let Some(tec) = record.get_mut(&key) {
+                                    // completes the RMS or height map that came first
+                                    tec.set_quantized_tecu(value, exponent);
+                                } else {
+                                    let tec = TEC::from_quantized(value, exponent);
+                                    record.insert(key, tec);
                                 }
                             },
                             Err(e) => {
@@ -234,6 +299,10 @@ impl Record {
             }
         }
 
+        if !skipped.is_empty() {
+            record.map.retain(|k, _| !skipped.contains(k));
+        }
+
         Ok((record, comments))
     }
 }
@@ -1,6 +1,39 @@
 use crate::prelude::Record;
 use gnss_qc_traits::{Merge, MergeError};
 
+#[cfg(doc)]
+use crate::prelude::TEC;
+
+/// [MergeStrategy] describes how coincident [TEC] values are combined,
+/// when merging two [Record]s, see [Record::merge_with_strategy].
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum MergeStrategy {
+    /// Coincident [TEC] values of the left-hand side are preserved,
+    /// only completed by the right-hand side RMS or height when they are missing.
+    #[default]
+    KeepLeft,
+
+    /// Coincident [TEC] values are averaged and their RMS are combined in quadrature,
+    /// see [Record::blend_mut].
+    Average,
+}
+
+impl Record {
+    /// Merges the right-hand side [Record] into ourselves, combining coincident [TEC] values
+    /// following this [MergeStrategy]. Values that only exist in `rhs` are inserted as is.
+    /// [MergeStrategy::KeepLeft] is equivalent to [Merge::merge_mut].
+    pub fn merge_with_strategy(
+        &mut self,
+        rhs: &Self,
+        strategy: MergeStrategy,
+    ) -> Result<(), MergeError> {
+        match strategy {
+            MergeStrategy::KeepLeft => self.merge_mut(rhs),
+            MergeStrategy::Average => self.blend_mut(rhs, 0.5).map_err(|_| MergeError::Other),
+        }
+    }
+}
+
 impl Merge for Record {
     fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
         let mut s = self.clone();
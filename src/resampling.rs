@@ -0,0 +1,188 @@
+use crate::{
+    coordinates::QuantizedCoordinates,
+    error::Error,
+    grid::Grid,
+    interpolation::bilinear_nodes,
+    linspace_node_index,
+    prelude::{IONEX, Key, Record, TEC},
+};
+
+use geo::Point;
+
+/// Bilinear contributions of the source grid, to one target node.
+#[derive(Debug, Clone, PartialEq)]
+struct ResampledNode {
+    /// Target node coordinates
+    coordinates: QuantizedCoordinates,
+
+    /// (index in the source nodes, bilinear weight) pairs
+    contributions: Vec<(usize, f64)>,
+}
+
+/// [GridResampler] re-grids 2D maps from a source [Grid] to a target [Grid],
+/// using bilinear interpolation. On regular grids, the surrounding source nodes
+/// and bilinear weights of each target node are identical for every map:
+/// they are computed once by [GridResampler::new], then applied to as many
+/// maps as needed, see [GridResampler::apply] and [IONEX::resample_with].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridResampler {
+    /// Source [Grid]
+    source: Grid,
+
+    /// Target [Grid]
+    target: Grid,
+
+    /// Source nodes coordinates, latitude major
+    source_nodes: Vec<QuantizedCoordinates>,
+
+    /// Target nodes that lie within the source [Grid]
+    target_nodes: Vec<ResampledNode>,
+}
+
+impl GridResampler {
+    /// Prepares a new [GridResampler] from this source [Grid] to this target [Grid].
+    /// Target nodes that lie outside of the source [Grid] will never be valued.
+    /// When the source longitudes span the whole globe (worldwide maps), longitudes wrap
+    /// around the globe, like [IONEX::interpolate_tec].
+    /// Both [Grid]s must be 2D, see [Grid::is_2d_grid].
+    pub fn new(source: &Grid, target: &Grid) -> Result<Self, Error> {
+        if source.is_3d_grid() || target.is_3d_grid() {
+            return Err(Error::NotFixedAltitude);
+        }
+
+        let (source_altitude_km, target_altitude_km) =
+            (source.altitude.start, target.altitude.start);
+
+        let num_longitudes = source.longitude.length();
+
+        let source_nodes = source
+            .latitude
+            .iter()
+            .flat_map(|lat_ddeg| {
+                source.longitude.iter().map(move |long_ddeg| {
+                    QuantizedCoordinates::from_decimal_degrees(
+                        lat_ddeg,
+                        long_ddeg,
+                        source_altitude_km,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut target_nodes = Vec::new();
+
+        for lat_ddeg in target.latitude.iter() {
+            for long_ddeg in target.longitude.iter() {
+                let point = Point::new(long_ddeg, lat_ddeg);
+
+                let nodes = match bilinear_nodes(&source.latitude, &source.longitude, point) {
+                    Ok(nodes) => nodes,
+                    Err(_) => continue,
+                };
+
+                let contributions = nodes
+                    .into_iter()
+                    .map(|((lat_node, long_node), weight)| {
+                        let index = linspace_node_index(&source.latitude, lat_node)
+                            * num_longitudes
+                            + linspace_node_index(&source.longitude, long_node);
+
+                        (index, weight)
+                    })
+                    .collect();
+
+                target_nodes.push(ResampledNode {
+                    coordinates: QuantizedCoordinates::from_decimal_degrees(
+                        lat_ddeg,
+                        long_ddeg,
+                        target_altitude_km,
+                    ),
+                    contributions,
+                });
+            }
+        }
+
+        Ok(Self {
+            source: *source,
+            target: *target,
+            source_nodes,
+            target_nodes,
+        })
+    }
+
+    /// Returns the source [Grid] of this [GridResampler].
+    pub fn source_grid(&self) -> Grid {
+        self.source
+    }
+
+    /// Returns the target [Grid] of this [GridResampler].
+    pub fn target_grid(&self) -> Grid {
+        self.target
+    }
+
+    /// Re-grids all the maps of this [Record], which must follow the source [Grid].
+    /// Target nodes surrounded by at least one missing source node are missing as well.
+    /// RMS values are interpolated too, when all surrounding source nodes have one.
+    pub fn apply(&self, record: &Record) -> Record {
+        let mut resampled = Record::default();
+
+        let mut source_map = Vec::<Option<TEC>>::with_capacity(self.source_nodes.len());
+
+        for epoch in record.epochs_iter() {
+            source_map.clear();
+
+            source_map.extend(self.source_nodes.iter().map(|coordinates| {
+                record
+                    .get(&Key {
+                        epoch,
+                        coordinates: *coordinates,
+                    })
+                    .copied()
+            }));
+
+            for node in self.target_nodes.iter() {
+                let samples = node
+                    .contributions
+                    .iter()
+                    .map(|(index, weight)| source_map[*index].map(|tec| (tec, *weight)))
+                    .collect::<Option<Vec<_>>>();
+
+                // at least one surrounding source node is missing
+                let samples = match samples {
+                    Some(samples) => samples,
+                    None => continue,
+                };
+
+                let tec = TEC::interpolated(samples);
+
+                resampled.insert(
+                    Key {
+                        epoch,
+                        coordinates: node.coordinates,
+                    },
+                    tec,
+                );
+            }
+        }
+
+        resampled
+    }
+}
+
+impl IONEX {
+    /// Re-grids this [IONEX] using a prepared [GridResampler], whose source [Grid]
+    /// must match this [IONEX] grid (otherwise [Error::GridMismatch] is returned).
+    /// This is much faster than interpolating each map individually,
+    /// because the bilinear weights are shared by all maps.
+    pub fn resample_with(&self, resampler: &GridResampler) -> Result<IONEX, Error> {
+        if !self.header.grid.same_grid_as(&resampler.source) {
+            return Err(Error::GridMismatch(
+                self.header.grid.diff(&resampler.source),
+            ));
+        }
+
+        let mut s = self.with_record(resampler.apply(&self.record));
+        s.header.grid = resampler.target;
+        Ok(s)
+    }
+}
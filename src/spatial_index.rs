@@ -0,0 +1,115 @@
+use geo::Point;
+
+use crate::prelude::{Epoch, IONEX, TEC};
+
+/// [SpatialIndex] is a KD-tree over the populated nodes of one map,
+/// that answers nearest neighbor queries in O(log n) on average.
+/// Unlike grid index arithmetic, holes are correctly handled:
+/// the nearest populated node is always returned.
+/// Distances are measured in the (longitude, latitude) plane, in decimal degrees.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpatialIndex {
+    /// Populated nodes, stored as an implicit KD-tree:
+    /// the median of each slice is the splitting node.
+    nodes: Vec<(Point<f64>, TEC)>,
+}
+
+/// Returns the coordinate used to split the tree at this depth:
+/// longitude (x) first, then alternating with latitude (y).
+fn split_coordinate(point: &Point<f64>, depth: usize) -> f64 {
+    if depth % 2 == 0 { point.x() } else { point.y() }
+}
+
+impl SpatialIndex {
+    /// Builds a new [SpatialIndex] from these (coordinates in decimal degrees, [TEC]) nodes.
+    pub fn new(mut nodes: Vec<(Point<f64>, TEC)>) -> Self {
+        Self::build(&mut nodes, 0);
+        Self { nodes }
+    }
+
+    /// Returns the number of indexed nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if this [SpatialIndex] has no node.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the nearest populated node (coordinates in decimal degrees, [TEC])
+    /// to this point (in decimal degrees), or None if this [SpatialIndex] is empty.
+    pub fn nearest(&self, point: Point<f64>) -> Option<(Point<f64>, TEC)> {
+        let mut best = None;
+        Self::search(&self.nodes, 0, point, &mut best);
+        best.map(|(_, node, tec)| (node, tec))
+    }
+
+    /// Recursively sorts these nodes as an implicit KD-tree.
+    fn build(nodes: &mut [(Point<f64>, TEC)], depth: usize) {
+        if nodes.len() <= 1 {
+            return;
+        }
+
+        let median = nodes.len() / 2;
+
+        nodes.select_nth_unstable_by(median, |(a, _), (b, _)| {
+            split_coordinate(a, depth).total_cmp(&split_coordinate(b, depth))
+        });
+
+        let (lower, upper) = nodes.split_at_mut(median);
+
+        Self::build(lower, depth + 1);
+        Self::build(&mut upper[1..], depth + 1);
+    }
+
+    /// Recursive nearest neighbor search, best is (squared distance, node, [TEC]).
+    fn search(
+        nodes: &[(Point<f64>, TEC)],
+        depth: usize,
+        point: Point<f64>,
+        best: &mut Option<(f64, Point<f64>, TEC)>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let median = nodes.len() / 2;
+        let (node, tec) = nodes[median];
+
+        let distance = (node.x() - point.x()).powi(2) + (node.y() - point.y()).powi(2);
+
+        if best.is_none_or(|(best_distance, _, _)| distance < best_distance) {
+            *best = Some((distance, node, tec));
+        }
+
+        let delta = split_coordinate(&point, depth) - split_coordinate(&node, depth);
+
+        let (near, far) = if delta < 0.0 {
+            (&nodes[..median], &nodes[median + 1..])
+        } else {
+            (&nodes[median + 1..], &nodes[..median])
+        };
+
+        Self::search(near, depth + 1, point, best);
+
+        // the other side may only contain a closer node if the splitting plane is closer
+        if best.is_none_or(|(best_distance, _, _)| delta.powi(2) < best_distance) {
+            Self::search(far, depth + 1, point, best);
+        }
+    }
+}
+
+impl IONEX {
+    /// Builds a [SpatialIndex] over the populated nodes of the map at this [Epoch],
+    /// for efficient nearest neighbor queries on maps with irregular coverage.
+    /// This assumes a 2D map, see [Self::fixed_altitude_km].
+    pub fn build_spatial_index(&self, epoch: Epoch) -> SpatialIndex {
+        SpatialIndex::new(
+            self.record
+                .synchronous_iter_ref(epoch)
+                .map(|(k, v)| (Point::new(k.longitude_ddeg(), k.latitude_ddeg()), *v))
+                .collect(),
+        )
+    }
+}
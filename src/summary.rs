@@ -0,0 +1,165 @@
+use crate::prelude::{Epoch, IONEX, TEC};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [EpochSummary] is the quick look of one map, see [IONEX::epoch_summary].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EpochSummary {
+    /// [Epoch] of this map
+    pub epoch: Epoch,
+
+    /// Minimal TEC value, in TECu, or None if this map has no data
+    pub min: Option<f64>,
+
+    /// Maximal TEC value, in TECu, or None if this map has no data
+    pub max: Option<f64>,
+
+    /// Mean TEC value, in TECu, or None if this map has no data
+    pub mean: Option<f64>,
+
+    /// Fraction of the grid nodes (described by the [Header](crate::prelude::Header))
+    /// that have a TEC value. 1.0 means that this map is complete.
+    pub coverage_fraction: f64,
+}
+
+impl IONEX {
+    /// Returns one [EpochSummary] per map, in chronological order: a compact quick look
+    /// of the TEC range and coverage of each map. Like [Self::synchronous_tec_min],
+    /// [Self::synchronous_tec_max] and [Self::synchronous_tec_mean], missing (non finite)
+    /// values are skipped, and min, max and mean are None if a map has no data.
+    pub fn epoch_summary(&self) -> Vec<EpochSummary> {
+        let num_nodes = self.header.grid.latitude.length()
+            * self.header.grid.longitude.length()
+            * self.header.grid.altitude.length();
+
+        self.epoch_iter()
+            .map(|epoch| {
+                let map_tecu =
+                    || finite_tecu(self.record.synchronous_iter_ref(epoch).map(|(_, v)| v));
+
+                let coverage_fraction = if num_nodes == 0 {
+                    0.0
+                } else {
+                    map_tecu().count() as f64 / num_nodes as f64
+                };
+
+                EpochSummary {
+                    epoch,
+                    min: min(map_tecu()),
+                    max: max(map_tecu()),
+                    mean: mean(map_tecu()),
+                    coverage_fraction,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the minimal TEC value (in TECu) of this entire [IONEX],
+    /// or None if it does not have any data. Missing values are skipped.
+    pub fn tec_min(&self) -> Option<f64> {
+        min(finite_tecu(self.record.iter().map(|(_, v)| v)))
+    }
+
+    /// Returns the maximal TEC value (in TECu) of this entire [IONEX],
+    /// or None if it does not have any data. Missing values are skipped.
+    pub fn tec_max(&self) -> Option<f64> {
+        max(finite_tecu(self.record.iter().map(|(_, v)| v)))
+    }
+
+    /// Returns the mean TEC value (in TECu) of this entire [IONEX],
+    /// or None if it does not have any data. Missing values are skipped.
+    pub fn tec_mean(&self) -> Option<f64> {
+        mean(finite_tecu(self.record.iter().map(|(_, v)| v)))
+    }
+
+    /// Returns the standard deviation of the TEC (in TECu) of this entire [IONEX],
+    /// or None if it has less than two values. Missing values are skipped.
+    /// This is the sample standard deviation (Bessel correction, divided by N-1).
+    pub fn tec_std_dev(&self) -> Option<f64> {
+        std_dev(finite_tecu(self.record.iter().map(|(_, v)| v)))
+    }
+
+    /// Returns the minimal TEC value (in TECu) of the map at this [Epoch],
+    /// or None if it does not have any data. Missing values are skipped.
+    pub fn synchronous_tec_min(&self, epoch: Epoch) -> Option<f64> {
+        min(finite_tecu(
+            self.record.synchronous_iter_ref(epoch).map(|(_, v)| v),
+        ))
+    }
+
+    /// Returns the maximal TEC value (in TECu) of the map at this [Epoch],
+    /// or None if it does not have any data. Missing values are skipped.
+    pub fn synchronous_tec_max(&self, epoch: Epoch) -> Option<f64> {
+        max(finite_tecu(
+            self.record.synchronous_iter_ref(epoch).map(|(_, v)| v),
+        ))
+    }
+
+    /// Returns the mean TEC value (in TECu) of the map at this [Epoch],
+    /// or None if it does not have any data. Missing values are skipped.
+    pub fn synchronous_tec_mean(&self, epoch: Epoch) -> Option<f64> {
+        mean(finite_tecu(
+            self.record.synchronous_iter_ref(epoch).map(|(_, v)| v),
+        ))
+    }
+
+    /// Returns the standard deviation of the TEC (in TECu) of the map at this [Epoch],
+    /// or None if it has less than two values. Missing values are skipped.
+    /// This is the sample standard deviation (Bessel correction, divided by N-1).
+    pub fn synchronous_tec_std_dev(&self, epoch: Epoch) -> Option<f64> {
+        std_dev(finite_tecu(
+            self.record.synchronous_iter_ref(epoch).map(|(_, v)| v),
+        ))
+    }
+}
+
+/// TEC values (in TECu) of these [TEC]s, skipping missing (non finite) values.
+/// The RMS component is ignored.
+pub(crate) fn finite_tecu<'a, I: Iterator<Item = &'a TEC>>(tec: I) -> impl Iterator<Item = f64> {
+    tec.map(|tec| tec.tecu()).filter(|tecu| tecu.is_finite())
+}
+
+fn min<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    values.min_by(|a, b| a.total_cmp(b))
+}
+
+fn max<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    values.max_by(|a, b| a.total_cmp(b))
+}
+
+fn mean<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
+
+fn std_dev<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    variance(values).map(f64::sqrt)
+}
+
+/// Sample variance (Bessel correction, divided by N-1) of these values,
+/// or None if there are less than two values.
+pub(crate) fn variance<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    let values = values.collect::<Vec<_>>();
+
+    if values.len() < 2 {
+        return None;
+    }
+
+    let num_samples = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / num_samples;
+
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (num_samples - 1.0);
+
+    Some(variance)
+}
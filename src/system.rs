@@ -61,7 +61,12 @@ impl std::str::FromStr for OtherSystem {
 
 impl std::fmt::Display for OtherSystem {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(&self.to_string())
+        match self {
+            Self::BENt => write!(f, "BEN"),
+            Self::ENVisat => write!(f, "ENV"),
+            Self::ERS => write!(f, "ERS"),
+            Self::IRI => write!(f, "IRI"),
+        }
     }
 }
 
@@ -98,7 +103,11 @@ impl std::str::FromStr for TheoreticalModel {
 
 impl std::fmt::Display for TheoreticalModel {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(&self.to_string())
+        match self {
+            Self::MIX => write!(f, "MIX"),
+            Self::NNS => write!(f, "NNS"),
+            Self::TOP => write!(f, "TOP"),
+        }
     }
 }
 
@@ -109,9 +118,12 @@ impl Default for ReferenceSystem {
 }
 
 impl std::fmt::Display for ReferenceSystem {
+    /// Formats this [ReferenceSystem] as the marker found in the
+    /// `IONEX VERSION / TYPE` header line. [Constellation::Mixed] is formatted as "GNSS".
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            Self::Constellation(c) => c.fmt(f),
+            Self::Constellation(Constellation::Mixed) => write!(f, "GNSS"),
+            Self::Constellation(c) => write!(f, "{}", c.to_string().to_uppercase()),
             Self::Other(other) => other.fmt(f),
             Self::Model(m) => m.fmt(f),
         }
@@ -122,9 +134,11 @@ impl FromStr for ReferenceSystem {
     type Err = ParsingError;
 
     fn from_str(system: &str) -> Result<Self, Self::Err> {
+        let system = system.trim();
+
         if let Ok(gnss) = Constellation::from_str(system) {
             Ok(Self::Constellation(gnss))
-        } else if system.eq("GNSS") {
+        } else if system.eq("GNSS") || system.eq("GNS") {
             Ok(Self::Constellation(Constellation::Mixed))
         } else if let Ok(other) = OtherSystem::from_str(system) {
             Ok(Self::Other(other))
@@ -146,4 +160,27 @@ mod test {
             ReferenceSystem::Constellation(Default::default())
         );
     }
+
+    #[test]
+    fn reference_system_parsing() {
+        for (content, expected) in [
+            ("GPS", ReferenceSystem::Constellation(Constellation::GPS)),
+            (
+                "GLONASS",
+                ReferenceSystem::Constellation(Constellation::Glonass),
+            ),
+            ("GNSS", ReferenceSystem::Constellation(Constellation::Mixed)),
+            ("IRI", ReferenceSystem::Other(OtherSystem::IRI)),
+            ("ENV", ReferenceSystem::Other(OtherSystem::ENVisat)),
+            ("TOP", ReferenceSystem::Model(TheoreticalModel::TOP)),
+            ("NNS", ReferenceSystem::Model(TheoreticalModel::NNS)),
+        ] {
+            let parsed = ReferenceSystem::from_str(content).unwrap_or_else(|e| {
+                panic!("Failed to parse reference system \"{}\": {}", content, e);
+            });
+
+            assert_eq!(parsed, expected);
+            assert_eq!(parsed.to_string(), content);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::quantized::Quantized;
+use crate::{mapf::MappingFunction, quantized::Quantized};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -67,6 +67,21 @@ impl TEC {
         }
     }
 
+    /// Builds an explicit missing [TEC], whose value is NaN.
+    /// Missing values are usually not stored, see [MissingPolicy](crate::prelude::MissingPolicy).
+    pub fn missing() -> Self {
+        Self {
+            rms: None,
+            height: None,
+            tecu: Quantized::missing(),
+        }
+    }
+
+    /// Returns true if this is an explicit missing [TEC], see [Self::missing].
+    pub fn is_missing(&self) -> bool {
+        self.tecu.is_missing()
+    }
+
     /// Updates this [TEC] with new TECu value
     pub fn with_tecu(mut self, tecu: f64) -> Self {
         self.tecu = Quantized::auto_scaled(tecu);
@@ -117,6 +132,90 @@ impl TEC {
         });
     }
 
+    /// Updates [TEC] value, from its quantization in TECu.
+    pub(crate) fn set_quantized_tecu(&mut self, tecu: i64, exponent: i8) {
+        self.tecu = Quantized {
+            exponent: -exponent,
+            value: tecu,
+        };
+    }
+
+    /// Updates [TEC] altitude offset (complex 3D height maps)
+    pub(crate) fn set_quantized_height(&mut self, height: i64, exponent: i8) {
+        self.height = Some(Quantized {
+            exponent: -exponent,
+            value: height,
+        });
+    }
+
+    /// Interpolates these ([TEC], weight) samples, as the weighted sum of their TECu values.
+    /// The RMS is interpolated using the same weights, when all samples have one.
+    pub(crate) fn interpolated<I: IntoIterator<Item = (TEC, f64)>>(samples: I) -> Self {
+        let (mut tecu, mut rms) = (0.0, Some(0.0));
+
+        for (tec, weight) in samples {
+            tecu += weight * tec.tecu();
+
+            rms = rms
+                .zip(tec.root_mean_square())
+                .map(|(rms, sample_rms)| rms + weight * sample_rms);
+        }
+
+        let tec = TEC::from_tecu(tecu);
+
+        match rms {
+            Some(rms) => tec.with_rms(rms),
+            None => tec,
+        }
+    }
+
+    /// Returns TEC quantized with IONEX exponent (scaling), as formatted in TEC maps.
+    pub(crate) fn quantized_tecu(&self, exponent: i8) -> i64 {
+        Quantized::new(self.tecu(), -exponent).value
+    }
+
+    /// Returns TEC RMS quantized with IONEX exponent (scaling), as formatted in RMS maps.
+    pub(crate) fn quantized_root_mean_square(&self, exponent: i8) -> Option<i64> {
+        let rms = self.root_mean_square().filter(|rms| rms.is_finite())?;
+        Some(Quantized::new(rms, -exponent).value)
+    }
+
+    /// Converts this vertical [TEC] into the slant [TEC] along a line of sight
+    /// at this elevation angle (in radians), using this [MappingFunction],
+    /// see [MappingFunction::evaluate]. IONEX maps describe vertical TEC:
+    /// this assumes self is a vertical TEC. The RMS is scaled by the same obliquity factor,
+    /// explicit missing values remain missing.
+    pub fn to_slant(&self, mapf: &MappingFunction, elevation_rad: f64) -> TEC {
+        if self.is_missing() {
+            return *self;
+        }
+
+        let factor = mapf.evaluate(elevation_rad);
+
+        let slant = self.with_tecu(self.tecu() * factor);
+
+        match self.root_mean_square() {
+            Some(rms) => slant.with_rms(rms * factor),
+            None => slant,
+        }
+    }
+
+    /// Returns altitude offset quantized with IONEX exponent (scaling), as formatted in height maps.
+    pub(crate) fn quantized_height(&self, exponent: i8) -> Option<i64> {
+        let height = self.height_km().filter(|height| height.is_finite())?;
+        Some(Quantized::new(height, -exponent).value)
+    }
+
+    /// Re-quantizes this [TEC] (and its RMS) using IONEX exponent (scaling).
+    /// The physical values are preserved, within the new quantization precision.
+    pub(crate) fn requantize_mut(&mut self, exponent: i8) {
+        self.tecu = Quantized::new(self.tecu(), -exponent);
+
+        if let Some(rms) = self.root_mean_square() {
+            self.rms = Some(Quantized::new(rms, -exponent));
+        }
+    }
+
     /// Returns Total Electron Content estimate, in TECu (=10^-16 m-2)
     pub fn tecu(&self) -> f64 {
         self.tecu.real_value()
@@ -132,11 +231,18 @@ impl TEC {
         let rms = self.rms?;
         Some(rms.real_value())
     }
+
+    /// Returns the altitude offset (in kilometers) described by complex 3D height maps
+    /// (if determined).
+    pub fn height_km(&self) -> Option<f64> {
+        let height = self.height?;
+        Some(height.real_value())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::TEC;
+    use super::{MappingFunction, TEC};
 
     #[test]
     fn quantized_tec() {
@@ -179,4 +285,22 @@ mod test {
         tec /= 2.0;
         assert_eq!(tec.tecu(), 4.5);
     }
+
+    #[test]
+    fn slant_tec() {
+        let vertical = TEC::from_tecu(10.0).with_rms(1.0);
+
+        for (elevation_deg, obliquity) in [(30.0_f64, 2.0), (45.0, 2.0_f64.sqrt()), (90.0, 1.0)] {
+            let slant = vertical.to_slant(&MappingFunction::CosZ, elevation_deg.to_radians());
+
+            assert!((slant.tecu() - 10.0 * obliquity).abs() < 1.0E-6);
+            assert!((slant.root_mean_square().unwrap() - obliquity).abs() < 1.0E-6);
+        }
+
+        let slant = vertical.to_slant(&MappingFunction::None, 0.1);
+        assert_eq!(slant, vertical);
+
+        let missing = TEC::missing();
+        assert!(missing.to_slant(&MappingFunction::CosZ, 0.5).is_missing());
+    }
 }
@@ -0,0 +1,440 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{
+        Duration, Epoch, FileAttributes, FillPolicy, Key, MaskAction, Point, Rect, Region, TEC,
+        coord,
+    },
+    tests::toolkit::{SYNTHETIC_ALTITUDE_KM, small_ionex, synthetic_ionex},
+};
+
+#[test]
+fn missing_coordinates() {
+    let mut ionex = small_ionex(2, |_, _, _| 5.0);
+
+    let (t0, t1) = (
+        Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap(),
+        Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap(),
+    );
+
+    assert_eq!(ionex.missing_count(), 0);
+    assert!(ionex.missing_coordinates(t0).is_empty());
+
+    // punch one node
+    let key = Key::from_decimal_degrees_km(t1, 5.0, -15.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&key).is_some());
+
+    assert_eq!(ionex.missing_count(), 1);
+    assert!(ionex.missing_coordinates(t0).is_empty());
+    assert_eq!(ionex.missing_coordinates(t1), vec![Point::new(-15.0, 5.0)]);
+}
+
+#[test]
+fn lossy_map_cells() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (-10.0, 10.0, 5.0),
+        (-20.0, 20.0, 5.0),
+        |_, lat, long| 100.0 + lat + long,
+    );
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    assert_eq!(ionex.map_cell_iter().count(), 4 * 8);
+    assert_eq!(ionex.map_cell_iter_lossy().count(), 4 * 8);
+    assert!(ionex.map_cell_iter_lossy().all(|(_, complete)| complete));
+
+    // punch the north eastern corner of the map
+    let key = Key::from_decimal_degrees_km(t0, 10.0, 20.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&key).is_some());
+
+    // strict iterator drops the edge cell
+    assert_eq!(ionex.map_cell_iter().count(), 4 * 8 - 1);
+
+    let cells = ionex.map_cell_iter_lossy().collect::<Vec<_>>();
+    assert_eq!(cells.len(), 4 * 8);
+
+    let incomplete = cells
+        .iter()
+        .filter(|(_, complete)| !complete)
+        .collect::<Vec<_>>();
+
+    assert_eq!(incomplete.len(), 1);
+
+    let (cell, _) = incomplete[0];
+
+    assert_eq!(cell.north_east.point, Point::new(20.0, 10.0));
+    assert_eq!(cell.south_west.point, Point::new(15.0, 5.0));
+
+    // filled with the mean of the other corners
+    let expected = (125.0 + 125.0 + 120.0) / 3.0;
+    assert!((cell.north_east.tec.tecu() - expected).abs() < 1.0E-6);
+}
+
+#[test]
+fn mask_region() {
+    let ionex = small_ionex(2, |_, _, _| 5.0);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    // small box: latitudes {0, 5}, longitudes {-5, 0, 5}
+    let region = Rect::new(coord!(x: -7.0, y: -2.0), coord!(x: 7.0, y: 7.0)).to_polygon();
+
+    let mut masked = ionex.clone();
+    masked.mask_region(&region, MaskAction::SetMissing);
+
+    assert_eq!(masked.missing_count(), 2 * 6);
+    assert_eq!(masked.header.grid, ionex.header.grid);
+
+    // masked nodes are kept, as explicit missing values
+    assert_eq!(masked.record.map.len(), ionex.record.map.len());
+
+    for (lat_ddeg, long_ddeg) in [(0.0, -5.0), (0.0, 0.0), (5.0, 5.0)] {
+        let key = Key::from_decimal_degrees_km(t0, lat_ddeg, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+        assert!(masked.record.get(&key).unwrap().is_missing());
+        assert!(
+            masked
+                .missing_coordinates(t0)
+                .contains(&Point::new(long_ddeg, lat_ddeg))
+        );
+    }
+
+    let key = Key::from_decimal_degrees_km(t0, -5.0, -5.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(masked.record.get(&key).is_some());
+
+    // interior region: grid is preserved, masked nodes are removed
+    let mut removed = ionex.clone();
+    removed.mask_region(&region, MaskAction::Remove);
+    assert_eq!(removed.header.grid, ionex.header.grid);
+    assert_eq!(removed.missing_count(), masked.missing_count());
+    assert_eq!(removed.record.map.len(), ionex.record.map.len() - 2 * 6);
+
+    let key = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(removed.record.get(&key).is_none());
+
+    // northern rows: grid is reduced
+    let region = Rect::new(coord!(x: -30.0, y: 2.0), coord!(x: 30.0, y: 12.0)).to_polygon();
+
+    let mut removed = ionex.clone();
+    removed.mask_region(&region, MaskAction::Remove);
+
+    assert_eq!(removed.header.grid.latitude.start, 0.0);
+    assert_eq!(removed.header.grid.latitude.end, -10.0);
+    assert_eq!(removed.header.grid.latitude.spacing, -5.0);
+    assert_eq!(removed.header.grid.longitude, ionex.header.grid.longitude);
+    assert_eq!(removed.missing_count(), 0);
+}
+
+#[test]
+fn pad_to_grid() {
+    let mut ionex = small_ionex(2, |_, _, _| 5.0);
+
+    let t1 = Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap();
+
+    assert_eq!(ionex.coverage_fraction(), 1.0);
+
+    // ragged corners
+    for (lat_ddeg, long_ddeg) in [(10.0, -20.0), (10.0, -15.0), (-10.0, 20.0)] {
+        let key = Key::from_decimal_degrees_km(t1, lat_ddeg, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+        assert!(ionex.record.map.remove(&key).is_some());
+    }
+
+    assert!((ionex.coverage_fraction() - (90.0 - 3.0) / 90.0).abs() < 1.0E-9);
+
+    for fill in [
+        FillPolicy::Null,
+        FillPolicy::Nearest,
+        FillPolicy::InverseDistance,
+    ] {
+        let mut padded = ionex.clone();
+        padded.pad_to_grid(fill);
+
+        assert_eq!(padded.coverage_fraction(), 1.0);
+        assert_eq!(padded.missing_count(), 0);
+
+        let key = Key::from_decimal_degrees_km(t1, 10.0, -20.0, SYNTHETIC_ALTITUDE_KM);
+        let tecu = padded.record.get(&key).unwrap().tecu();
+
+        let expected = if fill == FillPolicy::Null { 0.0 } else { 5.0 };
+        assert!((tecu - expected).abs() < 1.0E-6, "{:?}: {}", fill, tecu);
+    }
+
+    // explicit missing values: the grid is complete, but nothing is made up
+    let mut padded = ionex.clone();
+    padded.pad_to_grid(FillPolicy::Missing);
+
+    assert_eq!(padded.record.map.len(), 2 * 5 * 9);
+    assert_eq!(padded.missing_count(), 3);
+    assert_eq!(padded.coverage_fraction(), ionex.coverage_fraction());
+
+    for (lat_ddeg, long_ddeg) in [(10.0, -20.0), (10.0, -15.0), (-10.0, 20.0)] {
+        let key = Key::from_decimal_degrees_km(t1, lat_ddeg, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+        assert!(padded.record.get(&key).unwrap().is_missing());
+    }
+
+    // explicit missing values can be padded later on
+    padded.pad_to_grid(FillPolicy::Nearest);
+    assert_eq!(padded.missing_count(), 0);
+}
+
+#[test]
+fn shrink_to_data() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (87.5, -87.5, -2.5),
+        (-180.0, 180.0, 5.0),
+        |_, _, _| 5.0,
+    );
+
+    ionex.attributes = Some(FileAttributes::default());
+
+    assert!(ionex.header.grid.is_worldwide());
+    assert_eq!(ionex.attributes.as_ref().unwrap().region, Region::Worldwide);
+
+    // regional data, worldwide header
+    ionex.record.map.retain(|k, _| {
+        let (lat_ddeg, long_ddeg) = (k.latitude_ddeg(), k.longitude_ddeg());
+        (30.0..=50.0).contains(&lat_ddeg) && (-10.0..=20.0).contains(&long_ddeg)
+    });
+
+    ionex.shrink_to_data();
+
+    assert_eq!(ionex.header.grid.latitude.start, 50.0);
+    assert_eq!(ionex.header.grid.latitude.end, 30.0);
+    assert_eq!(ionex.header.grid.latitude.spacing, -2.5);
+
+    assert_eq!(ionex.header.grid.longitude.start, -10.0);
+    assert_eq!(ionex.header.grid.longitude.end, 20.0);
+    assert_eq!(ionex.header.grid.longitude.spacing, 5.0);
+
+    assert_eq!(ionex.attributes.as_ref().unwrap().region, Region::Regional);
+    assert!(!ionex.is_worldwide_map());
+}
+
+#[test]
+fn temporal_gaps() {
+    let mut ionex = small_ionex(24, |_, _, _| 5.0);
+
+    assert!(ionex.temporal_gaps().is_empty());
+
+    // remove one map
+    let missing = Epoch::from_str("2022-01-01T10:00:00 UTC").unwrap();
+    ionex.record.map.retain(|k, _| k.epoch != missing);
+
+    assert_eq!(
+        ionex.temporal_gaps(),
+        vec![(
+            Epoch::from_str("2022-01-01T09:00:00 UTC").unwrap(),
+            Epoch::from_str("2022-01-01T11:00:00 UTC").unwrap(),
+        )]
+    );
+}
+
+#[test]
+fn strip_trailing_empty() {
+    let mut ionex = small_ionex(4, |_, _, _| 5.0);
+
+    assert!(ionex.header.validate().is_empty());
+
+    // last map is all missing (over-counted), second map as well (interior)
+    let interior = Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap();
+    let trailing = Epoch::from_str("2022-01-01T03:00:00 UTC").unwrap();
+
+    ionex
+        .record
+        .map
+        .retain(|k, _| k.epoch != interior && k.epoch != trailing);
+
+    let mut stripped = ionex.clone();
+    stripped.strip_trailing_empty();
+
+    assert_eq!(stripped.header.number_of_maps, 3);
+    assert_eq!(
+        stripped.header.epoch_of_last_map,
+        Epoch::from_str("2022-01-01T02:00:00 UTC").unwrap()
+    );
+
+    assert!(stripped.header.validate().is_empty());
+    assert_eq!(stripped.record, ionex.record);
+
+    // nothing else to strip
+    let mut twice = stripped.clone();
+    twice.strip_trailing_empty();
+    assert_eq!(twice, stripped);
+}
+
+#[test]
+fn spatial_index() {
+    let mut ionex = small_ionex(1, |_, lat, long| 100.0 + lat + long / 100.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // ragged coverage: a hole in the middle and a missing corner
+    for (lat_ddeg, long_ddeg) in [
+        (0.0, 0.0),
+        (0.0, 5.0),
+        (5.0, 0.0),
+        (5.0, 5.0),
+        (-10.0, -20.0),
+        (-10.0, -15.0),
+        (-5.0, -20.0),
+    ] {
+        let key = Key::from_decimal_degrees_km(t0, lat_ddeg, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+        assert!(ionex.record.map.remove(&key).is_some());
+    }
+
+    let index = ionex.build_spatial_index(t0);
+    assert_eq!(index.len(), 5 * 9 - 7);
+
+    // nearest grid node (0, 0) is missing
+    let (point, tec) = index.nearest(Point::new(1.5, -1.0)).unwrap();
+    assert_eq!(point, Point::new(0.0, -5.0));
+    assert!((tec.tecu() - 95.0).abs() < 1.0E-6);
+
+    // within the missing corner
+    let (point, _) = index.nearest(Point::new(-19.0, -9.0)).unwrap();
+    assert_eq!(point, Point::new(-15.0, -5.0));
+
+    // outside of the grid
+    let (point, _) = index.nearest(Point::new(30.0, 12.0)).unwrap();
+    assert_eq!(point, Point::new(20.0, 10.0));
+
+    // brute force comparison
+    let nodes = ionex
+        .record
+        .iter()
+        .map(|(k, _)| Point::new(k.longitude_ddeg(), k.latitude_ddeg()))
+        .collect::<Vec<_>>();
+
+    let distance = |a: &Point<f64>, b: &Point<f64>| (a.x() - b.x()).hypot(a.y() - b.y());
+
+    for i in 0..50 {
+        let query = Point::new(-22.0 + i as f64 * 0.93, 11.0 - i as f64 * 0.47);
+
+        let expected = nodes
+            .iter()
+            .map(|node| distance(node, &query))
+            .fold(f64::INFINITY, f64::min);
+
+        let (nearest, _) = index.nearest(query).unwrap();
+        assert_eq!(distance(&nearest, &query), expected, "query {:?}", query);
+    }
+
+    assert!(
+        ionex
+            .build_spatial_index(t0 + Duration::from_hours(1.0))
+            .nearest(Point::new(0.0, 0.0))
+            .is_none()
+    );
+}
+
+#[test]
+fn replace_non_finite_with_missing() {
+    let mut ionex = small_ionex(2, |_, _, _| 5.0);
+
+    assert!(!ionex.has_non_finite());
+    assert_eq!(ionex.replace_non_finite_with_missing(), 0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // explicit missing values are not considered
+    let missing = Key::from_decimal_degrees_km(t0, 10.0, 20.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.insert(missing, TEC::missing());
+
+    assert!(!ionex.has_non_finite());
+    assert_eq!(ionex.replace_non_finite_with_missing(), 0);
+    assert!(ionex.record.get(&missing).unwrap().is_missing());
+
+    let nan = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    let infinite = Key::from_decimal_degrees_km(t0, 5.0, 5.0, SYNTHETIC_ALTITUDE_KM);
+    let nan_rms = Key::from_decimal_degrees_km(t0, -5.0, -5.0, SYNTHETIC_ALTITUDE_KM);
+
+    if let Some(tec) = ionex.record.get_mut(&nan) {
+        *tec = tec.with_tecu(f64::NAN);
+    }
+
+    // degenerate arithmetic
+    if let Some(tec) = ionex.record.get_mut(&infinite) {
+        *tec /= 0.0;
+    }
+
+    if let Some(tec) = ionex.record.get_mut(&nan_rms) {
+        *tec = tec.with_rms(f64::NAN);
+    }
+
+    assert!(ionex.has_non_finite());
+    assert_eq!(ionex.replace_non_finite_with_missing(), 3);
+    assert!(!ionex.has_non_finite());
+
+    assert!(ionex.record.get(&nan).unwrap().is_missing());
+    assert!(ionex.record.get(&infinite).unwrap().is_missing());
+
+    let tec = ionex.record.get(&nan_rms).unwrap();
+    assert_eq!(tec.tecu(), 5.0);
+    assert!(tec.root_mean_square().is_none());
+
+    assert_eq!(ionex.missing_count(), 3);
+    assert_eq!(ionex.replace_non_finite_with_missing(), 0);
+}
+
+#[test]
+fn dense_points() {
+    let mut ionex = small_ionex(2, |_, _, _| 5.0);
+
+    let t1 = Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap();
+
+    // punched map
+    for (lat_ddeg, long_ddeg) in [(10.0, -20.0), (0.0, 0.0), (-10.0, 20.0)] {
+        let key = Key::from_decimal_degrees_km(t1, lat_ddeg, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+        assert!(ionex.record.map.remove(&key).is_some());
+    }
+
+    let missing_count = ionex.missing_count();
+
+    for fill in [
+        FillPolicy::Null,
+        FillPolicy::Nearest,
+        FillPolicy::InverseDistance,
+    ] {
+        let points = ionex.dense_points(t1, fill).collect::<Vec<_>>();
+
+        // every node is yielded, following the grid directions
+        assert_eq!(points.len(), 5 * 9);
+        assert_eq!((points[0].0, points[0].1), (10.0, -20.0));
+        assert_eq!((points[44].0, points[44].1), (-10.0, 20.0));
+
+        for (lat_ddeg, long_ddeg, tecu) in points {
+            let punched =
+                [(10.0, -20.0), (0.0, 0.0), (-10.0, 20.0)].contains(&(lat_ddeg, long_ddeg));
+
+            let expected = if punched && fill == FillPolicy::Null {
+                0.0
+            } else {
+                5.0
+            };
+
+            assert!(
+                (tecu - expected).abs() < 1.0E-6,
+                "{:?}: ({}, {}) {}",
+                fill,
+                lat_ddeg,
+                long_ddeg,
+                tecu
+            );
+        }
+    }
+
+    // explicit missing values
+    for (lat_ddeg, long_ddeg, tecu) in ionex.dense_points(t1, FillPolicy::Missing) {
+        let punched = [(10.0, -20.0), (0.0, 0.0), (-10.0, 20.0)].contains(&(lat_ddeg, long_ddeg));
+        assert_eq!(tecu.is_nan(), punched);
+    }
+
+    // record is not modified
+    assert_eq!(ionex.missing_count(), missing_count);
+    assert_eq!(ionex.missing_count(), 3);
+}
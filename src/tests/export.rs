@@ -0,0 +1,230 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{Duration, Epoch, IONEX, Key},
+    tests::toolkit::{SYNTHETIC_ALTITUDE_KM, format_to_string, small_ionex},
+};
+
+#[test]
+fn epoch_values() {
+    let ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 100.0);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let values = ionex.epoch_values(t0).unwrap();
+
+    assert_eq!(values.len(), 5 * 9);
+
+    // NW corner
+    assert!((values[0] - (10.0 + 1.0 - 0.2)).abs() < 1.0E-6);
+
+    // SE corner
+    assert!((values[5 * 9 - 1] - (10.0 - 1.0 + 0.2)).abs() < 1.0E-6);
+
+    let t = Epoch::from_str("2022-01-01T05:00:00 UTC").unwrap();
+    assert!(ionex.epoch_values(t).is_err());
+}
+
+#[test]
+fn write_to_and_from_reader() {
+    let ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    let mut bytes = Vec::<u8>::new();
+
+    ionex.write_to(&mut bytes).unwrap_or_else(|e| {
+        panic!("Failed to format into memory: {}", e);
+    });
+
+    assert_eq!(
+        String::from_utf8(bytes.clone()).unwrap(),
+        format_to_string(&ionex)
+    );
+
+    let parsed = IONEX::from_reader(bytes.as_slice()).unwrap_or_else(|e| {
+        panic!("Failed to parse from memory: {}", e);
+    });
+
+    assert_eq!(parsed.header.grid, ionex.header.grid);
+    assert_eq!(parsed.record, ionex.record);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn write_gzip_and_from_gzip_reader() {
+    let ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    let mut compressed = Vec::<u8>::new();
+
+    ionex.write_gzip(&mut compressed, 9).unwrap_or_else(|e| {
+        panic!("Failed to compress into memory: {}", e);
+    });
+
+    // gzip magic bytes
+    assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+
+    let parsed = IONEX::from_gzip_reader(compressed.as_slice(), None).unwrap_or_else(|e| {
+        panic!("Failed to decompress from memory: {}", e);
+    });
+
+    assert!(parsed.attributes.is_none());
+    assert_eq!(parsed.header.grid, ionex.header.grid);
+    assert_eq!(parsed.record, ionex.record);
+
+    let parsed = IONEX::from_gzip_reader(compressed.as_slice(), Some("CKMG0020.22I.gz"))
+        .unwrap_or_else(|e| {
+            panic!("Failed to decompress from memory: {}", e);
+        });
+
+    assert!(parsed.attributes.is_some());
+    assert_eq!(parsed.record, ionex.record);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn to_json() {
+    let mut ionex = small_ionex(3, |_, lat, long| 10.0 + lat / 10.0 + long / 100.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // first node of the first map is missing
+    let key = Key::from_decimal_degrees_km(t0, 10.0, -20.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&key).is_some());
+
+    let content = ionex.to_json().unwrap_or_else(|e| {
+        panic!("Failed to dump as JSON: {}", e);
+    });
+
+    let json = serde_json::from_str::<serde_json::Value>(&content).unwrap();
+
+    assert_eq!(json["header"]["number_of_maps"], 3);
+    assert_eq!(json["header"]["file_type"], "GNSS");
+    assert_eq!(json["grid"]["latitude"]["spacing"], -5.0);
+    assert_eq!(json["grid"]["longitude"]["start"], -20.0);
+
+    let maps = json["maps"].as_array().unwrap();
+    assert_eq!(maps.len(), 3);
+
+    for (map, epoch) in maps.iter().zip(ionex.epoch_iter()) {
+        assert_eq!(map["epoch"], epoch.to_string());
+        assert_eq!(map["values"].as_array().unwrap().len(), 5 * 9);
+    }
+
+    // canonical grid order: (10, -20), (10, -15)..
+    assert!(maps[0]["values"][0].is_null());
+    let tecu = |value: &serde_json::Value| value.as_f64().unwrap();
+
+    assert!((tecu(&maps[0]["values"][1]) - 10.85).abs() < 1.0E-6);
+    assert!((tecu(&maps[1]["values"][0]) - 10.8).abs() < 1.0E-6);
+
+    // last node: (-10, 20)
+    assert!((tecu(&maps[2]["values"][44]) - 9.2).abs() < 1.0E-6);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_roundtrip() {
+    let mut ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 100.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    let key = Key::from_decimal_degrees_km(t0, 5.0, 10.0, SYNTHETIC_ALTITUDE_KM);
+    let tec = *ionex.record.get(&key).unwrap();
+    ionex.record.insert(key, tec.with_rms(0.5));
+
+    ionex.comments.push("record comment".to_string());
+
+    let content = serde_json::to_string(&ionex).unwrap_or_else(|e| {
+        panic!("Failed to serialize: {}", e);
+    });
+
+    // record is a list of (key, value) pairs
+    let json = serde_json::from_str::<serde_json::Value>(&content).unwrap();
+    assert_eq!(json["record"].as_array().unwrap().len(), 2 * 5 * 9);
+
+    let parsed = serde_json::from_str::<IONEX>(&content).unwrap_or_else(|e| {
+        panic!("Failed to deserialize: {}", e);
+    });
+
+    assert_eq!(parsed, ionex);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn to_geojson() {
+    let mut ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 100.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    let missing = Key::from_decimal_degrees_km(t0, 10.0, -20.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&missing).is_some());
+
+    let key = Key::from_decimal_degrees_km(t0, 5.0, 10.0, SYNTHETIC_ALTITUDE_KM);
+    let tec = *ionex.record.get(&key).unwrap();
+    ionex.record.insert(key, tec.with_rms(0.5));
+
+    let content = ionex.to_geojson(t0).unwrap_or_else(|e| {
+        panic!("Failed to dump as GeoJSON: {}", e);
+    });
+
+    let json = serde_json::from_str::<serde_json::Value>(&content).unwrap();
+
+    assert_eq!(json["type"], "FeatureCollection");
+
+    let features = json["features"].as_array().unwrap();
+    assert_eq!(features.len(), 5 * 9 - 1);
+
+    for feature in features {
+        assert_eq!(feature["type"], "Feature");
+        assert_eq!(feature["geometry"]["type"], "Point");
+
+        let coordinates = feature["geometry"]["coordinates"].as_array().unwrap();
+        let (long, lat) = (
+            coordinates[0].as_f64().unwrap(),
+            coordinates[1].as_f64().unwrap(),
+        );
+
+        let tecu = feature["properties"]["tecu"].as_f64().unwrap();
+        assert!((tecu - (10.0 + lat / 10.0 + long / 100.0)).abs() < 1.0E-6);
+
+        if lat == 5.0 && long == 10.0 {
+            assert_eq!(feature["properties"]["rms"], 0.5);
+        } else {
+            assert!(feature["properties"].get("rms").is_none());
+        }
+    }
+
+    assert!(matches!(
+        ionex.to_geojson(t0 + Duration::from_minutes(30.0)),
+        Err(crate::prelude::Error::TemporalMismatch)
+    ));
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn from_file_gzip_detection() {
+    let ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    // mislabeled: gzip compressed content, without .gz extension
+    let path = "test-gzip-detection.txt";
+
+    ionex.to_gzip_file(path).unwrap_or_else(|e| {
+        panic!("Failed to dump gzip file: {}", e);
+    });
+
+    let parsed = IONEX::from_file(path).unwrap_or_else(|e| {
+        panic!("Failed to parse gzip compressed file: {}", e);
+    });
+
+    assert_eq!(parsed.header.grid, ionex.header.grid);
+    assert_eq!(parsed.record, ionex.record);
+
+    // repository gzip file
+    let parsed = IONEX::from_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    assert_eq!(parsed.header.version.major, 1);
+    assert!(parsed.attributes.is_some());
+
+    let _ = std::fs::remove_file(path);
+}
@@ -1,4 +1,9 @@
-use crate::prelude::*;
+use std::str::FromStr;
+
+use crate::{
+    prelude::*,
+    tests::toolkit::{small_ionex, synthetic_ionex},
+};
 
 #[test]
 fn filename_conventions() {
@@ -8,3 +13,55 @@ fn filename_conventions() {
         assert_eq!(ionex.generate_standardized_filename(), testfile);
     }
 }
+
+#[test]
+fn split_by_utc_day() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        12,
+        Duration::from_hours(4.0),
+        (10.0, -10.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        |_, _, _| 1.0,
+    );
+
+    ionex.attributes = Some(FileAttributes::from_str("CKMG0010.22I").unwrap());
+    ionex.comments.push("multi day file".to_string());
+
+    let days = ionex.split_by_utc_day();
+    assert_eq!(days.len(), 2);
+
+    for (nth, (day, doy, filename)) in
+        [(&days[0], 1, "CKMG0010.22I"), (&days[1], 2, "CKMG0020.22I")]
+            .iter()
+            .enumerate()
+    {
+        let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap() + nth as f64 * Unit::Day;
+
+        assert_eq!(day.header.number_of_maps, 6);
+        assert_eq!(day.header.epoch_of_first_map, t0);
+        assert_eq!(day.header.epoch_of_last_map, t0 + 20.0 * Unit::Hour);
+        assert_eq!(day.header.grid, ionex.header.grid);
+        assert_eq!(day.comments, ionex.comments);
+        assert_eq!(day.epoch_iter().count(), 6);
+
+        assert_eq!(day.attributes.as_ref().unwrap().doy, *doy);
+        assert_eq!(day.generate_standardized_filename(), *filename);
+    }
+}
+
+#[test]
+fn epoch_to_doy_fraction() {
+    let ionex = small_ionex(1, |_, _, _| 1.0);
+
+    for (epoch, expected) in [
+        ("2022-01-01T00:00:00 UTC", 1.0),
+        ("2022-01-02T12:00:00 UTC", 2.5),
+        ("2022-01-03T06:00:00 UTC", 3.25),
+        ("2022-12-31T18:00:00 UTC", 365.75),
+    ] {
+        let epoch = Epoch::from_str(epoch).unwrap();
+        let doy = ionex.epoch_to_doy_fraction(epoch);
+        assert!((doy - expected).abs() < 1.0E-9, "{} != {}", doy, expected);
+    }
+}
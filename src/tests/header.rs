@@ -0,0 +1,402 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::{
+    fmt_ionex,
+    prelude::{
+        Axis, Constellation, Duration, Epoch, Header, HeaderWarning, IonosphereParameters,
+        Linspace, ParsingError, ReferenceSystem,
+    },
+    system::{OtherSystem, TheoreticalModel},
+    tests::toolkit::{
+        SYNTHETIC_ALTITUDE_KM, format_to_string, parse_str, roundtrip, small_ionex, synthetic_ionex,
+    },
+};
+
+#[test]
+fn base_radius_roundtrip() {
+    let mut ionex = small_ionex(2, |_, _, _| 1.0);
+
+    ionex.header = ionex.header.with_base_radius_km(6378.0);
+
+    let parsed = roundtrip(&ionex);
+
+    assert_eq!(parsed.header.base_radius_km, 6378.0);
+    assert!(parsed.header.validate().is_empty());
+}
+
+#[test]
+fn normalize_base_radius() {
+    let mut ionex = small_ionex(2, |_, lat, _| 10.0 + lat);
+
+    assert_eq!(ionex.header.base_radius_km, 6371.0);
+
+    let shell_radius_km = ionex.header.shell_radius_km();
+    assert_eq!(shell_radius_km, 6371.0 + SYNTHETIC_ALTITUDE_KM);
+
+    let tecu = ionex
+        .record
+        .iter()
+        .map(|(_, v)| v.tecu())
+        .collect::<Vec<_>>();
+
+    ionex.normalize_base_radius(6378.0);
+
+    assert_eq!(ionex.header.base_radius_km, 6378.0);
+    assert_eq!(ionex.header.shell_radius_km(), shell_radius_km);
+    assert_eq!(
+        ionex.fixed_altitude_km().unwrap(),
+        SYNTHETIC_ALTITUDE_KM - 7.0
+    );
+
+    assert!(
+        ionex
+            .record
+            .iter()
+            .all(|(k, _)| k.altitude_km() == SYNTHETIC_ALTITUDE_KM - 7.0)
+    );
+
+    assert_eq!(
+        ionex
+            .record
+            .iter()
+            .map(|(_, v)| v.tecu())
+            .collect::<Vec<_>>(),
+        tecu
+    );
+
+    // still coherent once formatted
+    let parsed = roundtrip(&ionex);
+    assert_eq!(parsed.header.shell_radius_km(), shell_radius_km);
+    assert_eq!(parsed.record, ionex.record);
+}
+
+#[test]
+fn base_radius_validation() {
+    let header = Header::default().with_number_of_maps(1);
+    assert!(header.validate().is_empty());
+
+    for radius_km in [0.0, 637.1, 6299.9, 6400.1, 63710.0] {
+        let header = header.with_base_radius_km(radius_km);
+
+        assert_eq!(
+            header.validate(),
+            vec![HeaderWarning::UnrealisticBaseRadius(radius_km)],
+            "{} km should not be accepted",
+            radius_km
+        );
+    }
+}
+
+#[test]
+fn missing_epoch_of_last_map() {
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        3,
+        Duration::from_hours(2.0),
+        (10.0, -10.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        |_, _, _| 1.0,
+    );
+
+    let content = format_to_string(&ionex)
+        .lines()
+        .filter(|line| !line.contains("EPOCH OF LAST MAP"))
+        .join("\n");
+
+    let parsed = parse_str(&content).unwrap();
+
+    assert_eq!(
+        parsed.header.epoch_of_first_map,
+        Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap()
+    );
+
+    assert_eq!(
+        parsed.header.epoch_of_last_map,
+        Epoch::from_str("2022-01-01T04:00:00 UTC").unwrap()
+    );
+
+    assert_eq!(parsed.header.timeseries().count(), 3);
+}
+
+#[test]
+fn null_sampling_period() {
+    let ionex = small_ionex(2, |_, _, _| 1.0);
+
+    for interval in ["0", "-3600"] {
+        let content = format_to_string(&ionex)
+            .lines()
+            .map(|line| {
+                if line.contains("INTERVAL") {
+                    fmt_ionex(&format!("{:6}", interval), "INTERVAL")
+                } else {
+                    line.to_string()
+                }
+            })
+            .join("\n");
+
+        match parse_str(&content) {
+            Err(ParsingError::InvalidSamplingPeriod) => {},
+            Err(e) => panic!("INTERVAL {}: unexpected error {}", interval, e),
+            Ok(_) => panic!("INTERVAL {} should not be accepted", interval),
+        }
+    }
+}
+
+#[test]
+fn file_type_roundtrip() {
+    let ionex = small_ionex(1, |_, _, _| 1.0);
+
+    assert_eq!(ionex.header.file_type(), "GNSS");
+    assert_eq!(roundtrip(&ionex).header.file_type(), "GNSS");
+
+    let mut ionex = ionex;
+    ionex.header = ionex.header.with_file_type("IRI");
+
+    let parsed = roundtrip(&ionex);
+    assert_eq!(parsed.header.file_type(), "IRI");
+    assert_eq!(
+        parsed.header.reference_system,
+        ReferenceSystem::Other(OtherSystem::IRI)
+    );
+
+    // custom markers are preserved, the reference system is left untouched
+    ionex.header = ionex.header.with_file_type("XYZ");
+
+    let parsed = roundtrip(&ionex);
+    assert_eq!(parsed.header.file_type(), "XYZ");
+    assert_eq!(
+        parsed.header.reference_system,
+        ReferenceSystem::Other(OtherSystem::IRI)
+    );
+
+    // the reference system remains the single source of truth otherwise
+    ionex.header = ionex
+        .header
+        .with_reference_system(ReferenceSystem::default());
+
+    let parsed = roundtrip(&ionex);
+    assert_eq!(
+        parsed.header.file_type(),
+        ReferenceSystem::default().to_string()
+    );
+}
+
+#[test]
+fn reference_system_roundtrip() {
+    let mut ionex = small_ionex(1, |_, _, _| 1.0);
+
+    for (reference_system, marker) in [
+        (
+            ReferenceSystem::Constellation(Constellation::Glonass),
+            "GLONASS",
+        ),
+        (ReferenceSystem::Constellation(Constellation::Mixed), "GNSS"),
+    ] {
+        ionex.header = ionex.header.with_reference_system(reference_system);
+
+        let content = format_to_string(&ionex);
+
+        let version_line = content
+            .lines()
+            .find(|line| line.contains("IONEX VERSION / TYPE"))
+            .unwrap();
+
+        assert_eq!(version_line[40..60].trim(), marker);
+
+        let parsed = roundtrip(&ionex);
+        assert_eq!(parsed.header.reference_system, reference_system);
+        assert_eq!(parsed.header.file_type(), marker);
+    }
+}
+
+#[test]
+fn model_parameters_roundtrip() {
+    let parameters = IonosphereParameters {
+        amplitude_s: 5.0E-9,
+        period_s: 72000.0,
+        phase_rad: 0.5,
+        slant: 1.25,
+    };
+
+    let mut ionex = small_ionex(1, |_, _, _| 1.0);
+
+    // not formatted when not using a theoretical model
+    ionex.header = ionex.header.with_model_parameters(parameters);
+    assert!(roundtrip(&ionex).header.model_parameters.is_none());
+
+    ionex.header = ionex
+        .header
+        .with_reference_system(ReferenceSystem::Model(TheoreticalModel::NNS));
+
+    let parsed = roundtrip(&ionex);
+
+    let parsed = parsed.header.model_parameters.unwrap();
+
+    assert!((parsed.amplitude_s - parameters.amplitude_s).abs() < 1.0E-15);
+    assert!((parsed.period_s - parameters.period_s).abs() < 1.0E-6);
+    assert!((parsed.phase_rad - parameters.phase_rad).abs() < 1.0E-6);
+    assert!((parsed.slant - parameters.slant).abs() < 1.0E-6);
+}
+
+#[test]
+fn fixed_altitude() {
+    let ionex = small_ionex(1, |_, _, _| 1.0);
+
+    assert_eq!(ionex.fixed_altitude_km().unwrap(), 350.0);
+
+    let ionex = ionex.with_header(
+        ionex
+            .header
+            .with_altitude_grid(Linspace::new(450.0, 450.0, 0.0).unwrap()),
+    );
+
+    assert_eq!(ionex.fixed_altitude_km().unwrap(), 450.0);
+
+    // 3D
+    let ionex = ionex.with_header(
+        ionex
+            .header
+            .with_altitude_grid(Linspace::new(100.0, 500.0, 100.0).unwrap()),
+    );
+
+    assert!(ionex.is_3d());
+    assert!(ionex.fixed_altitude_km().is_err());
+}
+
+#[test]
+fn duration_validation() {
+    let mut ionex = small_ionex(25, |_, _, _| 1.0);
+
+    assert_eq!(ionex.duration(), Duration::from_hours(24.0));
+    assert!(ionex.header.validate().is_empty());
+
+    // interval and number of maps disagree with the time frame
+    ionex.header.number_of_maps = 24;
+
+    assert_eq!(
+        ionex.header.validate(),
+        vec![HeaderWarning::InconsistentDuration(
+            Duration::from_hours(24.0),
+            Duration::from_hours(23.0)
+        )]
+    );
+}
+
+#[test]
+fn malformed_header_validation() {
+    let ionex = small_ionex(3, |_, _, _| 1.0);
+
+    assert!(ionex.header.validate().is_empty());
+
+    // no map
+    let mut header = ionex.header.with_number_of_maps(0);
+    header.epoch_of_last_map = header.epoch_of_first_map;
+    assert_eq!(header.validate(), vec![HeaderWarning::NoMaps]);
+
+    // time frame going backwards
+    let header = ionex
+        .header
+        .with_epoch_of_last_map(Epoch::from_str("2021-12-31T22:00:00 UTC").unwrap());
+
+    let warnings = header.validate();
+
+    assert!(warnings.contains(&HeaderWarning::InvalidTimeFrame(
+        Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap(),
+        Epoch::from_str("2021-12-31T22:00:00 UTC").unwrap(),
+    )));
+
+    // null spacing on a multi point axis
+    let header = ionex
+        .header
+        .with_latitude_grid(Linspace::from((10.0, -10.0, 0.0)));
+
+    assert_eq!(
+        header.validate(),
+        vec![HeaderWarning::NullSpacing(Axis::Latitude)]
+    );
+
+    // 3D map with a single altitude point
+    let header = ionex.header.with_map_dimension(3);
+
+    assert_eq!(
+        header.validate(),
+        vec![HeaderWarning::InconsistentMapDimension(3)]
+    );
+
+    // 2D map with several altitude points
+    let header = ionex.header.with_altitude_grid(Linspace::from((
+        SYNTHETIC_ALTITUDE_KM,
+        SYNTHETIC_ALTITUDE_KM + 100.0,
+        50.0,
+    )));
+
+    assert_eq!(
+        header.validate(),
+        vec![HeaderWarning::InconsistentMapDimension(2)]
+    );
+
+    // consistent 3D map
+    let header = header.with_map_dimension(3);
+    assert!(header.validate().is_empty());
+}
+
+#[test]
+fn program_run_by_date() {
+    let mut ionex = small_ionex(1, |_, _, _| 1.0);
+
+    ionex.header = ionex
+        .header
+        .with_program("ionex-rs")
+        .with_run_by("NAV-SOLUTIONS")
+        .with_date("20220101 120000 UTC");
+
+    let content = format_to_string(&ionex);
+
+    let line = content
+        .lines()
+        .find(|line| line.contains("PGM / RUN BY / DATE"))
+        .unwrap();
+
+    assert_eq!(
+        line,
+        fmt_ionex(
+            "ionex-rs            NAV-SOLUTIONS       20220101 120000 UTC",
+            "PGM / RUN BY / DATE"
+        )
+    );
+
+    let parsed = roundtrip(&ionex);
+    assert_eq!(parsed.header.program, Some("ionex-rs".to_string()));
+    assert_eq!(parsed.header.run_by, Some("NAV-SOLUTIONS".to_string()));
+    assert_eq!(parsed.header.date, Some("20220101 120000 UTC".to_string()));
+}
+
+#[test]
+fn elevation_cutoff() {
+    let mut ionex = small_ionex(1, |_, _, _| 1.0);
+
+    ionex.header = ionex.header.with_elevation_cutoff(10.0);
+
+    let content = format_to_string(&ionex);
+
+    assert!(
+        content
+            .lines()
+            .any(|line| line.starts_with("  10.0") && line.ends_with("ELEVATION CUTOFF"))
+    );
+
+    let parsed = roundtrip(&ionex);
+    assert_eq!(parsed.header.elevation_cutoff, 10.0);
+    assert!(parsed.header.validate().is_empty());
+
+    for invalid in [-5.0, 95.0] {
+        let header = ionex.header.with_elevation_cutoff(invalid);
+
+        assert_eq!(
+            header.validate(),
+            vec![HeaderWarning::UnrealisticElevationCutoff(invalid)]
+        );
+    }
+}
@@ -0,0 +1,612 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{
+        Duration, Epoch, Error, Grid, GridResampler, IONEX, Key, Linspace, Point, TEC, Unit,
+    },
+    tests::toolkit::{SYNTHETIC_ALTITUDE_KM, small_ionex, synthetic_ionex},
+};
+
+#[test]
+fn lerp_between() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    // linear in time, so the interpolation is exact
+    let tecu = |t: Epoch, lat: f64, long: f64| {
+        10.0 + (t - t_ref).to_unit(Unit::Hour) + lat / 10.0 + long / 100.0
+    };
+
+    let day1 = small_ionex(24, tecu);
+
+    let day2 = synthetic_ionex(
+        "2022-01-02T00:00:00 UTC",
+        24,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        tecu,
+    );
+
+    // neither file contains this instant
+    let t = Epoch::from_str("2022-01-01T23:30:00 UTC").unwrap();
+
+    let record = IONEX::lerp_between(&day1, &day2, t).unwrap();
+
+    assert_eq!(record.map.len(), 5 * 9);
+
+    for (key, tec) in record.iter() {
+        assert_eq!(key.epoch, t);
+
+        let expected = tecu(t, key.latitude_ddeg(), key.longitude_ddeg());
+        assert!((tec.tecu() - expected).abs() < 1.0E-3);
+    }
+
+    let key = Key::from_decimal_degrees_km(t, 5.0, -10.0, SYNTHETIC_ALTITUDE_KM);
+    assert!((record.get(&key).unwrap().tecu() - (10.0 + 23.5 + 0.5 - 0.1)).abs() < 1.0E-3);
+
+    // outside the boundary
+    let t = Epoch::from_str("2022-01-02T01:00:00 UTC").unwrap();
+    assert!(IONEX::lerp_between(&day1, &day2, t).is_err());
+
+    // grids must match
+    let regional = synthetic_ionex(
+        "2022-01-02T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (5.0, -5.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        tecu,
+    );
+
+    let t = Epoch::from_str("2022-01-01T23:30:00 UTC").unwrap();
+    assert!(IONEX::lerp_between(&day1, &regional, t).is_err());
+}
+
+#[test]
+fn blend_rms_quadrature() {
+    let mut lhs = small_ionex(2, |_, _, _| 10.0);
+
+    let mut rhs = small_ionex(2, |_, _, _| 20.0);
+
+    for (_, tec) in lhs.record.iter_mut() {
+        *tec = tec.with_rms(3.0);
+    }
+
+    for (_, tec) in rhs.record.iter_mut() {
+        *tec = tec.with_rms(4.0);
+    }
+
+    let blended = lhs.blend(&rhs, 0.5).unwrap();
+
+    // sqrt(0.25 * 9 + 0.25 * 16) = 2.5
+    for (_, tec) in blended.record.iter() {
+        assert!((tec.tecu() - 15.0).abs() < 1.0E-6);
+        assert!((tec.root_mean_square().unwrap() - 2.5).abs() < 1.0E-6);
+    }
+
+    let blended = lhs.blend(&rhs, 0.8).unwrap();
+
+    // sqrt(0.64 * 9 + 0.04 * 16) = sqrt(6.4)
+    for (_, tec) in blended.record.iter() {
+        assert!((tec.tecu() - 12.0).abs() < 1.0E-6);
+        assert!((tec.root_mean_square().unwrap() - 6.4_f64.sqrt()).abs() < 1.0E-6);
+    }
+
+    assert!(lhs.blend(&rhs, 1.5).is_err());
+}
+
+#[test]
+fn sample_at() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let ionex = small_ionex(4, |t, lat, long| {
+        10.0 + (t - t_ref).to_unit(Unit::Hour) + lat / 10.0 + long / 100.0
+    });
+
+    let mut queries = Vec::new();
+
+    // unsorted epochs, sampled or not, some outside the time frame
+    for minutes in [150.0, 0.0, 45.0, 180.0, 90.0, 200.0, 10.0, -30.0] {
+        for (lat, long) in [
+            (0.0, 0.0),
+            (2.5, -7.5),
+            (-9.0, 19.0),
+            (10.0, 20.0),
+            (-10.0, -20.0),
+            (11.0, 0.0),
+            (0.0, 25.0),
+        ] {
+            queries.push((t_ref + minutes * Unit::Minute, Point::new(long, lat)));
+        }
+    }
+
+    let batched = ionex.sample_at(&queries);
+
+    assert_eq!(batched.len(), queries.len());
+
+    let mut num_ok = 0;
+
+    for ((epoch, point), batched) in queries.iter().zip(batched.iter()) {
+        let individual = ionex.interpolated_tecu(*epoch, *point);
+
+        match (individual, batched) {
+            (Ok(individual), Ok(batched)) => {
+                num_ok += 1;
+                assert!(
+                    (batched.tecu() - individual).abs() < 1.0E-6,
+                    "batched={} individual={} at {:?} {:?}",
+                    batched.tecu(),
+                    individual,
+                    epoch,
+                    point
+                );
+            },
+            (Err(_), Err(_)) => {},
+            (individual, batched) => {
+                panic!(
+                    "mismatch at {:?} {:?}: individual={:?} batched={:?}",
+                    epoch, point, individual, batched
+                );
+            },
+        }
+    }
+
+    // 6 epochs within the time frame, 5 points within the grid
+    assert_eq!(num_ok, 6 * 5);
+
+    // worldwide map: the 355° -> 0° wraparound step is interpolated too
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (87.5, -87.5, -2.5),
+        (0.0, 355.0, 5.0),
+        |_, lat, long| 20.0 + lat / 10.0 + long.to_radians().cos(),
+    );
+
+    let t = t_ref + 30.0 * Unit::Minute;
+
+    for long in [357.5, 359.0, -1.25] {
+        let point = Point::new(long, 1.0);
+
+        let individual = ionex.interpolated_tecu(t, point).unwrap_or_else(|e| {
+            panic!("failed to interpolate at longitude {}: {}", long, e);
+        });
+
+        let batched = ionex.sample_at(&[(t, point)])[0]
+            .as_ref()
+            .map(|tec| tec.tecu())
+            .unwrap_or_else(|e| {
+                panic!("failed to sample at longitude {}: {}", long, e);
+            });
+
+        assert!((batched - individual).abs() < 1.0E-6);
+    }
+
+    // the resampler wraps around the globe as well
+    let target = Grid {
+        latitude: ionex.header.grid.latitude,
+        longitude: Linspace::from((2.5, 357.5, 5.0)),
+        altitude: ionex.header.grid.altitude,
+    };
+
+    let resampler = GridResampler::new(&ionex.header.grid, &target).unwrap();
+    let resampled = ionex.resample_with(&resampler).unwrap();
+
+    let key = Key::from_decimal_degrees_km(t_ref, 0.0, 357.5, SYNTHETIC_ALTITUDE_KM);
+    let expected = ionex
+        .interpolated_tecu(t_ref, Point::new(357.5, 0.0))
+        .unwrap();
+
+    assert!((resampled.record.get(&key).unwrap().tecu() - expected).abs() < 1.0E-6);
+}
+
+#[test]
+fn grid_resampler() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let mut ionex = small_ionex(3, |t, lat, long| {
+        10.0 + (t - t_ref).to_unit(Unit::Hour) + lat * long / 100.0
+    });
+
+    // finer grid, partly outside of the source grid
+    let target = Grid {
+        latitude: Linspace::from((10.0, -10.0, -2.5)),
+        longitude: Linspace::from((-25.0, 25.0, 2.5)),
+        altitude: Linspace::from((SYNTHETIC_ALTITUDE_KM, SYNTHETIC_ALTITUDE_KM, 0.0)),
+    };
+
+    let resampler = GridResampler::new(&ionex.header.grid, &target).unwrap();
+
+    let resampled = ionex.resample_with(&resampler).unwrap();
+
+    assert_eq!(resampled.header.grid, target);
+
+    // 9 latitudes, 17 longitudes within the source grid
+    assert_eq!(resampled.record.map.len(), 3 * 9 * 17);
+
+    // compare to the naive path
+    for epoch in ionex.epoch_iter() {
+        for lat in target.latitude.iter() {
+            for long in target.longitude.iter() {
+                let key = Key::from_decimal_degrees_km(epoch, lat, long, SYNTHETIC_ALTITUDE_KM);
+
+                match ionex.interpolated_tecu(epoch, Point::new(long, lat)) {
+                    Ok(expected) => {
+                        let tec = resampled.record.get(&key).unwrap_or_else(|| {
+                            panic!("missing resampled node {:?}", key);
+                        });
+
+                        assert!(
+                            (tec.tecu() - expected).abs() < 1.0E-6,
+                            "resampled={} naive={} at {:?}",
+                            tec.tecu(),
+                            expected,
+                            key
+                        );
+                    },
+                    Err(_) => {
+                        assert!(resampled.record.get(&key).is_none());
+                    },
+                }
+            }
+        }
+    }
+
+    // missing source nodes propagate to the surrounding target nodes
+    let t0 = ionex.header.epoch_of_first_map;
+    let removed = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.map.remove(&removed);
+
+    let resampled = ionex.resample_with(&resampler).unwrap();
+
+    for (lat, long, missing) in [
+        (0.0, 0.0, true),
+        (2.5, 2.5, true),
+        (-2.5, -2.5, true),
+        (5.0, 0.0, false),
+        (0.0, 5.0, false),
+    ] {
+        let key = Key::from_decimal_degrees_km(t0, lat, long, SYNTHETIC_ALTITUDE_KM);
+        assert_eq!(resampled.record.get(&key).is_none(), missing);
+    }
+
+    // source grid must match
+    let other = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -2.5),
+        (-20.0, 20.0, 5.0),
+        |_, _, _| 1.0,
+    );
+
+    assert!(other.resample_with(&resampler).is_err());
+}
+
+#[test]
+fn rms_interpolation() {
+    let mut ionex = small_ionex(2, |_, lat, _| 10.0 + lat / 10.0);
+
+    for (k, tec) in ionex.record.iter_mut() {
+        *tec = tec.with_rms(1.0 + k.longitude_ddeg() / 10.0);
+    }
+
+    let target = Grid {
+        latitude: Linspace::from((10.0, -10.0, -2.5)),
+        longitude: Linspace::from((-20.0, 20.0, 2.5)),
+        altitude: ionex.header.grid.altitude,
+    };
+
+    let resampler = GridResampler::new(&ionex.header.grid, &target).unwrap();
+    let resampled = ionex.resample_with(&resampler).unwrap();
+
+    assert!(
+        resampled
+            .record
+            .iter()
+            .all(|(_, tec)| tec.root_mean_square().is_some())
+    );
+
+    // mid-cell point: average of the four corners
+    let t0 = ionex.header.epoch_of_first_map;
+    let key = Key::from_decimal_degrees_km(t0, 2.5, 7.5, SYNTHETIC_ALTITUDE_KM);
+    let tec = resampled.record.get(&key).unwrap();
+
+    assert!((tec.tecu() - 10.25).abs() < 1.0E-6);
+    assert!((tec.root_mean_square().unwrap() - 1.75).abs() < 1.0E-6);
+
+    // batched (spatial + temporal) interpolation
+    let t = t0 + Duration::from_minutes(30.0);
+
+    let tec = ionex.sample_at(&[(t, Point::new(7.5, 2.5))])[0]
+        .as_ref()
+        .cloned()
+        .unwrap();
+
+    assert!((tec.root_mean_square().unwrap() - 1.75).abs() < 1.0E-6);
+
+    // RMS is not made up when one node does not have one
+    let key = Key::from_decimal_degrees_km(t0, 5.0, 10.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.map.insert(key, TEC::from_tecu(10.5));
+
+    let tec = ionex.sample_at(&[(t0, Point::new(7.5, 2.5))])[0]
+        .as_ref()
+        .cloned()
+        .unwrap();
+
+    assert!(tec.root_mean_square().is_none());
+}
+
+#[test]
+fn grid_diff() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let target = Grid {
+        latitude: Linspace::from((87.5, -87.5, -5.0)),
+        longitude: Linspace::from((-180.0, 175.0, 5.0)),
+        altitude: ionex.header.grid.altitude,
+    };
+
+    let resampler = GridResampler::new(&ionex.header.grid, &target).unwrap();
+    let resampled = ionex.resample_with(&resampler).unwrap();
+
+    assert!(ionex.header.grid.diff(&ionex.header.grid).is_empty());
+
+    assert_eq!(
+        ionex.header.grid.diff(&resampled.header.grid),
+        vec![
+            "latitude spacing -2.5 vs -5.0".to_string(),
+            "longitude end 180.0 vs 175.0".to_string(),
+        ]
+    );
+
+    let mut appended = ionex.clone();
+
+    match appended.append_in_time(&resampled) {
+        Err(Error::GridMismatch(diff)) => {
+            assert_eq!(diff, ionex.header.grid.diff(&resampled.header.grid));
+        },
+        other => panic!("expected grid mismatch, got {:?}", other),
+    }
+
+    let error = ionex.blend(&resampled, 0.5).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "grid mismatch: latitude spacing -2.5 vs -5.0, longitude end 180.0 vs 175.0"
+    );
+}
+
+#[test]
+fn unitary_temporal_stretching() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let mut stretched = ionex.clone();
+
+    // bit for bit identical, even when repeated
+    for _ in 0..2 {
+        stretched.temporal_stretching_mut(1.0).unwrap_or_else(|e| {
+            panic!("unexpected error: {}", e);
+        });
+
+        assert_eq!(stretched, ionex);
+    }
+
+    assert!(stretched.temporal_stretching_mut(0.0).is_err());
+    assert!(stretched.temporal_stretching_mut(f64::NAN).is_err());
+}
+
+#[test]
+fn temporal_stretching() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let t0 = ionex.header.epoch_of_first_map;
+    let dt = ionex.header.sampling_period;
+
+    // doubled sampling rate
+    let mut upscaled = ionex.clone();
+
+    upscaled.temporal_stretching_mut(2.0).unwrap_or_else(|e| {
+        panic!("temporal upscaling failed: {}", e);
+    });
+
+    assert_eq!(upscaled.header.sampling_period, dt / 2.0);
+    assert_eq!(upscaled.header.number_of_maps, 2 * 25 - 1);
+    assert_eq!(upscaled.epoch_iter().count(), 2 * 25 - 1);
+    assert_eq!(upscaled.header.epoch_of_first_map, t0);
+    assert_eq!(
+        upscaled.header.epoch_of_last_map,
+        ionex.header.epoch_of_last_map
+    );
+
+    for (k, tec) in ionex.record.iter() {
+        // original maps are preserved
+        assert_eq!(upscaled.record.get(k), Some(tec));
+
+        // new maps lie in between the original maps
+        let key = Key {
+            epoch: k.epoch + dt / 2.0,
+            coordinates: k.coordinates,
+        };
+
+        let next = Key {
+            epoch: k.epoch + dt,
+            coordinates: k.coordinates,
+        };
+
+        if let Some(next) = ionex.record.get(&next) {
+            let interpolated = upscaled.record.get(&key).unwrap_or_else(|| {
+                panic!("missing interpolated value at {:?}", key);
+            });
+
+            let expected = (tec.tecu() + next.tecu()) / 2.0;
+            assert!((interpolated.tecu() - expected).abs() < 1.0E-6);
+        }
+    }
+
+    // back to the original sampling rate
+    upscaled.temporal_stretching_mut(0.5).unwrap_or_else(|e| {
+        panic!("temporal decimation failed: {}", e);
+    });
+
+    assert_eq!(upscaled.header.sampling_period, dt);
+    assert_eq!(upscaled.header.number_of_maps, 25);
+    assert_eq!(upscaled.record, ionex.record);
+
+    // decimation: one map out of four
+    let mut decimated = ionex.clone();
+    decimated.temporal_stretching_mut(0.25).unwrap();
+
+    assert_eq!(decimated.header.number_of_maps, 7);
+    assert_eq!(decimated.epoch_iter().count(), 7);
+
+    assert!(
+        decimated
+            .epoch_iter()
+            .all(|t| (t - t0).to_seconds() % (4.0 * dt.to_seconds()) == 0.0)
+    );
+}
+
+#[test]
+fn decimation() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let t0 = ionex.header.epoch_of_first_map;
+    let dt = ionex.header.sampling_period;
+
+    assert_eq!(ionex.header.number_of_maps, 25);
+
+    // one map out of two
+    let mut decimated = ionex.clone();
+    decimated.decimate_by_epoch(2);
+
+    assert_eq!(decimated.header.number_of_maps, 13);
+    assert_eq!(decimated.epoch_iter().count(), 13);
+    assert_eq!(decimated.header.sampling_period, dt * 2.0);
+    assert_eq!(decimated.header.epoch_of_first_map, t0);
+    assert_eq!(decimated.header.epoch_of_last_map, t0 + dt * 24.0);
+
+    for (k, tec) in decimated.record.iter() {
+        assert_eq!(ionex.record.get(k), Some(tec));
+    }
+
+    // same result by interval
+    let mut by_interval = ionex.clone();
+    by_interval.decimate_by_interval(dt * 2.0);
+
+    assert_eq!(by_interval.header, decimated.header);
+    assert_eq!(by_interval.record, decimated.record);
+
+    // one map out of five
+    let mut decimated = ionex.clone();
+    decimated.decimate_by_interval(dt * 5.0);
+
+    assert_eq!(decimated.header.number_of_maps, 5);
+    assert_eq!(decimated.epoch_iter().count(), 5);
+    assert_eq!(decimated.header.epoch_of_last_map, t0 + dt * 20.0);
+
+    // no effect
+    let mut unchanged = ionex.clone();
+    unchanged.decimate_by_epoch(1);
+    unchanged.decimate_by_interval(dt);
+
+    assert_eq!(unchanged.header, ionex.header);
+    assert_eq!(unchanged.record, ionex.record);
+}
+
+#[test]
+fn extract_time_window() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let t0 = Epoch::from_str("2022-01-02T00:00:00 UTC").unwrap();
+    let t6 = Epoch::from_str("2022-01-02T06:00:00 UTC").unwrap();
+
+    assert_eq!(ionex.header.epoch_of_first_map, t0);
+
+    let extracted = ionex.extract_time_window(t0, t6).unwrap_or_else(|e| {
+        panic!("failed to extract 00:00-06:00: {}", e);
+    });
+
+    assert_eq!(extracted.header.number_of_maps, 7);
+    assert_eq!(extracted.epoch_iter().count(), 7);
+    assert_eq!(extracted.header.epoch_of_first_map, t0);
+    assert_eq!(extracted.header.epoch_of_last_map, t6);
+    assert_eq!(extracted.header.grid, ionex.header.grid);
+
+    for (k, tec) in extracted.record.iter() {
+        assert!(k.epoch >= t0 && k.epoch <= t6);
+        assert_eq!(ionex.record.get(k), Some(tec));
+    }
+
+    // window partially outside the file
+    let extracted = ionex
+        .extract_time_window(
+            Epoch::from_str("2022-01-02T22:30:00 UTC").unwrap(),
+            Epoch::from_str("2022-01-03T12:00:00 UTC").unwrap(),
+        )
+        .unwrap();
+
+    assert_eq!(extracted.header.number_of_maps, 2);
+
+    // no overlap
+    assert!(matches!(
+        ionex.extract_time_window(
+            Epoch::from_str("2022-01-05T00:00:00 UTC").unwrap(),
+            Epoch::from_str("2022-01-05T06:00:00 UTC").unwrap(),
+        ),
+        Err(Error::OutsideTemporalBoundaries)
+    ));
+}
+
+#[test]
+fn interpolate_tec() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let ionex = small_ionex(2, |t, lat, long| {
+        10.0 + lat / 10.0 + long / 100.0 + (t - t_ref).to_unit(Unit::Hour)
+    });
+
+    let expected = |hours: f64, lat: f64, long: f64| 10.0 + lat / 10.0 + long / 100.0 + hours;
+
+    // on grid: exact
+    let key = Key::from_decimal_degrees_km(t_ref, 5.0, 10.0, SYNTHETIC_ALTITUDE_KM);
+
+    let tec = ionex.interpolate_tec(t_ref, 5.0, 10.0).unwrap();
+    assert_eq!(Some(&tec), ionex.record.get(&key));
+
+    // off grid
+    for (lat, long) in [(2.5, 7.5), (-8.0, -13.0), (10.0, 1.0), (0.1, 19.9)] {
+        let tec = ionex.interpolate_tec(t_ref, lat, long).unwrap_or_else(|e| {
+            panic!("failed to interpolate at ({}, {}): {}", lat, long, e);
+        });
+
+        assert!((tec.tecu() - expected(0.0, lat, long)).abs() < 1.0E-6);
+    }
+
+    // in between two maps
+    let t = t_ref + Duration::from_minutes(15.0);
+    let tec = ionex.interpolate_tec(t, 2.5, 7.5).unwrap();
+    assert!((tec.tecu() - expected(0.25, 2.5, 7.5)).abs() < 1.0E-6);
+
+    // boundaries
+    assert!(matches!(
+        ionex.interpolate_tec(t_ref, 12.0, 0.0),
+        Err(Error::OutsideSpatialBoundaries)
+    ));
+
+    assert!(matches!(
+        ionex.interpolate_tec(t_ref + Duration::from_hours(2.0), 0.0, 0.0),
+        Err(Error::OutsideTemporalBoundaries)
+    ));
+}
@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{Epoch, KlobucharModel, Point},
+    tests::toolkit::small_ionex,
+};
+
+/// Nighttime Klobuchar vertical delay (5 ns), expressed in TECu on L1
+const NIGHTTIME_TECU: f64 = 9.2316;
+
+#[test]
+fn nighttime_klobuchar() {
+    let t = Epoch::from_str("2022-01-01T02:00:00 GPST").unwrap();
+
+    let model = KlobucharModel::new([0.0; 4], [0.0; 4]);
+
+    let (ipp, tecu) = model.vertical_tec(t, Point::new(0.0, 0.0), 90.0, 0.0);
+
+    assert!((tecu - NIGHTTIME_TECU).abs() < 1.0E-3);
+    assert!(ipp.x().abs() < 1.0E-6);
+    assert!(ipp.y() > 0.0 && ipp.y() < 0.1);
+}
+
+#[test]
+fn diff_klobuchar() {
+    let ionex = small_ionex(25, |_, _, _| 20.0);
+
+    // nighttime: constant 5 ns delay
+    let t = Epoch::from_str("2022-01-01T02:30:00 UTC").unwrap();
+
+    let diff = ionex
+        .diff_klobuchar([0.0; 4], [0.0; 4], t, Point::new(0.0, 0.0), 90.0, 0.0)
+        .unwrap();
+
+    assert!((diff - (NIGHTTIME_TECU - 20.0)).abs() < 1.0E-3);
+
+    // 14:00 local time at the IPP: peak of the cosine term
+    let alpha = [5.0E-9, 0.0, 0.0, 0.0];
+    let t = Epoch::from_str("2022-01-01T14:00:00 GPST").unwrap();
+
+    let diff = ionex
+        .diff_klobuchar(alpha, [0.0; 4], t, Point::new(0.0, 0.0), 90.0, 0.0)
+        .unwrap();
+
+    assert!((diff - (2.0 * NIGHTTIME_TECU - 20.0)).abs() < 1.0E-3);
+
+    // outside the map
+    assert!(
+        ionex
+            .diff_klobuchar(alpha, [0.0; 4], t, Point::new(50.0, 45.0), 90.0, 0.0)
+            .is_err()
+    );
+}
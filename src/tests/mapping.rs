@@ -0,0 +1,153 @@
+use crate::{
+    prelude::{Key, MappingFunction, MaskAction, Rect, coord},
+    tests::toolkit::{SYNTHETIC_ALTITUDE_KM, small_ionex},
+};
+
+#[test]
+fn slant_map() {
+    let mut ionex = small_ionex(2, |_, lat, long| 20.0 + lat / 10.0 + long / 10.0);
+
+    ionex.header = ionex.header.with_mapping_function(MappingFunction::CosZ);
+
+    // zenith: obliquity is 1.0
+    let slant = ionex.to_slant_map(90.0);
+
+    assert_eq!(slant.record.map.len(), ionex.record.map.len());
+    assert_eq!(slant.header.comments.len(), ionex.header.comments.len() + 1);
+
+    for ((k1, v1), (k2, v2)) in ionex.record.iter().zip(slant.record.iter()) {
+        assert_eq!(k1, k2);
+        assert!((v1.tecu() - v2.tecu()).abs() < 1.0E-6);
+    }
+
+    // 30° elevation
+    let radius_km = ionex.header.base_radius_km as f64;
+    let sin_z = radius_km / (radius_km + SYNTHETIC_ALTITUDE_KM) * 60.0_f64.to_radians().sin();
+    let expected = 1.0 / (1.0 - sin_z * sin_z).sqrt();
+
+    let obliquity = MappingFunction::CosZ.obliquity(30.0, radius_km, SYNTHETIC_ALTITUDE_KM);
+    assert!((obliquity - expected).abs() < 1.0E-9);
+    assert!(obliquity > 1.0 && obliquity < 2.0);
+
+    let slant = ionex.to_slant_map(30.0);
+
+    for ((_, v1), (_, v2)) in ionex.record.iter().zip(slant.record.iter()) {
+        assert!((v1.tecu() * obliquity - v2.tecu()).abs() < 1.0E-3);
+    }
+
+    // no mapping function
+    assert_eq!(MappingFunction::None.obliquity(30.0, radius_km, 350.0), 1.0);
+}
+
+#[test]
+fn convert_mapping_function() {
+    let ionex = small_ionex(2, |_, lat, long| 20.0 + lat / 10.0 + long / 10.0);
+
+    assert_eq!(ionex.header.mapf, MappingFunction::None);
+
+    // 30° elevation, R=6371 km, H=350 km: sin(z') = 6371/6721 * sin(60°)
+    let obliquity = 1.751210157868907;
+
+    let radius_km = ionex.header.base_radius_km as f64;
+    let modeled = MappingFunction::CosZ.obliquity(30.0, radius_km, SYNTHETIC_ALTITUDE_KM);
+    assert!((modeled - obliquity).abs() < 1.0E-9);
+
+    let cosz = ionex.convert_mapping_function(MappingFunction::CosZ, 30.0);
+
+    assert_eq!(cosz.header.mapf, MappingFunction::CosZ);
+    assert_eq!(cosz.header.comments.len(), ionex.header.comments.len() + 1);
+
+    // the slant TEC is preserved: vertical values shrink when the obliquity grows
+    for ((_, v1), (_, v2)) in ionex.record.iter().zip(cosz.record.iter()) {
+        assert!((v1.tecu() / obliquity - v2.tecu()).abs() < 1.0E-6);
+        assert!(v2.tecu() < v1.tecu());
+    }
+
+    // back to the initial convention
+    let none = cosz.convert_mapping_function(MappingFunction::None, 30.0);
+
+    assert_eq!(none.header.mapf, MappingFunction::None);
+
+    for ((k1, v1), (k2, v2)) in ionex.record.iter().zip(none.record.iter()) {
+        assert_eq!(k1, k2);
+        assert!((v1.tecu() - v2.tecu()).abs() < 1.0E-6);
+    }
+
+    // masked nodes remain missing
+    let mut masked = ionex.clone();
+    let region = Rect::new(coord!(x: -2.0, y: -2.0), coord!(x: 2.0, y: 2.0)).to_polygon();
+    masked.mask_region(&region, MaskAction::SetMissing);
+
+    let t0 = ionex.header.epoch_of_first_map;
+    let key = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+
+    let cosz = masked.convert_mapping_function(MappingFunction::CosZ, 30.0);
+
+    assert!(cosz.record.get(&key).unwrap().is_missing());
+    assert_eq!(cosz.missing_count(), masked.missing_count());
+    assert_eq!(cosz.missing_count(), 2);
+}
+
+#[test]
+#[cfg(feature = "nav")]
+fn vtec_correction() {
+    use std::str::FromStr;
+
+    use crate::prelude::{Epoch, Point, SV};
+
+    let mut ionex = small_ionex(2, |_, lat, long| 20.0 + lat / 10.0 + long / 10.0);
+
+    ionex.header = ionex.header.with_mapping_function(MappingFunction::CosZ);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+    let receiver = Point::new(0.0, 0.0);
+
+    let (g01, g02, g03, g04, g05) = (
+        SV::from_str("G01").unwrap(),
+        SV::from_str("G02").unwrap(),
+        SV::from_str("G03").unwrap(),
+        SV::from_str("G04").unwrap(),
+        SV::from_str("G05").unwrap(),
+    );
+
+    let sv_azel = [
+        (g01, 0.0, 90.0),  // zenith
+        (g02, 0.0, 30.0),  // north
+        (g03, 90.0, 30.0), // east
+        (g04, 0.0, -5.0),  // below horizon
+        (g05, 180.0, 5.0), // IPP outside of the map
+    ];
+
+    let corrections = ionex.vtec_correction(t0, &sv_azel, receiver);
+
+    assert_eq!(corrections.len(), 3);
+
+    let radius_km = ionex.header.base_radius_km as f64;
+
+    // earth centered angle at 30° elevation
+    let psi_deg = 60.0
+        - (radius_km / (radius_km + SYNTHETIC_ALTITUDE_KM) * 30.0_f64.to_radians().cos())
+            .asin()
+            .to_degrees();
+
+    let obliquity = MappingFunction::CosZ.obliquity(30.0, radius_km, SYNTHETIC_ALTITUDE_KM);
+
+    for (sv, expected) in [
+        (g01, 20.0),
+        (g02, (20.0 + psi_deg / 10.0) * obliquity),
+        (g03, (20.0 + psi_deg / 10.0) * obliquity),
+    ] {
+        let (_, slant_tecu) = corrections
+            .iter()
+            .find(|(corrected, _)| *corrected == sv)
+            .unwrap_or_else(|| panic!("{} is not corrected", sv));
+
+        assert!(
+            (slant_tecu - expected).abs() < 1.0E-3,
+            "{}: {} vs {}",
+            sv,
+            slant_tecu,
+            expected
+        );
+    }
+}
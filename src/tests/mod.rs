@@ -1,10 +1,20 @@
 pub mod toolkit;
 // pub mod formatting;
 
+mod coverage;
+mod export;
 mod filename;
+mod header;
+mod interpolation;
+mod klobuchar;
+mod mapping;
 mod parsing;
+mod profile;
 mod qc;
+mod quantization;
+mod record;
 mod roi;
+mod statistics;
 // mod stretching;
 
 mod v1;
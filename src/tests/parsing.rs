@@ -1,4 +1,19 @@
-use crate::prelude::IONEX;
+use std::{
+    io::{BufReader, BufWriter},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    prelude::{
+        Duration, Epoch, Error, FileAttributes, IONEX, Key, Linspace, MissingPolicy, ParseOptions,
+        ParsingError, TEC,
+    },
+    tests::toolkit::{
+        SYNTHETIC_ALTITUDE_KM, format_to_string, parse_str, small_ionex, synthetic_ionex,
+    },
+};
 
 #[test]
 fn repo_parsing() {
@@ -28,3 +43,552 @@ fn repo_parsing() {
         }
     }
 }
+
+/// Parses, formats and names this content,
+/// bubbling up all errors as a single [Error].
+fn parse_format_and_name(content: &str, filename: &str) -> Result<Vec<u8>, Error> {
+    let mut reader = BufReader::new(content.as_bytes());
+    let mut ionex = IONEX::parse(&mut reader)?;
+
+    ionex.attributes = Some(FileAttributes::from_str(filename)?);
+
+    let mut writer = BufWriter::new(Vec::new());
+    ionex.format(&mut writer)?;
+
+    Ok(writer.into_inner().unwrap_or_default())
+}
+
+#[test]
+fn error_propagation() {
+    let ionex = synthetic_ionex(
+        "2022-01-02T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        |_, _, _| 1.0,
+    );
+
+    let content = format_to_string(&ionex);
+
+    let bytes = parse_format_and_name(&content, "CKMG0020.22I").unwrap();
+    assert!(!bytes.is_empty());
+
+    match parse_format_and_name(&content, "invalid") {
+        Err(Error::FileAttributes(_)) => {},
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("invalid file name should not be accepted"),
+    }
+
+    let content = content.replace("3600", "   0");
+
+    match parse_format_and_name(&content, "CKMG0020.22I") {
+        Err(Error::Parsing(ParsingError::InvalidSamplingPeriod)) => {},
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("null sampling period should not be accepted"),
+    }
+}
+
+#[test]
+fn from_files() {
+    let paths = [
+        "data/IONEX/V1/CKMG0020.22I.gz",
+        "data/IONEX/V1/CKMG0090.21I.gz",
+        "data/IONEX/V1/NOT_A_FILE.22I",
+    ];
+
+    let (ionex, failures) = IONEX::from_files(&paths).unwrap_or_else(|e| {
+        panic!("Failed to parse batch: {}", e);
+    });
+
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0.to_str(), Some("data/IONEX/V1/NOT_A_FILE.22I"));
+
+    // chronological order
+    assert_eq!(
+        ionex.header.epoch_of_first_map.to_string(),
+        "2021-01-09T00:00:00 UTC"
+    );
+
+    assert_eq!(
+        ionex.header.epoch_of_last_map.to_string(),
+        "2022-01-03T00:00:00 UTC"
+    );
+
+    let ckmg0090 = IONEX::from_gzip_file("data/IONEX/V1/CKMG0090.21I.gz").unwrap();
+    let ckmg0020 = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap();
+
+    assert_eq!(
+        ionex.header.number_of_maps,
+        ckmg0090.header.number_of_maps + ckmg0020.header.number_of_maps
+    );
+
+    assert_eq!(
+        ionex.record.map.len(),
+        ckmg0090.record.map.len() + ckmg0020.record.map.len()
+    );
+
+    // nothing to parse
+    let paths: [&str; 0] = [];
+    assert!(IONEX::from_files(&paths).is_err());
+}
+
+#[test]
+fn missing_policies() {
+    let mut ionex = small_ionex(2, |_, _, _| 5.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // holes
+    let holes = [(10.0, -20.0), (0.0, 0.0), (-10.0, 20.0)]
+        .iter()
+        .map(|(lat, long)| Key::from_decimal_degrees_km(t0, *lat, *long, SYNTHETIC_ALTITUDE_KM))
+        .collect::<Vec<_>>();
+
+    for key in holes.iter() {
+        assert!(ionex.record.map.remove(key).is_some());
+    }
+
+    let content = format_to_string(&ionex);
+
+    let parse = |policy: MissingPolicy| {
+        let mut reader = BufReader::new(content.as_bytes());
+        let options = ParseOptions::default().with_missing_policy(policy);
+
+        IONEX::parse_with(&mut reader, options).unwrap_or_else(|e| {
+            panic!("failed to parse with {:?}: {}", policy, e);
+        })
+    };
+
+    // skipped (default)
+    let skipped = parse(MissingPolicy::Skip);
+    assert_eq!(skipped.record, ionex.record);
+
+    for key in holes.iter() {
+        assert!(skipped.record.get(key).is_none());
+    }
+
+    // explicit missing values
+    let missing = parse(MissingPolicy::InsertMissing);
+    assert_eq!(missing.record.map.len(), 2 * 5 * 9);
+
+    for key in holes.iter() {
+        let tec = missing.record.get(key).unwrap();
+        assert!(tec.is_missing());
+        assert!(tec.tecu().is_nan());
+    }
+
+    // NaN values
+    let nan = parse(MissingPolicy::InsertNaN);
+    assert_eq!(nan.record.map.len(), 2 * 5 * 9);
+
+    for key in holes.iter() {
+        let tec = nan.record.get(key).unwrap();
+        assert!(!tec.is_missing());
+        assert!(tec.tecu().is_nan());
+    }
+
+    // missing and NaN values are equal to themselves
+    for dense in [&missing, &nan] {
+        assert_eq!(*dense, dense.clone());
+        assert_eq!(dense.record, dense.record.clone());
+    }
+
+    // dense records are formatted with the standard missing marker
+    let expected = format_to_string(&skipped);
+
+    for dense in [missing, nan] {
+        assert_eq!(format_to_string(&dense), expected);
+    }
+}
+
+#[test]
+fn strip_trailing_missing_map() {
+    let mut ionex = small_ionex(3, |_, _, _| 5.0);
+
+    let t1 = Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap();
+    let t2 = Epoch::from_str("2022-01-01T02:00:00 UTC").unwrap();
+
+    // over-counted map, formatted as an all-9999 block
+    for (k, tec) in ionex.record.map.iter_mut() {
+        if k.epoch == t2 {
+            *tec = TEC::missing();
+        }
+    }
+
+    let content = format_to_string(&ionex);
+
+    for policy in [MissingPolicy::InsertMissing, MissingPolicy::InsertNaN] {
+        let mut reader = BufReader::new(content.as_bytes());
+        let options = ParseOptions::default().with_missing_policy(policy);
+
+        let mut parsed = IONEX::parse_with(&mut reader, options).unwrap_or_else(|e| {
+            panic!("failed to parse with {:?}: {}", policy, e);
+        });
+
+        assert_eq!(parsed.record.last_epoch(), Some(t2));
+
+        parsed.strip_trailing_empty();
+
+        assert_eq!(parsed.header.number_of_maps, 2);
+        assert_eq!(parsed.header.epoch_of_last_map, t1);
+        assert_eq!(parsed.record.last_epoch(), Some(t1));
+        assert_eq!(parsed.record.map.len(), 2 * 5 * 9);
+        assert!(parsed.header.validate().is_empty());
+    }
+}
+
+#[test]
+fn comment_sections() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (5.0, -5.0, -5.0),
+        (-10.0, 10.0, 5.0),
+        |_, lat, long| 10.0 + lat + long,
+    );
+
+    ionex.header.comments = vec!["header comment".to_string()];
+    ionex.comments = vec!["record comment".to_string()];
+
+    let content = format_to_string(&ionex);
+
+    let position = |pattern: &str| {
+        content
+            .lines()
+            .position(|line| line.ends_with("COMMENT") && line.split_at(60).0.trim() == pattern)
+            .unwrap_or_else(|| panic!("\"{}\" not formatted", pattern))
+    };
+
+    let end_of_header = content
+        .lines()
+        .position(|line| line.ends_with("END OF HEADER"))
+        .unwrap();
+
+    assert!(position("header comment") < end_of_header);
+    assert!(position("record comment") > end_of_header);
+
+    let parsed = parse_str(&content).unwrap();
+
+    assert_eq!(parsed.header.comments, vec!["header comment".to_string()]);
+    assert_eq!(parsed.comments, vec!["record comment".to_string()]);
+    assert_eq!(parsed.record, ionex.record);
+
+    // comments found within a map do not disturb the map
+    let first_map_line = content
+        .lines()
+        .position(|line| line.ends_with("LAT/LON1/LON2/DLON/H"))
+        .unwrap();
+
+    let content = content
+        .lines()
+        .enumerate()
+        .flat_map(|(nth, line)| {
+            let mut lines = vec![line.to_string()];
+            if nth == first_map_line + 1 {
+                lines.push(format!("{:<60}COMMENT", "within map"));
+            }
+            lines
+        })
+        .join("\n");
+
+    let parsed = parse_str(&content).unwrap();
+
+    assert_eq!(parsed.header.comments, vec!["header comment".to_string()]);
+    assert_eq!(
+        parsed.comments,
+        vec!["record comment".to_string(), "within map".to_string()]
+    );
+    assert_eq!(parsed.record, ionex.record);
+}
+
+#[test]
+fn comments_management() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (5.0, -5.0, -5.0),
+        (-10.0, 10.0, 5.0),
+        |_, _, _| 1.0,
+    );
+
+    ionex.header.comments = vec!["header comment".to_string()];
+
+    let mut ionex = ionex.with_comments(vec!["first".to_string()]);
+    ionex.push_comment("second");
+
+    assert_eq!(
+        ionex.comment_iter().collect::<Vec<_>>(),
+        vec!["header comment", "first", "second"]
+    );
+
+    let parsed = parse_str(&format_to_string(&ionex)).unwrap();
+
+    assert_eq!(
+        parsed.comment_iter().collect::<Vec<_>>(),
+        vec!["header comment", "first", "second"]
+    );
+
+    ionex.clear_comments();
+
+    assert!(ionex.comments.is_empty());
+    assert_eq!(
+        ionex.comment_iter().collect::<Vec<_>>(),
+        vec!["header comment"]
+    );
+}
+
+/// Synthetic 3D [IONEX] content, where the height map comes first (or last).
+/// TEC (0.1 TECu) and height (0.1 km) values are deduced from the coordinates.
+fn synthetic_height_maps_content(
+    tec: fn(f64, f64, f64) -> i64,
+    height: fn(f64, f64, f64) -> i64,
+    height_first: bool,
+) -> String {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (5.0, -5.0, -5.0),
+        (-10.0, 10.0, 5.0),
+        |_, _, _| 1.0,
+    );
+
+    ionex.header.grid.altitude = Linspace::from((350.0, 450.0, 100.0));
+
+    let formatted = format_to_string(&ionex);
+
+    let line = |content: &str, marker: &str| format!("{:<60}{}\n", content, marker);
+
+    let mut content = formatted
+        .lines()
+        .take_while_inclusive(|line| !line.ends_with("END OF HEADER"))
+        .map(|line| format!("{}\n", line))
+        .join("");
+
+    let mut maps = [("HEIGHT MAP", height), ("TEC MAP", tec)];
+
+    if !height_first {
+        maps.reverse();
+    }
+
+    for (marker, value) in maps {
+        content.push_str(&line("     1", &format!("START OF {}", marker)));
+
+        content.push_str(&line(
+            "  2022     1     1     0     0     0",
+            "EPOCH OF CURRENT MAP",
+        ));
+
+        for altitude_km in [350.0, 450.0] {
+            for lat_ddeg in [5.0, 0.0, -5.0] {
+                content.push_str(&line(
+                    &format!(
+                        "  {:6.1}{:6.1}{:6.1}{:6.1}{:6.1}",
+                        lat_ddeg, -10.0, 10.0, 5.0, altitude_km
+                    ),
+                    "LAT/LON1/LON2/DLON/H",
+                ));
+
+                for long_ddeg in [-10.0, -5.0, 0.0, 5.0, 10.0] {
+                    content.push_str(&format!("{:5}", value(lat_ddeg, long_ddeg, altitude_km)));
+                }
+
+                content.push('\n');
+            }
+        }
+
+        content.push_str(&line("     1", &format!("END OF {}", marker)));
+    }
+
+    content.push_str(&line("", "END OF FILE"));
+    content
+}
+
+#[test]
+fn height_maps() {
+    let tec = |lat: f64, long: f64, alt: f64| (100.0 + lat + long + alt / 10.0) as i64;
+    let height = |lat: f64, long: f64, alt: f64| (alt / 10.0 + lat - long) as i64;
+
+    let content = synthetic_height_maps_content(tec, height, true);
+
+    let parsed = parse_str(&content).unwrap_or_else(|e| {
+        panic!("failed to parse 3D content: {}", e);
+    });
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    assert!(parsed.is_3d());
+    assert_eq!(parsed.header.exponent(), -1);
+    assert_eq!(parsed.record.map.len(), 2 * 3 * 5);
+
+    for altitude_km in [350.0, 450.0] {
+        for lat_ddeg in [5.0, 0.0, -5.0] {
+            for long_ddeg in [-10.0, -5.0, 0.0, 5.0, 10.0] {
+                let key = Key::from_decimal_degrees_km(t0, lat_ddeg, long_ddeg, altitude_km);
+
+                let value = parsed.record.get(&key).unwrap_or_else(|| {
+                    panic!("missing value at {:?}", key);
+                });
+
+                // height map came first: TEC value completed it
+                let expected = tec(lat_ddeg, long_ddeg, altitude_km) as f64 / 10.0;
+                assert!((value.tecu() - expected).abs() < 1.0E-9, "{:?}", key);
+
+                let expected = height(lat_ddeg, long_ddeg, altitude_km) as f64 / 10.0;
+                let height_km = value.height_km().unwrap();
+                assert!((height_km - expected).abs() < 1.0E-9, "{:?}", key);
+
+                assert!(value.root_mean_square().is_none());
+            }
+        }
+    }
+
+    // volume maps roundtrip
+    let formatted = format_to_string(&parsed);
+
+    assert_eq!(
+        formatted
+            .lines()
+            .filter(|line| line.ends_with("START OF HEIGHT MAP"))
+            .count(),
+        1
+    );
+
+    assert_eq!(
+        formatted
+            .lines()
+            .filter(|line| line.ends_with("LAT/LON1/LON2/DLON/H"))
+            .count(),
+        2 * 2 * 3
+    );
+
+    let reparsed = parse_str(&formatted).unwrap_or_else(|e| {
+        panic!("failed to parse back 3D content: {}", e);
+    });
+
+    assert_eq!(reparsed.record, parsed.record);
+
+    for (key, value) in parsed.record.iter() {
+        assert_eq!(
+            reparsed.record.get(key).unwrap().height_km(),
+            value.height_km()
+        );
+    }
+}
+
+#[test]
+fn inconsistent_grid_spacing() {
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (5.0, -5.0, -5.0),
+        (-10.0, 10.0, 5.0),
+        |_, lat, long| 10.0 + lat + long,
+    );
+
+    let formatted = format_to_string(&ionex);
+
+    // consistent blocks
+    let parsed = parse_str(&formatted).unwrap();
+    assert_eq!(parsed.record, ionex.record);
+
+    // second latitude band uses a finer DLON
+    let second_band = formatted
+        .lines()
+        .positions(|line| line.ends_with("LAT/LON1/LON2/DLON/H"))
+        .nth(1)
+        .unwrap();
+
+    let content = formatted
+        .lines()
+        .enumerate()
+        .map(|(nth, line)| {
+            if nth == second_band {
+                format!(
+                    "{:<60}LAT/LON1/LON2/DLON/H",
+                    format!(
+                        "  {:6.1}{:6.1}{:6.1}{:6.1}{:6.1}",
+                        0.0, -10.0, 10.0, 2.5, SYNTHETIC_ALTITUDE_KM
+                    )
+                )
+            } else {
+                line.to_string()
+            }
+        })
+        .join("\n");
+
+    match parse_str(&content) {
+        Err(ParsingError::InconsistentGridSpacing(first, spacing)) => {
+            assert_eq!(first, 5.0);
+            assert_eq!(spacing, 2.5);
+        },
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("inconsistent DLON should not be accepted"),
+    }
+}
+
+#[test]
+fn height_map_and_missing_tec() {
+    // TEC is missing at (0°, 0°, 350 km), but the height map describes it
+    let tec = |lat: f64, long: f64, alt: f64| {
+        if lat == 0.0 && long == 0.0 && alt == 350.0 {
+            9999
+        } else {
+            (100.0 + lat + long) as i64
+        }
+    };
+
+    let height = |lat: f64, long: f64, alt: f64| (alt / 10.0 + lat - long) as i64;
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+    let hole = Key::from_decimal_degrees_km(t0, 0.0, 0.0, 350.0);
+
+    // the policy does not depend on the map order
+    for height_first in [true, false] {
+        let content = synthetic_height_maps_content(tec, height, height_first);
+
+        let parse = |policy: MissingPolicy| {
+            let mut reader = BufReader::new(content.as_bytes());
+            let options = ParseOptions::default().with_missing_policy(policy);
+
+            IONEX::parse_with(&mut reader, options).unwrap_or_else(|e| {
+                panic!("failed to parse with {:?}: {}", policy, e);
+            })
+        };
+
+        // skipped: no fake value, along with its height
+        let skipped = parse(MissingPolicy::Skip);
+        assert_eq!(skipped.record.map.len(), 2 * 3 * 5 - 1);
+        assert!(skipped.record.get(&hole).is_none());
+
+        // explicit missing value, that preserves the height
+        let missing = parse(MissingPolicy::InsertMissing);
+        assert_eq!(missing.record.map.len(), 2 * 3 * 5);
+
+        let tec = missing.record.get(&hole).unwrap();
+        assert!(tec.is_missing());
+        assert_eq!(tec.height_km(), Some(3.5));
+
+        // NaN value, that preserves the height
+        let nan = parse(MissingPolicy::InsertNaN);
+        assert_eq!(nan.record.map.len(), 2 * 3 * 5);
+
+        let tec = nan.record.get(&hole).unwrap();
+        assert!(!tec.is_missing());
+        assert!(tec.tecu().is_nan());
+        assert_eq!(tec.height_km(), Some(3.5));
+
+        // other nodes are not affected
+        for parsed in [skipped, missing, nan] {
+            for (key, tec) in parsed.record.iter().filter(|(key, _)| **key != hole) {
+                let (lat, long) = (key.latitude_ddeg(), key.longitude_ddeg());
+                assert!((tec.tecu() - (100.0 + lat + long) / 10.0).abs() < 1.0E-9);
+                assert!(tec.height_km().is_some());
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{Duration, Epoch},
+    tests::toolkit::{small_ionex, synthetic_ionex},
+};
+
+#[test]
+fn equatorial_profile() {
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    // equatorial ridge, slowly increasing eastwards
+    let ridge = |_: Epoch, lat: f64, long: f64| 50.0 + long / 10.0 - lat.abs();
+
+    // equator is a grid node
+    let ionex = small_ionex(1, ridge);
+
+    let profile = ionex.equatorial_profile(t0);
+    assert_eq!(profile.len(), 9);
+
+    for (nth, (long_ddeg, tecu)) in profile.iter().enumerate() {
+        assert_eq!(*long_ddeg, -20.0 + nth as f64 * 5.0);
+        assert!((tecu - (50.0 + long_ddeg / 10.0)).abs() < 1.0E-6);
+    }
+
+    // equator is not a grid node
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (7.5, -7.5, -5.0),
+        (-20.0, 20.0, 5.0),
+        ridge,
+    );
+
+    let profile = ionex.equatorial_profile(t0);
+    assert_eq!(profile.len(), 9);
+
+    for (long_ddeg, tecu) in profile.iter() {
+        assert!((tecu - (47.5 + long_ddeg / 10.0)).abs() < 1.0E-6);
+    }
+
+    // epoch is not sampled: both maps are interpolated
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (7.5, -7.5, -5.0),
+        (-20.0, 20.0, 5.0),
+        |t, lat, long| ridge(t, lat, long) + (t - t0).to_seconds() / 360.0,
+    );
+
+    let t = t0 + Duration::from_minutes(30.0);
+
+    let profile = ionex.equatorial_profile(t);
+    assert_eq!(profile.len(), 9);
+
+    for (long_ddeg, tecu) in profile.iter() {
+        let expected = ionex.interpolate_tec(t, 0.0, *long_ddeg).unwrap().tecu();
+        assert_eq!(*tecu, expected);
+        assert!((tecu - (52.5 + long_ddeg / 10.0)).abs() < 1.0E-6);
+    }
+
+    // map does not cross the equator
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (40.0, 20.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        ridge,
+    );
+
+    assert!(ionex.equatorial_profile(t0).is_empty());
+}
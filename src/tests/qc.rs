@@ -1,12 +1,12 @@
 use crate::{
-    prelude::{Duration, IONEX, MappingFunction, Rect, Version, coord},
+    prelude::{Duration, IONEX, Key, MappingFunction, MergeStrategy, Rect, TEC, Version, coord},
     tests::{
         init_logger,
-        toolkit::{TestPoint, generic_comparison, generic_test},
+        toolkit::{TestPoint, generic_comparison, generic_test, small_ionex, synthetic_ionex},
     },
 };
 
-use gnss_qc_traits::Merge;
+use gnss_qc_traits::{Merge, MergeError};
 
 use std::fs::File;
 use std::io::BufWriter;
@@ -101,3 +101,216 @@ fn v1_files_merge() {
     );
     assert!(parsed.is_worldwide_map());
 }
+
+#[test]
+fn merged_comments_dedup() {
+    let mut file_a = small_ionex(2, |_, _, _| 1.0);
+
+    let mut file_b = file_a.clone();
+
+    file_a.header.comments = vec![
+        "first".to_string(),
+        "shared".to_string(),
+        "first".to_string(),
+    ];
+
+    file_a.comments = vec!["shared".to_string()];
+
+    file_b.header.comments = vec!["shared".to_string(), "second".to_string()];
+
+    let mut merged = file_a.merge(&file_b).unwrap_or_else(|e| {
+        panic!("failed to merge both files: {}", e);
+    });
+
+    let mut writer = BufWriter::new(Vec::<u8>::new());
+
+    merged.format(&mut writer).unwrap_or_else(|e| {
+        panic!("failed to format merged file: {}", e);
+    });
+
+    let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+    for comment in ["first", "shared", "second", "FILE MERGE"] {
+        let occurrences = content
+            .lines()
+            .filter(|line| line.ends_with("COMMENT") && line.split_at(60).0.trim() == comment)
+            .count();
+
+        assert_eq!(occurrences, 1, "\"{}\" should be formatted once", comment);
+    }
+
+    merged.dedup_comments();
+
+    assert_eq!(
+        merged.header.comments,
+        vec![
+            "first".to_string(),
+            "shared".to_string(),
+            "second".to_string(),
+            "FILE MERGE".to_string(),
+        ]
+    );
+
+    assert!(merged.comments.is_empty());
+}
+
+#[test]
+fn merge_conflicts() {
+    let coarse = small_ionex(2, |_, _, _| 1.0);
+
+    let fine = synthetic_ionex(
+        "2022-01-01T02:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -2.5),
+        (-20.0, 20.0, 2.5),
+        |_, _, _| 1.0,
+    );
+
+    // 5° and 2.5° grids
+    assert!(matches!(
+        coarse.merge(&fine),
+        Err(MergeError::DimensionMismatch)
+    ));
+
+    assert!(matches!(
+        fine.merge(&coarse),
+        Err(MergeError::DimensionMismatch)
+    ));
+
+    // only the latitude spacing differs
+    let mut other = coarse.clone();
+    other.header.grid.latitude.spacing = -2.5;
+    assert!(coarse.merge(&other).is_err());
+
+    // same spacing, shifted origin: nodes are not aligned
+    let mut other = coarse.clone();
+    other.header.grid.longitude.start = -17.5;
+    other.header.grid.longitude.end = 22.5;
+
+    assert!(matches!(
+        coarse.merge(&other),
+        Err(MergeError::DimensionMismatch)
+    ));
+
+    // same planar grid, other altitude
+    let mut other = coarse.clone();
+    other.header.grid.altitude.start = 450.0;
+    other.header.grid.altitude.end = 450.0;
+
+    assert!(matches!(
+        coarse.merge(&other),
+        Err(MergeError::DimensionMismatch)
+    ));
+
+    // 2D and 3D maps
+    let mut other = coarse.clone();
+    other.header.map_dimension = 3;
+    assert!(coarse.merge(&other).is_err());
+
+    // mapping functions
+    let mut other = coarse.clone();
+    other.header.mapf = MappingFunction::CosZ;
+    assert!(coarse.merge(&other).is_err());
+
+    // failed merge does not modify the header
+    let mut header = coarse.header.clone();
+    assert!(header.merge_mut(&fine.header).is_err());
+    assert_eq!(header, coarse.header);
+
+    // compatible files
+    let mut other = coarse.clone();
+    other.header.epoch_of_first_map = fine.header.epoch_of_first_map;
+    assert!(coarse.merge(&other).is_ok());
+}
+
+#[test]
+fn merge_averaged() {
+    let mut file_a = small_ionex(2, |_, _, _| 10.0);
+
+    // overlapping solution, one map later
+    let mut file_b = synthetic_ionex(
+        "2022-01-01T01:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        |_, _, _| 20.0,
+    );
+
+    for tec in file_a.record.map.values_mut() {
+        *tec = tec.with_rms(1.0);
+    }
+
+    for tec in file_b.record.map.values_mut() {
+        *tec = tec.with_rms(2.0);
+    }
+
+    let t1 = file_b.header.epoch_of_first_map;
+
+    let averaged = file_a.merge_averaged(&file_b).unwrap_or_else(|e| {
+        panic!("failed to merge both files: {}", e);
+    });
+
+    assert!(averaged.is_merged());
+    assert_eq!(averaged.epoch_iter().count(), 3);
+
+    for (k, tec) in averaged.record.iter() {
+        let (tecu, rms) = if k.epoch < t1 {
+            (10.0, 1.0)
+        } else if k.epoch > t1 {
+            (20.0, 2.0)
+        } else {
+            // sqrt((0.5 * 1)² + (0.5 * 2)²)
+            (15.0, 1.25_f64.sqrt())
+        };
+
+        assert!((tec.tecu() - tecu).abs() < 1.0E-6, "{:?}", k);
+        assert!(
+            (tec.root_mean_square().unwrap() - rms).abs() < 1.0E-3,
+            "{:?}",
+            k
+        );
+    }
+
+    // left values are preserved by default
+    let merged = file_a.merge(&file_b).unwrap();
+
+    let key = Key::from_decimal_degrees_km(t1, 0.0, 0.0, file_a.header.grid.altitude.start);
+    assert_eq!(merged.record.get(&key).unwrap().tecu(), 10.0);
+
+    let mut record = file_a.record.clone();
+
+    record
+        .merge_with_strategy(&file_b.record, MergeStrategy::KeepLeft)
+        .unwrap();
+
+    assert_eq!(record, merged.record);
+
+    // coincident missing values do not take part in the average
+    let (lhs_key, rhs_key) = (
+        Key::from_decimal_degrees_km(t1, 5.0, 5.0, file_a.header.grid.altitude.start),
+        Key::from_decimal_degrees_km(t1, -5.0, -5.0, file_a.header.grid.altitude.start),
+    );
+
+    file_a.record.insert(lhs_key, TEC::missing());
+    file_b.record.insert(rhs_key, TEC::from_tecu(f64::NAN));
+
+    // one sided RMS is kept as is: the blend is not more accurate than the known estimate
+    let rms_key = Key::from_decimal_degrees_km(t1, 0.0, 5.0, file_a.header.grid.altitude.start);
+    file_b.record.insert(rms_key, TEC::from_tecu(20.0));
+
+    let averaged = file_a.merge_averaged(&file_b).unwrap();
+
+    let tec = averaged.record.get(&lhs_key).unwrap();
+    assert_eq!(tec.tecu(), 20.0);
+    assert_eq!(tec.root_mean_square(), Some(2.0));
+
+    let tec = averaged.record.get(&rhs_key).unwrap();
+    assert_eq!(tec.tecu(), 10.0);
+    assert_eq!(tec.root_mean_square(), Some(1.0));
+
+    let tec = averaged.record.get(&rms_key).unwrap();
+    assert!((tec.tecu() - 15.0).abs() < 1.0E-6);
+    assert_eq!(tec.root_mean_square(), Some(1.0));
+}
@@ -0,0 +1,101 @@
+use crate::{
+    prelude::{Duration, Linspace, TEC},
+    tests::toolkit::{roundtrip, small_ionex, synthetic_ionex},
+};
+
+#[test]
+fn set_exponent() {
+    let ionex = small_ionex(3, |_, lat, long| 12.3 + (lat + long).abs() / 10.0);
+
+    let mut rescaled = ionex.clone();
+    rescaled.set_exponent(-2);
+
+    assert_eq!(rescaled.header.exponent, -2);
+
+    for (k, tec) in rescaled.record.iter() {
+        let original = ionex.record.get(k).unwrap();
+        assert!((tec.tecu() - original.tecu()).abs() < 1.0E-9);
+        assert_eq!(tec.tecu.exponent, 2);
+    }
+
+    let parsed = roundtrip(&rescaled);
+
+    assert_eq!(parsed.header.exponent, -2);
+
+    for (k, tec) in parsed.record.iter() {
+        let original = ionex.record.get(k).unwrap();
+        assert!(
+            (tec.tecu() - original.tecu()).abs() < 1.0E-9,
+            "{:?}: parsed {} but {} is expected",
+            k,
+            tec.tecu(),
+            original.tecu()
+        );
+    }
+}
+
+#[test]
+fn content_hash() {
+    let ionex = small_ionex(3, |_, lat, long| 10.5 + (lat + long).abs());
+
+    assert_eq!(ionex.content_hash(), ionex.clone().content_hash());
+
+    // physically equal
+    let mut rescaled = ionex.clone();
+    rescaled.set_exponent(-2);
+    assert_eq!(rescaled.content_hash(), ionex.content_hash());
+
+    rescaled.set_exponent(-1);
+    assert_eq!(rescaled.content_hash(), ionex.content_hash());
+
+    // parsed back (quantized with header exponent)
+    assert_eq!(roundtrip(&ionex).content_hash(), ionex.content_hash());
+
+    // physically different
+    let mut modified = ionex.clone();
+
+    for (_, tec) in modified.record.iter_mut().take(1) {
+        *tec = TEC::from_tecu(tec.tecu() + 0.1);
+    }
+
+    assert_ne!(modified.content_hash(), ionex.content_hash());
+}
+
+#[test]
+fn max_quantization_error() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -2.5),
+        (-20.0, 20.0, 5.0),
+        |_, lat, long| 12.34 + (lat + long).abs() / 7.0,
+    );
+
+    let original = ionex.clone();
+
+    ionex.set_exponent(-1);
+    assert!((ionex.max_quantization_error_tecu() - 0.05).abs() < 1.0E-9);
+
+    let parsed = roundtrip(&ionex);
+
+    for (k, tec) in original.record.iter() {
+        let error = (parsed.record.get(k).unwrap().tecu() - tec.tecu()).abs();
+        assert!(error <= ionex.max_quantization_error_tecu() + 1.0E-9);
+    }
+
+    ionex.set_exponent(-3);
+    assert!((ionex.max_quantization_error_tecu() - 0.0005).abs() < 1.0E-12);
+
+    ionex.set_exponent(0);
+    assert_eq!(ionex.max_quantization_error_tecu(), 0.5);
+
+    let (lat, long, alt) = ionex.max_coordinates_quantization_error();
+    assert!((lat - 0.05).abs() < 1.0E-9);
+    assert_eq!(long, 0.5);
+    assert_eq!(alt, 0.5);
+
+    ionex.header.grid.longitude = Linspace::new(-20.0, 20.0, 1.25).unwrap();
+    let (_, long, _) = ionex.max_coordinates_quantization_error();
+    assert!((long - 0.005).abs() < 1.0E-9);
+}
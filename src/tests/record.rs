@@ -0,0 +1,294 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{Epoch, Error, Key, Point, Record, TEC},
+    tests::toolkit::{SYNTHETIC_ALTITUDE_KM, small_ionex},
+};
+
+#[test]
+fn synchronous_iter_ref() {
+    let ionex = small_ionex(3, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    for epoch in ionex.epoch_iter() {
+        let cloned = ionex.record.synchronous_iter(epoch).collect::<Vec<_>>();
+
+        let borrowed = ionex
+            .record
+            .synchronous_iter_ref(epoch)
+            .map(|(k, v)| (*k, *v))
+            .collect::<Vec<_>>();
+
+        assert_eq!(borrowed.len(), 5 * 9);
+        assert_eq!(borrowed, cloned);
+    }
+
+    let t = Epoch::from_str("2022-01-01T00:30:00 UTC").unwrap();
+    assert_eq!(ionex.record.synchronous_iter_ref(t).count(), 0);
+}
+
+#[test]
+fn synchronous_iter_mut() {
+    let mut ionex = small_ionex(3, |_, _, _| 10.0);
+
+    let t1 = Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap();
+
+    for (_, tec) in ionex.record.synchronous_iter_mut(t1) {
+        *tec *= 2.0;
+    }
+
+    for (k, tec) in ionex.record.iter() {
+        let expected = if k.epoch == t1 { 20.0 } else { 10.0 };
+        assert_eq!(tec.tecu(), expected);
+    }
+
+    let filtered = ionex
+        .record
+        .iter()
+        .filter(|(k, _)| k.epoch == t1)
+        .map(|(k, v)| (*k, *v))
+        .collect::<Vec<_>>();
+
+    let ranged = ionex.record.synchronous_iter(t1).collect::<Vec<_>>();
+
+    assert_eq!(filtered.len(), 5 * 9);
+    assert_eq!(ranged, filtered);
+}
+
+#[test]
+fn epoch_records() {
+    let ionex = small_ionex(4, |t, lat, long| {
+        t.to_gpst_seconds() / 3600.0 + lat / 10.0 + long / 10.0
+    });
+
+    let mut concatenated = Record::default();
+    let mut epochs = Vec::new();
+
+    for (epoch, record) in ionex.epoch_records() {
+        assert_eq!(record.map.len(), 5 * 9);
+        assert!(record.iter().all(|(k, _)| k.epoch == epoch));
+
+        for (k, v) in record.iter() {
+            concatenated.insert(*k, *v);
+        }
+
+        epochs.push(epoch);
+    }
+
+    assert_eq!(epochs, ionex.epoch_iter().collect::<Vec<_>>());
+    assert_eq!(concatenated, ionex.record);
+}
+
+#[test]
+fn locate() {
+    let ionex = small_ionex(2, |_, _, _| 1.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // NW corner is the first formatted value
+    assert_eq!(ionex.record.locate(t0, 10.0, -20.0), Some((0, 0)));
+
+    // SE corner is the last formatted value
+    assert_eq!(ionex.record.locate(t0, -10.0, 20.0), Some((4, 8)));
+
+    assert_eq!(ionex.record.locate(t0, 5.0, 0.0), Some((1, 4)));
+
+    // not a grid node
+    assert_eq!(ionex.record.locate(t0, 2.5, 0.0), None);
+
+    // not sampled
+    let t = Epoch::from_str("2022-01-01T00:30:00 UTC").unwrap();
+    assert_eq!(ionex.record.locate(t, 10.0, -20.0), None);
+}
+
+#[test]
+fn rebuild() {
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let mut record = Record::default();
+
+    // logically identical, but quantized differently
+    let noisy_lat_ddeg = -87.5 + 35.0 * 2.5 + 1.0E-9;
+
+    record.insert(
+        Key::from_decimal_degrees_km(t0, 0.0, 5.0, 350.0),
+        TEC::from_tecu(1.0),
+    );
+
+    record.insert(
+        Key::from_decimal_degrees_km(t0, noisy_lat_ddeg, 5.0, 350.0),
+        TEC::from_tecu(2.0),
+    );
+
+    record.insert(
+        Key::from_decimal_degrees_km(t0, 2.5, 5.0, 350.0),
+        TEC::from_tecu(3.0),
+    );
+
+    assert_eq!(record.iter().count(), 3);
+
+    assert_eq!(record.rebuild(), 1);
+    assert_eq!(record.iter().count(), 2);
+
+    // last entry is preserved
+    let key = Key::from_decimal_degrees_km(t0, 0.0, 5.0, 350.0);
+    assert_eq!(record.get(&key).unwrap().tecu(), 2.0);
+
+    let key = Key::from_decimal_degrees_km(t0, 2.5, 5.0, 350.0);
+    assert_eq!(record.get(&key).unwrap().tecu(), 3.0);
+
+    // canonical record is stable
+    assert_eq!(record.rebuild(), 0);
+}
+
+#[test]
+fn insert_checked() {
+    let mut ionex = small_ionex(3, |_, _, _| 1.0);
+
+    let t1 = Epoch::from_str("2022-01-01T01:00:00 UTC").unwrap();
+    let size = ionex.record.map.len();
+
+    // on grid
+    let key = Key::from_decimal_degrees_km(t1, 5.0, -15.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.insert_checked(key, TEC::from_tecu(2.0)).unwrap();
+
+    assert_eq!(ionex.record.map.len(), size);
+    assert_eq!(ionex.record.get(&key).unwrap().tecu(), 2.0);
+
+    // off grid
+    for (lat_ddeg, long_ddeg, alt_km) in [
+        (2.5, -15.0, SYNTHETIC_ALTITUDE_KM),
+        (5.0, -14.0, SYNTHETIC_ALTITUDE_KM),
+        (15.0, -15.0, SYNTHETIC_ALTITUDE_KM),
+        (5.0, -15.0, 450.0),
+    ] {
+        let key = Key::from_decimal_degrees_km(t1, lat_ddeg, long_ddeg, alt_km);
+
+        assert!(matches!(
+            ionex.insert_checked(key, TEC::from_tecu(2.0)),
+            Err(Error::OffGrid)
+        ));
+    }
+
+    // off temporal axis
+    let t = Epoch::from_str("2022-01-01T01:30:00 UTC").unwrap();
+    let key = Key::from_decimal_degrees_km(t, 5.0, -15.0, SYNTHETIC_ALTITUDE_KM);
+
+    assert!(matches!(
+        ionex.insert_checked(key, TEC::from_tecu(2.0)),
+        Err(Error::OffTemporalAxis)
+    ));
+
+    let t = Epoch::from_str("2022-01-01T03:00:00 UTC").unwrap();
+    let key = Key::from_decimal_degrees_km(t, 5.0, -15.0, SYNTHETIC_ALTITUDE_KM);
+
+    assert!(matches!(
+        ionex.insert_checked(key, TEC::from_tecu(2.0)),
+        Err(Error::OutsideTemporalBoundaries)
+    ));
+
+    assert_eq!(ionex.record.map.len(), size);
+}
+
+#[test]
+fn get_cell() {
+    let mut ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+    let sw = Point::new(-5.0, 0.0);
+
+    let cell = ionex
+        .record
+        .get_cell(t0, sw, -5.0, 5.0, SYNTHETIC_ALTITUDE_KM)
+        .unwrap();
+
+    assert_eq!(cell.epoch, t0);
+    assert_eq!(cell.south_west.point, sw);
+    assert_eq!(cell.north_east.point, Point::new(0.0, 5.0));
+    assert_eq!(cell.north_west.point, Point::new(-5.0, 5.0));
+    assert_eq!(cell.south_east.point, Point::new(0.0, 0.0));
+    assert!((cell.north_east.tec.tecu() - 10.5).abs() < 1.0E-9);
+
+    // identical to the cell grid
+    assert_eq!(ionex.cell_grid(t0)[2][3], Some(cell));
+
+    // outside of the grid
+    assert!(
+        ionex
+            .record
+            .get_cell(t0, Point::new(20.0, 0.0), 5.0, 5.0, SYNTHETIC_ALTITUDE_KM)
+            .is_none()
+    );
+
+    // missing corner
+    let key = Key::from_decimal_degrees_km(t0, 5.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.map.remove(&key);
+
+    assert!(
+        ionex
+            .record
+            .get_cell(t0, sw, 5.0, 5.0, SYNTHETIC_ALTITUDE_KM)
+            .is_none()
+    );
+}
+
+#[test]
+fn retain() {
+    let mut ionex = small_ionex(2, |_, lat, _| 10.0 + lat);
+
+    // TEC threshold
+    ionex.record.retain(|_, tec| tec.tecu() >= 10.0);
+
+    assert_eq!(ionex.record.map.len(), 2 * 3 * 9);
+    assert!(ionex.record.iter().all(|(k, _)| k.latitude_ddeg() >= 0.0));
+}
+
+#[test]
+fn csv_roundtrip() {
+    let mut ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 100.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    let key = Key::from_decimal_degrees_km(t0, 5.0, 10.0, SYNTHETIC_ALTITUDE_KM);
+    let tec = *ionex.record.get(&key).unwrap();
+    ionex.record.insert(key, tec.with_rms(0.25));
+
+    let missing = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.insert(missing, TEC::missing());
+
+    let mut content = Vec::<u8>::new();
+    ionex.record.to_csv(&mut content).unwrap();
+
+    let content = String::from_utf8(content).unwrap();
+    let mut lines = content.lines();
+
+    assert_eq!(
+        lines.next(),
+        Some("epoch,lat_ddeg,long_ddeg,alt_km,tecu,rms")
+    );
+
+    assert_eq!(lines.count(), 2 * 5 * 9);
+
+    assert_eq!(
+        content
+            .lines()
+            .filter(|line| line.ends_with(",0.25"))
+            .count(),
+        1
+    );
+    assert!(content.contains("2022-01-01T00:00:00 UTC,0,0,350,,\n"));
+
+    let parsed = Record::from_csv(&mut content.as_bytes()).unwrap_or_else(|e| {
+        panic!("Failed to parse CSV: {}", e);
+    });
+
+    assert!(parsed.get(&missing).unwrap().is_missing());
+    assert_eq!(parsed.map.len(), ionex.record.map.len());
+
+    for (k, v) in ionex.record.iter().filter(|(k, _)| **k != missing) {
+        assert_eq!(parsed.get(k), Some(v));
+    }
+
+    // invalid content
+    assert!(Record::from_csv(&mut "2022-01-01T00:00:00 UTC,5,10,350".as_bytes()).is_err());
+    assert!(Record::from_csv(&mut "2022-01-01T00:00:00 UTC,5,10,350,x,".as_bytes()).is_err());
+}
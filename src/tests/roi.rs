@@ -1,6 +1,15 @@
+use std::str::FromStr;
+
 use crate::{
-    prelude::{IONEX, Rect, coord},
-    tests::init_logger,
+    is_linspace_node,
+    prelude::{
+        Cell3x3, Duration, Epoch, Error, FileAttributes, Geometry, IONEX, Key, Linspace, MapCell,
+        Point, Rect, Unit, coord,
+    },
+    tests::{
+        init_logger,
+        toolkit::{SYNTHETIC_ALTITUDE_KM, small_ionex, synthetic_ionex},
+    },
 };
 
 #[test]
@@ -35,3 +44,626 @@ fn worldwide_map() {
     let bounding_rect = reduced.bounding_rect_degrees();
     assert_eq!(bounding_rect, roi);
 }
+
+#[test]
+fn cell3x3_iter() {
+    let mut ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    // 4x8 cells: 2x6 interior windows
+    let windows = ionex.cell3x3_iter(t0).collect::<Vec<_>>();
+    assert_eq!(windows.len(), 2 * 6);
+
+    for window in windows.iter() {
+        assert_eq!(window.center.epoch, t0);
+
+        let center = window.center.center();
+        let north = window.north.center();
+        let east = window.east.center();
+        let southwest = window.southwest.center();
+
+        assert_eq!(north.y() - center.y(), 5.0);
+        assert_eq!(north.x(), center.x());
+        assert_eq!(east.x() - center.x(), 5.0);
+        assert_eq!(east.y(), center.y());
+        assert_eq!(center.x() - southwest.x(), 5.0);
+        assert_eq!(center.y() - southwest.y(), 5.0);
+    }
+
+    // one missing node removes all windows sharing it
+    ionex.record.map.remove(&Key::from_decimal_degrees_km(
+        t0,
+        0.0,
+        0.0,
+        SYNTHETIC_ALTITUDE_KM,
+    ));
+
+    assert_eq!(ionex.cell3x3_iter(t0).count(), 2 * 6 - 2 * 4);
+
+    // epoch does not exist
+    let t = Epoch::from_str("2022-01-02T00:00:00 UTC").unwrap();
+    assert_eq!(ionex.cell3x3_iter(t).count(), 0);
+}
+
+#[test]
+fn cell3x3_from_map_cells() {
+    let ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+    let center = Point::new(2.5, 2.5);
+
+    // 3x3 block of grid cells, as gathered by the map iterator
+    let cells = ionex
+        .map_cell_iter()
+        .filter(|cell| {
+            let cell_center = cell.center();
+
+            cell.epoch == t0
+                && (cell_center.x() - center.x()).abs() <= 5.0
+                && (cell_center.y() - center.y()).abs() <= 5.0
+        })
+        .collect::<Vec<_>>();
+
+    let cells: [MapCell; 9] = cells.try_into().unwrap_or_else(|cells: Vec<MapCell>| {
+        panic!("expecting 9 cells, got {}", cells.len());
+    });
+
+    let cell3x3 = Cell3x3::from_slice(cells).unwrap();
+    assert_eq!(cell3x3.center.center(), center);
+
+    // the field is linear: interpolations are exact, whatever the corner labels
+    for factor in [0.5, 2.0, 3.0] {
+        let stretched = cell3x3.stretched(factor).unwrap_or_else(|e| {
+            panic!("failed to stretch by {}: {}", factor, e);
+        });
+
+        assert!((stretched.longitude_span_degrees() - 5.0 * factor).abs() < 1.0E-9);
+        assert!((stretched.latitude_span_degrees() - 5.0 * factor).abs() < 1.0E-9);
+
+        for corner in [
+            stretched.north_east,
+            stretched.north_west,
+            stretched.south_east,
+            stretched.south_west,
+        ] {
+            let (long, lat) = (corner.point.x(), corner.point.y());
+            let expected = 10.0 + lat / 10.0 + long / 10.0;
+
+            assert!(
+                (corner.tec.tecu() - expected).abs() < 1.0E-6,
+                "factor={} at {:?}: {} vs {}",
+                factor,
+                corner.point,
+                corner.tec.tecu(),
+                expected
+            );
+        }
+    }
+
+    assert!(matches!(
+        cell3x3.stretched(3.5),
+        Err(Error::OutsideSpatialBoundaries)
+    ));
+
+    // single cell interpolation, in absolute coordinates
+    let tec = cell3x3
+        .center
+        .spatial_tec_interp(Point::new(1.0, 4.0))
+        .unwrap();
+    assert!((tec.tecu() - 10.5).abs() < 1.0E-6);
+}
+
+#[test]
+fn map_cell_iter_in() {
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (87.5, -87.5, -2.5),
+        (-180.0, 180.0, 5.0),
+        |_, lat, long| 10.0 + lat / 10.0 + long / 100.0,
+    );
+
+    let bounds = Rect::new(coord! { x: 1.0, y: 40.0 }, coord! { x: 12.0, y: 46.0 });
+
+    let expected = ionex
+        .map_cell_iter()
+        .filter(|cell| {
+            let lat = [cell.north_east.point.y(), cell.south_west.point.y()];
+            let long = [cell.north_east.point.x(), cell.north_west.point.x()];
+
+            lat.iter().cloned().fold(f64::INFINITY, f64::min) <= 46.0
+                && lat.iter().cloned().fold(f64::NEG_INFINITY, f64::max) >= 40.0
+                && long.iter().cloned().fold(f64::INFINITY, f64::min) <= 12.0
+                && long.iter().cloned().fold(f64::NEG_INFINITY, f64::max) >= 1.0
+        })
+        .collect::<Vec<_>>();
+
+    let cells = ionex.map_cell_iter_in(bounds).collect::<Vec<_>>();
+
+    // [37.5; 47.5] x [0; 15]: 4 latitude and 3 longitude windows, 2 maps
+    assert_eq!(cells.len(), 4 * 3 * 2);
+    assert_eq!(cells, expected);
+
+    // bounds outside of the map
+    let bounds = Rect::new(coord! { x: 190.0, y: 0.0 }, coord! { x: 200.0, y: 10.0 });
+    assert_eq!(ionex.map_cell_iter_in(bounds).count(), 0);
+}
+
+#[test]
+fn cell_at() {
+    let ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 10.0);
+
+    let t0 = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    // 4x8 cells, south to north and west to east
+    let grid = ionex.cell_grid(t0);
+    assert_eq!(grid.len(), 4);
+    assert!(grid.iter().all(|row| row.len() == 8));
+
+    for (point, expected) in [
+        (Point::new(-17.5, -7.5), (0, 0)),
+        (Point::new(17.5, 7.5), (3, 7)),
+        (Point::new(1.0, -2.0), (1, 4)),
+        (Point::new(-20.0, 10.0), (3, 0)),
+        (Point::new(20.0, -10.0), (0, 7)),
+    ] {
+        let (cell, row, column) = ionex
+            .cell_at(t0, point)
+            .unwrap_or_else(|| panic!("no cell at {:?}", point));
+
+        assert_eq!((row, column), expected, "invalid indexes for {:?}", point);
+        assert_eq!(grid[row][column], Some(cell));
+
+        let (south_west, north_east) = (cell.south_west.point, cell.north_east.point);
+        assert!(south_west.x() <= point.x() && point.x() <= north_east.x());
+        assert!(south_west.y() <= point.y() && point.y() <= north_east.y());
+    }
+
+    // outside of the grid
+    assert!(ionex.cell_at(t0, Point::new(25.0, 0.0)).is_none());
+    assert!(ionex.cell_at(t0, Point::new(0.0, -12.0)).is_none());
+}
+
+#[test]
+fn subgrid() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let bounds = Rect::new(coord!(x: -42.0, y: -30.0), coord!(x: 40.0, y: 31.0));
+
+    // 10°x10° aligned subset
+    let subgrid = ionex.subgrid(4, 2, Some(bounds));
+
+    assert_eq!(
+        subgrid.header.grid.latitude,
+        Linspace::from((30.0, -30.0, -10.0))
+    );
+    assert_eq!(
+        subgrid.header.grid.longitude,
+        Linspace::from((-40.0, 40.0, 10.0))
+    );
+    assert_eq!(subgrid.header.grid.altitude, ionex.header.grid.altitude);
+
+    assert!(subgrid.is_regional_map());
+
+    let num_epochs = ionex.epoch_iter().count();
+    assert_eq!(subgrid.record.map.len(), num_epochs * 7 * 9);
+
+    for (key, tec) in subgrid.record.iter() {
+        assert_eq!(key.latitude_ddeg() % 10.0, 0.0);
+        assert_eq!(key.longitude_ddeg() % 10.0, 0.0);
+        assert_eq!(ionex.record.get(key), Some(tec));
+    }
+
+    // aligned with the original grid, so the sub-grid can be formatted
+    let formatted = crate::tests::toolkit::roundtrip(&subgrid);
+    assert_eq!(formatted.record, subgrid.record);
+
+    // unbounded: decimation only
+    let subgrid = ionex.subgrid(2, 3, None);
+
+    assert_eq!(
+        subgrid.header.grid.latitude,
+        Linspace::from((87.5, -87.5, -5.0))
+    );
+    assert_eq!(
+        subgrid.header.grid.longitude,
+        Linspace::from((-180.0, 180.0, 15.0))
+    );
+    assert_eq!(subgrid.attributes, ionex.attributes);
+
+    // no node within bounds
+    let outside = Rect::new(coord!(x: 1.0, y: 1.0), coord!(x: 2.0, y: 2.0));
+    let empty = ionex.subgrid(1, 1, Some(outside));
+
+    assert!(empty.record.map.is_empty());
+    assert_eq!(empty.header.grid, ionex.header.grid);
+}
+
+#[test]
+fn map_cell_corners() {
+    let ionex = small_ionex(1, |_, lat, long| 100.0 + lat + long / 100.0);
+
+    let cells = ionex.map_cell_iter().collect::<Vec<_>>();
+    assert_eq!(cells.len(), 4 * 8);
+
+    for cell in cells.iter() {
+        let corners = [
+            cell.north_east,
+            cell.north_west,
+            cell.south_east,
+            cell.south_west,
+        ];
+
+        // four distinct corners, lying on the grid
+        for (i, a) in corners.iter().enumerate() {
+            assert!(is_linspace_node(&ionex.header.grid.latitude, a.point.y()));
+            assert!(is_linspace_node(&ionex.header.grid.longitude, a.point.x()));
+
+            for b in corners.iter().skip(i + 1) {
+                assert_ne!(a.point, b.point);
+            }
+
+            // each corner holds the TEC of its own node
+            let expected = 100.0 + a.point.y() + a.point.x() / 100.0;
+            assert!((a.tec.tecu() - expected).abs() < 1e-6);
+        }
+
+        assert_eq!(cell.north_east.point.x(), cell.south_east.point.x());
+        assert_eq!(cell.north_west.point.x(), cell.south_west.point.x());
+        assert_eq!(cell.north_east.point.y(), cell.north_west.point.y());
+        assert_eq!(cell.south_east.point.y(), cell.south_west.point.y());
+    }
+}
+
+#[test]
+fn reindex_to_grid() {
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (0.0, 360.0, 30.0),
+        |_, lat, long| 10.0 + lat / 10.0 + long / 100.0,
+    );
+
+    let t0 = ionex.header.epoch_of_first_map;
+    let original = ionex.clone();
+
+    let mut relabeled = ionex.header.grid;
+    relabeled.longitude = Linspace::from((-180.0, 180.0, 30.0));
+
+    ionex.reindex_to_grid(relabeled).unwrap();
+
+    assert_eq!(ionex.header.grid, relabeled);
+    assert_eq!(ionex.record.map.len(), original.record.map.len());
+
+    // nodes are re-mapped by position
+    for (long_ddeg, relabeled_ddeg) in [(0.0, -180.0), (90.0, -90.0), (360.0, 180.0)] {
+        let before = Key::from_decimal_degrees_km(t0, 5.0, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+        let after = Key::from_decimal_degrees_km(t0, 5.0, relabeled_ddeg, SYNTHETIC_ALTITUDE_KM);
+        assert_eq!(ionex.record.get(&after), original.record.get(&before));
+    }
+
+    // node count mismatch
+    let mut mismatch = ionex.header.grid;
+    mismatch.longitude = Linspace::from((-180.0, 180.0, 5.0));
+
+    assert!(matches!(
+        ionex.reindex_to_grid(mismatch),
+        Err(Error::GridMismatch(_))
+    ));
+
+    assert_eq!(ionex.header.grid, relabeled);
+}
+
+#[test]
+fn roi_at() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let mut ionex = small_ionex(2, |t, lat, long| {
+        10.0 + lat + long / 10.0 + (t - t_ref).to_unit(Unit::Hour)
+    });
+
+    let t0 = ionex.header.epoch_of_first_map;
+    let t1 = ionex.header.epoch_of_last_map;
+
+    let tecu = |hours: f64, lat: f64, long: f64| 10.0 + lat + long / 10.0 + hours;
+
+    let corners = |cell: &MapCell| {
+        [
+            cell.north_east,
+            cell.north_west,
+            cell.south_east,
+            cell.south_west,
+        ]
+        .map(|corner| (corner.point.x(), corner.point.y()))
+    };
+
+    // within a single map cell
+    let cell = ionex
+        .roi_at(t0, Geometry::Point(Point::new(2.0, 3.0)))
+        .unwrap();
+
+    assert_eq!(cell.epoch, t0);
+    assert_eq!(
+        corners(&cell),
+        [(5.0, 5.0), (0.0, 5.0), (5.0, 0.0), (0.0, 0.0)]
+    );
+
+    for corner in [
+        cell.north_east,
+        cell.north_west,
+        cell.south_east,
+        cell.south_west,
+    ] {
+        let expected = tecu(0.0, corner.point.y(), corner.point.x());
+        assert!((corner.tec.tecu() - expected).abs() < 1.0E-6);
+    }
+
+    // single node: wrapped by the south western cell
+    let cell = ionex
+        .roi_at(t0, Geometry::Point(Point::new(5.0, 5.0)))
+        .unwrap();
+
+    assert_eq!(
+        corners(&cell),
+        [(5.0, 5.0), (0.0, 5.0), (5.0, 0.0), (0.0, 0.0)]
+    );
+
+    // spans several grid steps
+    let roi = Rect::new(coord! { x: 1.0, y: -7.0 }, coord! { x: 12.0, y: 4.0 });
+    let cell = ionex.roi_at(t1, Geometry::Rect(roi)).unwrap();
+
+    assert_eq!(cell.epoch, t1);
+    assert_eq!(
+        corners(&cell),
+        [(15.0, 5.0), (0.0, 5.0), (15.0, -10.0), (0.0, -10.0)]
+    );
+
+    assert!(cell.contains(&Geometry::Rect(roi)));
+
+    // in between two maps
+    let t = t0 + Duration::from_minutes(30.0);
+    let cell = ionex.roi_at(t, Geometry::Rect(roi)).unwrap();
+
+    assert_eq!(cell.epoch, t);
+
+    for corner in [
+        cell.north_east,
+        cell.north_west,
+        cell.south_east,
+        cell.south_west,
+    ] {
+        let expected = tecu(0.5, corner.point.y(), corner.point.x());
+        assert!((corner.tec.tecu() - expected).abs() < 1.0E-6);
+    }
+
+    // errors
+    assert!(matches!(
+        ionex.roi_at(t0, Geometry::Point(Point::new(30.0, 0.0))),
+        Err(Error::OutsideSpatialBoundaries)
+    ));
+
+    assert!(matches!(
+        ionex.roi_at(
+            t1 + Duration::from_hours(1.0),
+            Geometry::Point(Point::new(2.0, 3.0))
+        ),
+        Err(Error::OutsideTemporalBoundaries)
+    ));
+
+    let key = Key::from_decimal_degrees_km(t0, 5.0, 5.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&key).is_some());
+
+    assert!(matches!(
+        ionex.roi_at(t0, Geometry::Point(Point::new(2.0, 3.0))),
+        Err(Error::IncompleteCell)
+    ));
+}
+
+#[test]
+fn retain_within_rect() {
+    let mut ionex = small_ionex(2, |_, lat, long| 10.0 + lat / 10.0 + long / 100.0);
+
+    let original = ionex.clone();
+
+    // nothing to retain: unchanged
+    let outside = Rect::new(coord! { x: 30.0, y: 30.0 }, coord! { x: 40.0, y: 40.0 });
+
+    assert!(matches!(
+        ionex.retain_within_rect(outside),
+        Err(Error::OutsideSpatialBoundaries)
+    ));
+
+    assert_eq!(ionex, original);
+
+    let rect = Rect::new(coord! { x: -7.0, y: -3.0 }, coord! { x: 10.0, y: 6.0 });
+    ionex.retain_within_rect(rect).unwrap();
+
+    // [0; 5] x [-5; 10]
+    assert_eq!(ionex.record.map.len(), 2 * 2 * 4);
+
+    assert_eq!(ionex.header.grid.latitude, Linspace::from((5.0, 0.0, -5.0)));
+    assert_eq!(
+        ionex.header.grid.longitude,
+        Linspace::from((-5.0, 10.0, 5.0))
+    );
+
+    for (k, tec) in ionex.record.iter() {
+        assert!(k.latitude_ddeg() >= 0.0 && k.latitude_ddeg() <= 5.0);
+        assert!(k.longitude_ddeg() >= -5.0 && k.longitude_ddeg() <= 10.0);
+        assert_eq!(original.record.get(k), Some(tec));
+    }
+}
+
+#[test]
+fn map_cell_iter_wrapped() {
+    // worldwide map that does not close the globe: 175° -> -180° step is missing
+    let mut ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (87.5, -87.5, -2.5),
+        (-180.0, 175.0, 5.0),
+        |_, lat, long| 20.0 + lat / 10.0 + long.to_radians().cos(),
+    );
+
+    ionex.attributes = Some(FileAttributes::default());
+    assert!(ionex.is_worldwide_map());
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    let num_cells = ionex.map_cell_iter().count();
+    let num_wrapped = ionex.map_cell_iter_wrapped().count();
+
+    // one wraparound cell per latitude window and per map
+    assert_eq!(num_wrapped, num_cells + 70 * 2);
+
+    let wraparound = ionex
+        .map_cell_iter_wrapped()
+        .skip(num_cells)
+        .collect::<Vec<_>>();
+
+    for cell in wraparound.iter() {
+        assert_eq!(cell.north_west.point.x(), 175.0);
+        assert_eq!(cell.south_west.point.x(), 175.0);
+        assert_eq!(cell.north_east.point.x(), 180.0);
+        assert_eq!(cell.south_east.point.x(), 180.0);
+
+        let lat = cell.north_east.point.y();
+        let expected = 20.0 + lat / 10.0 + (-180.0_f64).to_radians().cos();
+        assert!((cell.north_east.tec.tecu() - expected).abs() < 1.0E-3);
+    }
+
+    // the seam can now be interpolated
+    let tec0 = ionex.interpolate_tec(t0, 0.0, 175.0).unwrap();
+    let tec1 = ionex.interpolate_tec(t0, 0.0, -180.0).unwrap();
+
+    for (long, weight) in [(177.5, 0.5), (179.0, 0.8), (-181.0, 0.8), (539.0, 0.8)] {
+        let tec = ionex.interpolate_tec(t0, 0.0, long).unwrap_or_else(|e| {
+            panic!("failed to interpolate at longitude {}: {}", long, e);
+        });
+
+        let expected = tec0.tecu() * (1.0 - weight) + tec1.tecu() * weight;
+        assert!((tec.tecu() - expected).abs() < 1.0E-6);
+    }
+
+    // the wraparound only depends on the grid
+    ionex.attributes = None;
+    assert_eq!(ionex.map_cell_iter_wrapped().count(), num_wrapped);
+
+    // regional maps are not wrapped
+    let regional = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (87.5, -87.5, -2.5),
+        (-180.0, 170.0, 5.0),
+        |_, lat, long| 20.0 + lat / 10.0 + long.to_radians().cos(),
+    );
+
+    assert_eq!(
+        regional.map_cell_iter_wrapped().count(),
+        regional.map_cell_iter().count()
+    );
+
+    assert!(matches!(
+        regional.interpolate_tec(t0, 0.0, 175.0),
+        Err(Error::OutsideSpatialBoundaries)
+    ));
+
+    // 0..355° grid: the wraparound step crosses the 0° meridian
+    let shifted = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        1,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (0.0, 355.0, 5.0),
+        |_, lat, long| 20.0 + lat / 10.0 + long / 100.0,
+    );
+
+    let num_cells = shifted.map_cell_iter().count();
+
+    let wraparound = shifted
+        .map_cell_iter_wrapped()
+        .skip(num_cells)
+        .collect::<Vec<_>>();
+
+    assert_eq!(wraparound.len(), 4);
+
+    for cell in wraparound.iter() {
+        assert_eq!(cell.north_west.point.x(), 355.0);
+        assert_eq!(cell.south_west.point.x(), 355.0);
+        assert_eq!(cell.north_east.point.x(), 360.0);
+        assert_eq!(cell.south_east.point.x(), 360.0);
+
+        // eastern corners are the 0° nodes
+        let lat = cell.north_east.point.y();
+        assert!((cell.north_east.tec.tecu() - (20.0 + lat / 10.0)).abs() < 1.0E-3);
+        assert!((cell.north_west.tec.tecu() - (23.55 + lat / 10.0)).abs() < 1.0E-3);
+
+        let point = Point::new(357.5, lat);
+        let tec = cell.spatial_tec_interp(point).unwrap();
+        assert!((tec.tecu() - (21.775 + lat / 10.0)).abs() < 1.0E-3);
+
+        let interpolated = shifted.interpolate_tec(cell.epoch, lat, 357.5).unwrap();
+        assert!((interpolated.tecu() - tec.tecu()).abs() < 1.0E-6);
+    }
+}
+
+#[test]
+fn regional_longitude_conventions() {
+    let ionex = synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        2,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (-120.0, -80.0, 5.0),
+        |_, lat, long| 20.0 + lat / 10.0 + long / 10.0,
+    );
+
+    assert!(ionex.is_regional_map());
+
+    // only the covered span is remapped
+    let shifted = ionex.to_zero_to_360();
+
+    assert!(shifted.is_regional_map());
+    assert_eq!(
+        shifted.header.grid.longitude,
+        Linspace::from((240.0, 280.0, 5.0))
+    );
+    assert_eq!(shifted.header.grid.latitude, ionex.header.grid.latitude);
+    assert_eq!(shifted.record.map.len(), ionex.record.map.len());
+
+    for (k, v) in ionex.record.iter() {
+        let key = Key::from_decimal_degrees_km(
+            k.epoch,
+            k.latitude_ddeg(),
+            k.longitude_ddeg() + 360.0,
+            k.altitude_km(),
+        );
+
+        assert_eq!(shifted.record.get(&key), Some(v));
+    }
+
+    let symmetric = shifted.to_symmetric_longitude();
+    assert_eq!(symmetric.header.grid, ionex.header.grid);
+    assert_eq!(symmetric.record, ionex.record);
+
+    // span crossing the 0° meridian remains contiguous
+    let ionex = small_ionex(1, |_, lat, long| 20.0 + lat / 10.0 + long / 10.0);
+
+    let shifted = ionex.to_zero_to_360();
+
+    assert!(shifted.is_regional_map());
+    assert_eq!(
+        shifted.header.grid.longitude,
+        Linspace::from((340.0, 380.0, 5.0))
+    );
+    assert_eq!(shifted.record.map.len(), ionex.record.map.len());
+    assert_eq!(shifted.to_symmetric_longitude().record, ionex.record);
+}
@@ -0,0 +1,224 @@
+use std::str::FromStr;
+
+use crate::{
+    prelude::{Duration, Epoch, IONEX, Key, TEC, Unit},
+    tests::toolkit::{SYNTHETIC_ALTITUDE_KM, small_ionex},
+};
+
+#[test]
+fn temporal_variance() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let mut ionex = small_ionex(4, |t, lat, long| {
+        if lat == 0.0 && long == 0.0 {
+            // 12, 8, 12, 8
+            let hours = (t - t_ref).to_unit(Unit::Hour).round() as i64;
+            if hours % 2 == 0 { 12.0 } else { 8.0 }
+        } else {
+            10.0 + lat / 10.0
+        }
+    });
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // one node is sampled only once
+    for epoch in ionex.epoch_iter().skip(1).collect::<Vec<_>>() {
+        let key = Key::from_decimal_degrees_km(epoch, -5.0, -5.0, SYNTHETIC_ALTITUDE_KM);
+        ionex.record.map.remove(&key);
+    }
+
+    let variance = ionex.temporal_variance();
+
+    assert_eq!(variance.map.len(), 5 * 9 - 1);
+    assert!(variance.iter().all(|(k, _)| k.epoch == t0));
+
+    let oscillating = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    let oscillating = variance.get(&oscillating).unwrap();
+
+    // mean is 10, deviations are all 2: 4 * 2² / (4 - 1)
+    assert!((oscillating.tecu() - 16.0 / 3.0).abs() < 1.0E-6);
+
+    let constant = Key::from_decimal_degrees_km(t0, 5.0, 5.0, SYNTHETIC_ALTITUDE_KM);
+    assert_eq!(variance.get(&constant).unwrap().tecu(), 0.0);
+
+    let single = Key::from_decimal_degrees_km(t0, -5.0, -5.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(variance.get(&single).is_none());
+
+    // non finite samples are skipped
+    let t_last = ionex.header.epoch_of_last_map;
+
+    let key = Key::from_decimal_degrees_km(t_last, 5.0, 5.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.insert(key, TEC::missing());
+
+    let key = Key::from_decimal_degrees_km(t_last, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.insert(key, TEC::from_tecu(f64::NAN));
+
+    let variance = ionex.temporal_variance();
+
+    assert_eq!(variance.map.len(), 5 * 9 - 1);
+
+    let constant = Key::from_decimal_degrees_km(t0, 5.0, 5.0, SYNTHETIC_ALTITUDE_KM);
+    assert_eq!(variance.get(&constant).unwrap().tecu(), 0.0);
+
+    // 12, 8, 12: mean is 32/3
+    let oscillating = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    let expected = ((4.0 / 3.0_f64).powi(2) * 2.0 + (8.0 / 3.0_f64).powi(2)) / 2.0;
+    assert!((variance.get(&oscillating).unwrap().tecu() - expected).abs() < 1.0E-6);
+}
+
+#[test]
+fn epoch_summary() {
+    let mut ionex = small_ionex(3, |_, lat, long| 20.0 + lat / 10.0 + long / 10.0);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // punch one node of the first map
+    let punched = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&punched).is_some());
+
+    let summary = ionex.epoch_summary();
+
+    assert_eq!(summary.len(), ionex.header.number_of_maps as usize);
+    assert_eq!(summary.len(), ionex.epoch_iter().count());
+
+    for (nth, row) in summary.iter().enumerate() {
+        assert_eq!(row.epoch, t0 + Duration::from_hours(nth as f64));
+        assert!((row.min.unwrap() - 16.0).abs() < 1e-6);
+        assert!((row.max.unwrap() - 24.0).abs() < 1e-6);
+
+        // same conventions as the synchronous accessors
+        assert_eq!(row.min, ionex.synchronous_tec_min(row.epoch));
+        assert_eq!(row.max, ionex.synchronous_tec_max(row.epoch));
+        assert_eq!(row.mean, ionex.synchronous_tec_mean(row.epoch));
+
+        if nth == 0 {
+            assert!((row.coverage_fraction - 44.0 / 45.0).abs() < 1e-9);
+        } else {
+            assert_eq!(row.coverage_fraction, 1.0);
+            assert!((row.mean.unwrap() - 20.0).abs() < 1e-6);
+        }
+    }
+
+    // map without any finite value
+    let t_last = ionex.header.epoch_of_last_map;
+
+    for (_, tec) in ionex.record.synchronous_iter_mut(t_last) {
+        *tec = TEC::missing();
+    }
+
+    let last = ionex.epoch_summary().pop().unwrap();
+
+    assert_eq!(last.epoch, t_last);
+    assert_eq!(last.coverage_fraction, 0.0);
+    assert!(last.min.is_none() && last.max.is_none() && last.mean.is_none());
+}
+
+#[test]
+fn gradient_magnitude_map() {
+    let mut ionex = small_ionex(2, |_, lat, long| 20.0 + 0.4 * lat + 0.3 * long);
+
+    let t0 = ionex.header.epoch_of_first_map;
+
+    // holes are handled by one-sided differences
+    let punched = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    assert!(ionex.record.map.remove(&punched).is_some());
+
+    let gradient = ionex.gradient_magnitude_map(t0);
+
+    assert_eq!(gradient.map.len(), 5 * 9 - 1);
+    assert!(gradient.iter().all(|(k, _)| k.epoch == t0));
+
+    // linear ramp: constant 0.5 TECu/° gradient, edges included
+    for (_, tec) in gradient.iter() {
+        assert!((tec.tecu() - 0.5).abs() < 1.0E-6);
+    }
+}
+
+#[test]
+fn tec_statistics() {
+    let t_ref = Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap();
+
+    let mut ionex = small_ionex(2, |t, lat, _| {
+        // 10 + [-1; 1] then 20 + [-1; 1]
+        10.0 * (1.0 + (t - t_ref).to_unit(Unit::Hour)) + lat / 10.0
+    });
+
+    let (t0, t1) = (
+        ionex.header.epoch_of_first_map,
+        ionex.header.epoch_of_last_map,
+    );
+
+    // explicit missing values are skipped
+    let missing = Key::from_decimal_degrees_km(t0, 0.0, 0.0, SYNTHETIC_ALTITUDE_KM);
+    ionex.record.insert(missing, TEC::missing());
+
+    assert!((ionex.tec_min().unwrap() - 9.0).abs() < 1.0E-6);
+    assert!((ionex.tec_max().unwrap() - 21.0).abs() < 1.0E-6);
+
+    // (45 * 10 - 10) + 45 * 20
+    assert!((ionex.tec_mean().unwrap() - 1340.0 / 89.0).abs() < 1.0E-6);
+
+    assert!((ionex.synchronous_tec_min(t0).unwrap() - 9.0).abs() < 1.0E-6);
+    assert!((ionex.synchronous_tec_max(t0).unwrap() - 11.0).abs() < 1.0E-6);
+    assert!((ionex.synchronous_tec_mean(t0).unwrap() - 10.0).abs() < 1.0E-6);
+    assert!((ionex.synchronous_tec_mean(t1).unwrap() - 20.0).abs() < 1.0E-6);
+
+    // 9 nodes per latitude: deviations are (±1, ±0.5, 0) TECu
+    let expected = (9.0 * 2.0 * (1.0 + 0.25) / (5.0 * 9.0 - 1.0)).sqrt();
+    assert!((ionex.synchronous_tec_std_dev(t1).unwrap() - expected).abs() < 1.0E-6);
+
+    assert!(ionex.tec_std_dev().unwrap() > 5.0);
+
+    // no data
+    let t = t1 + Duration::from_hours(1.0);
+
+    assert!(ionex.synchronous_tec_min(t).is_none());
+    assert!(ionex.synchronous_tec_max(t).is_none());
+    assert!(ionex.synchronous_tec_mean(t).is_none());
+    assert!(ionex.synchronous_tec_std_dev(t).is_none());
+}
+
+#[test]
+fn ckmg_tec_statistics() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let values = ionex
+        .record
+        .iter()
+        .map(|(_, tec)| tec.tecu())
+        .filter(|tecu| tecu.is_finite())
+        .collect::<Vec<_>>();
+
+    let (min, max, mean) = (
+        ionex.tec_min().unwrap(),
+        ionex.tec_max().unwrap(),
+        ionex.tec_mean().unwrap(),
+    );
+
+    assert!(min >= 0.0);
+    assert!(min <= mean && mean <= max);
+
+    assert_eq!(min, values.iter().cloned().fold(f64::INFINITY, f64::min));
+    assert_eq!(
+        max,
+        values.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+    );
+
+    let expected_mean = values.iter().sum::<f64>() / values.len() as f64;
+    assert!((mean - expected_mean).abs() < 1.0E-6);
+
+    let std_dev = ionex.tec_std_dev().unwrap();
+    assert!(std_dev > 0.0 && std_dev < max - min);
+
+    for epoch in ionex.epoch_iter() {
+        let (min_t, max_t, mean_t) = (
+            ionex.synchronous_tec_min(epoch).unwrap(),
+            ionex.synchronous_tec_max(epoch).unwrap(),
+            ionex.synchronous_tec_mean(epoch).unwrap(),
+        );
+
+        assert!(min <= min_t && min_t <= mean_t && mean_t <= max_t && max_t <= max);
+    }
+}
@@ -1,14 +1,117 @@
 use log::info;
-use std::str::FromStr;
+
+use std::{
+    io::{BufReader, BufWriter},
+    str::FromStr,
+};
 
 use crate::{
     coordinates::QuantizedCoordinates,
-    prelude::{Epoch, Header, IONEX, Key},
+    prelude::{Duration, Epoch, Header, IONEX, Key, Linspace, ParsingError, Record, TEC},
 };
 
+/// Fixed altitude of all synthetic maps, in kilometers.
+pub const SYNTHETIC_ALTITUDE_KM: f64 = 350.0;
+
+/// Builds a synthetic 2D [IONEX] starting at `t0` (UTC),
+/// made of `num_maps` complete maps spaced by `sampling_period`,
+/// on the (start, end, spacing) latitude and longitude grids.
+/// Each node is valued by `tecu(epoch, latitude_ddeg, longitude_ddeg)`.
+pub fn synthetic_ionex<F: Fn(Epoch, f64, f64) -> f64>(
+    t0: &str,
+    num_maps: usize,
+    sampling_period: Duration,
+    latitude: (f64, f64, f64),
+    longitude: (f64, f64, f64),
+    tecu: F,
+) -> IONEX {
+    let t0 = Epoch::from_str(t0).unwrap();
+    let t_last = t0 + sampling_period * (num_maps - 1) as f64;
+
+    let mut header = Header::default()
+        .with_epoch_of_first_map(t0)
+        .with_epoch_of_last_map(t_last)
+        .with_number_of_maps(num_maps as u32)
+        .with_latitude_grid(Linspace::from(latitude))
+        .with_longitude_grid(Linspace::from(longitude))
+        .with_altitude_grid(Linspace::from((
+            SYNTHETIC_ALTITUDE_KM,
+            SYNTHETIC_ALTITUDE_KM,
+            0.0,
+        )));
+
+    header.sampling_period = sampling_period;
+
+    let num_latitudes = ((latitude.1 - latitude.0) / latitude.2).round() as usize + 1;
+    let num_longitudes = ((longitude.1 - longitude.0) / longitude.2).round() as usize + 1;
+
+    let mut record = Record::default();
+
+    for nth_map in 0..num_maps {
+        let epoch = t0 + sampling_period * nth_map as f64;
+
+        for i in 0..num_latitudes {
+            let lat_ddeg = latitude.0 + i as f64 * latitude.2;
+
+            for j in 0..num_longitudes {
+                let long_ddeg = longitude.0 + j as f64 * longitude.2;
+
+                let key =
+                    Key::from_decimal_degrees_km(epoch, lat_ddeg, long_ddeg, SYNTHETIC_ALTITUDE_KM);
+
+                record.insert(key, TEC::from_tecu(tecu(epoch, lat_ddeg, long_ddeg)));
+            }
+        }
+    }
+
+    IONEX::new(header, record)
+}
+
+/// Builds a small synthetic 2D [IONEX] with [synthetic_ionex], on the default test grid:
+/// latitudes 10°..-10° and longitudes -20°..20° (5° spacing), starting on 2022-01-01 at
+/// midnight (UTC), made of `num_maps` hourly maps.
+pub fn small_ionex<F: Fn(Epoch, f64, f64) -> f64>(num_maps: usize, tecu: F) -> IONEX {
+    synthetic_ionex(
+        "2022-01-01T00:00:00 UTC",
+        num_maps,
+        Duration::from_hours(1.0),
+        (10.0, -10.0, -5.0),
+        (-20.0, 20.0, 5.0),
+        tecu,
+    )
+}
+
+/// Formats this [IONEX] into a readable [String].
+pub fn format_to_string(ionex: &IONEX) -> String {
+    let mut writer = BufWriter::new(Vec::<u8>::new());
+
+    ionex.format(&mut writer).unwrap_or_else(|e| {
+        panic!("failed to format IONEX: {}", e);
+    });
+
+    let bytes = writer.into_inner().unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+/// Parses [IONEX] from readable content.
+pub fn parse_str(content: &str) -> Result<IONEX, ParsingError> {
+    let mut reader = BufReader::new(content.as_bytes());
+    IONEX::parse(&mut reader)
+}
+
+/// Formats this [IONEX] into memory and parses it back.
+pub fn roundtrip(ionex: &IONEX) -> IONEX {
+    let content = format_to_string(ionex);
+
+    parse_str(&content).unwrap_or_else(|e| {
+        panic!("failed to parse back IONEX: {}", e);
+    })
+}
+
 /// Verifies two [Header]s are strictly identical
 pub fn generic_header_comparison(dut: &Header, model: &Header) {
     assert_eq!(dut.version, model.version);
+    assert_eq!(dut.file_type(), model.file_type());
     assert_eq!(dut.comments, model.comments);
     assert_eq!(dut.program, model.program);
     assert_eq!(dut.run_by, model.run_by);
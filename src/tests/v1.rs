@@ -1,8 +1,10 @@
 use crate::{
-    prelude::{Duration, IONEX, MappingFunction, Rect, Version, coord},
+    prelude::{
+        Duration, Grid, IONEX, Key, Linspace, MappingFunction, Record, Rect, TEC, Version, coord,
+    },
     tests::{
         init_logger,
-        toolkit::{TestPoint, generic_comparison, generic_test},
+        toolkit::{TestPoint, generic_comparison, generic_test, parse_str, roundtrip},
     },
 };
 
@@ -224,6 +226,7 @@ fn parse_ckmg0020() {
 
     assert_eq!(ionex.header.elevation_cutoff, 0.0);
     assert_eq!(ionex.header.exponent, -1);
+    assert_eq!(ionex.header.exponent(), -1);
 
     assert_eq!(
         ionex.bounding_rect_degrees(),
@@ -263,6 +266,204 @@ fn parse_ckmg0020() {
     generic_comparison(&parsed, &ionex);
 }
 
+#[test]
+fn ckmg0020_grid_accessors() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let latitude = Linspace::from((87.5, -87.5, -2.5));
+    let longitude = Linspace::from((-180.0, 180.0, 5.0));
+    let altitude = Linspace::from((350.0, 350.0, 0.0));
+
+    assert_eq!(ionex.latitude_grid(), latitude);
+    assert_eq!(ionex.longitude_grid(), longitude);
+    assert_eq!(ionex.altitude_grid(), altitude);
+    assert_eq!(ionex.grid_spacing_degrees(), (-2.5, 5.0));
+    assert_eq!(ionex.latitude_span_degrees(), 175.0);
+    assert_eq!(ionex.longitude_span_degrees(), 360.0);
+    assert_eq!(ionex.altitude_width_km(), 0.0);
+
+    // grid is preserved by formatting
+    let parsed = roundtrip(&ionex);
+
+    assert_eq!(parsed.latitude_grid(), latitude);
+    assert_eq!(parsed.longitude_grid(), longitude);
+    assert_eq!(parsed.altitude_grid(), altitude);
+    assert_eq!(parsed.grid_spacing_degrees(), (-2.5, 5.0));
+}
+
+#[test]
+fn ckmg0020_ascending_latitude() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let ascending = ionex.with_ascending_latitude();
+
+    assert_eq!(ascending.header.grid.latitude.start, -87.5);
+    assert_eq!(ascending.header.grid.latitude.end, 87.5);
+    assert_eq!(ascending.header.grid.latitude.spacing, 2.5);
+
+    let parsed = roundtrip(&ascending);
+
+    assert_eq!(parsed.header.grid.latitude, ascending.header.grid.latitude);
+    assert_eq!(parsed.record, ionex.record);
+
+    // back to the original convention
+    let descending = parsed.with_descending_latitude();
+    assert_eq!(descending.header.grid, ionex.header.grid);
+}
+
+#[test]
+fn ckmg0020_grid_from_record() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let grid = Grid::from_record(&ionex.record).unwrap_or_else(|e| {
+        panic!("Failed to infer CKMG0020 grid: {}", e);
+    });
+
+    assert_eq!(grid, ionex.header.grid);
+
+    // non uniform spacing
+    let mut record = ionex.record.clone();
+    let epoch = ionex.header.epoch_of_first_map;
+
+    record.insert(
+        Key::from_decimal_degrees_km(epoch, 86.0, -180.0, 350.0),
+        TEC::from_tecu(10.0),
+    );
+
+    assert!(Grid::from_record(&record).is_err());
+    assert!(Grid::from_record(&Record::default()).is_err());
+}
+
+#[test]
+fn ckmg0020_grid_signature() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let parsed = roundtrip(&ionex);
+
+    assert_eq!(
+        ionex.header.grid.signature(),
+        parsed.header.grid.signature()
+    );
+    assert!(ionex.header.grid.same_grid_as(&parsed.header.grid));
+
+    let mut resampled = ionex.header.grid;
+    resampled.latitude = resampled.latitude.resampled(2.0).unwrap();
+
+    assert_ne!(ionex.header.grid.signature(), resampled.signature());
+    assert!(!ionex.header.grid.same_grid_as(&resampled));
+}
+
+#[test]
+fn ckmg0020_grid_snap() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let grid = ionex.header.grid;
+
+    assert_eq!(grid.snap(87.49, 0.0, 350.0), Some((87.5, 0.0, 350.0)));
+    assert_eq!(grid.snap(86.0, -177.6, 350.0), Some((85.0, -180.0, 350.0)));
+    assert_eq!(grid.snap(-87.5, 180.0, 350.0), Some((-87.5, 180.0, 350.0)));
+    assert_eq!(grid.snap(1.3, 2.6, 350.0), Some((2.5, 5.0, 350.0)));
+
+    // outside
+    assert_eq!(grid.snap(88.0, 0.0, 350.0), None);
+    assert_eq!(grid.snap(0.0, 181.0, 350.0), None);
+    assert_eq!(grid.snap(0.0, 0.0, 450.0), None);
+}
+
+#[test]
+fn ckmg0020_format_with_header() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let header = ionex.header.with_program("ionex-rs");
+
+    let mut writer = BufWriter::new(Vec::<u8>::new());
+
+    ionex
+        .format_with_header(&header, &mut writer)
+        .unwrap_or_else(|e| {
+            panic!("Failed to re-header CKMG0020: {}", e);
+        });
+
+    let content = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+    let parsed = parse_str(&content).unwrap_or_else(|e| {
+        panic!("Failed to parse re-headered CKMG0020: {}", e);
+    });
+
+    assert_eq!(parsed.header.program, Some("ionex-rs".to_string()));
+    assert_eq!(parsed.record, ionex.record);
+
+    // grid must describe the record
+    let header = ionex.header.with_latitude_grid(Linspace {
+        start: 85.0,
+        end: -85.0,
+        spacing: -2.5,
+    });
+
+    let mut writer = BufWriter::new(Vec::<u8>::new());
+    assert!(ionex.format_with_header(&header, &mut writer).is_err());
+}
+
+#[test]
+fn ckmg0020_zero_to_360() {
+    let ionex = IONEX::from_gzip_file("data/IONEX/V1/CKMG0020.22I.gz").unwrap_or_else(|e| {
+        panic!("Failed to parse CKMG0020: {}", e);
+    });
+
+    let shifted = ionex.to_zero_to_360();
+
+    assert_eq!(shifted.header.grid.longitude.start, 0.0);
+    assert_eq!(shifted.header.grid.longitude.end, 360.0);
+    assert_eq!(shifted.header.grid.longitude.spacing, 5.0);
+    assert_eq!(shifted.header.grid.latitude, ionex.header.grid.latitude);
+
+    for (k, v) in ionex.record.iter() {
+        let long_ddeg = k.longitude_ddeg();
+
+        // -180° and 180° describe the same meridian
+        if long_ddeg == -180.0 {
+            continue;
+        }
+
+        let key = Key::from_decimal_degrees_km(
+            k.epoch,
+            k.latitude_ddeg(),
+            long_ddeg.rem_euclid(360.0),
+            k.altitude_km(),
+        );
+
+        assert_eq!(shifted.record.get(&key), Some(v));
+
+        if long_ddeg == 0.0 {
+            let key =
+                Key::from_decimal_degrees_km(k.epoch, k.latitude_ddeg(), 360.0, k.altitude_km());
+            assert_eq!(shifted.record.get(&key), Some(v));
+        }
+    }
+
+    let symmetric = shifted.to_symmetric_longitude();
+
+    assert_eq!(symmetric.header.grid, ionex.header.grid);
+
+    for (k, v) in ionex.record.iter() {
+        if k.longitude_ddeg() != -180.0 {
+            assert_eq!(symmetric.record.get(k), Some(v));
+        }
+    }
+}
+
 #[test]
 fn parse_jplg() {
     init_logger();